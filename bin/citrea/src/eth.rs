@@ -1,18 +1,23 @@
 use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow::Context as _;
-use ethereum_rpc::{EthRpcConfig, FeeHistoryCacheConfig, GasPriceOracleConfig};
+use ethereum_rpc::{EthRpcConfig, FeeHistoryCacheConfig, FilterConfig, GasPriceOracleConfig};
+use sov_db::ledger_db::LedgerDB;
 use sov_modules_api::default_context::DefaultContext;
 use sov_prover_storage_manager::SnapshotManager;
 use sov_rollup_interface::services::da::DaService;
 use sov_state::ProverStorage;
+use sov_stf_runner::RpcConfig;
 
 // register ethereum methods.
 pub(crate) fn register_ethereum<Da: DaService>(
     da_service: Da,
     storage: ProverStorage<sov_state::DefaultStorageSpec, SnapshotManager>,
+    ledger_db: &LedgerDB,
     methods: &mut jsonrpsee::RpcModule<()>,
-    sequencer_client_url: Option<String>,
+    rpc_config: &RpcConfig,
+    sequencer_client_urls: Option<Vec<String>>,
 ) -> Result<(), anyhow::Error> {
     let eth_rpc_config = {
         let eth_signer = eth_dev_signer();
@@ -20,18 +25,29 @@ pub(crate) fn register_ethereum<Da: DaService>(
             eth_signer,
             gas_price_oracle_config: GasPriceOracleConfig::default(),
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
+            filter_config: FilterConfig {
+                max_filters_per_connection: rpc_config.max_filters_per_connection,
+                max_filters: rpc_config.max_filters,
+                idle_timeout: Duration::from_secs(rpc_config.filter_idle_timeout_secs),
+            },
         }
     };
 
-    let ethereum_rpc = ethereum_rpc::get_ethereum_rpc::<DefaultContext, Da>(
+    let ethereum_rpc = ethereum_rpc::get_ethereum_rpc::<DefaultContext, Da, LedgerDB>(
         da_service,
         eth_rpc_config,
         storage,
-        sequencer_client_url,
+        ledger_db.clone(),
+        ledger_db.subscribe_soft_batches(),
+        sequencer_client_urls,
     );
     methods
         .merge(ethereum_rpc)
-        .context("Failed to merge Ethereum RPC modules")
+        .context("Failed to merge Ethereum RPC modules")?;
+
+    // Runs last, so that block taking methods of both the runtime and ethereum RPC are wrapped
+    ethereum_rpc::resolve_block_tags(methods, ledger_db.clone())
+        .context("Failed to register safe and finalized block tags")
 }
 
 // TODO: #840
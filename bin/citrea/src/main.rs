@@ -11,7 +11,10 @@ use sov_modules_api::Spec;
 use sov_modules_rollup_blueprint::RollupBlueprint;
 use sov_state::storage::NativeStorage;
 use sov_stf_runner::{from_toml_path, FullNodeConfig, ProverConfig};
-use tracing::{error, instrument};
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument};
 
 #[cfg(test)]
 mod test_rpc;
@@ -138,11 +141,28 @@ where
     let rollup_config: FullNodeConfig<DaC> = from_toml_path(rollup_config_path)
         .context("Failed to read rollup configuration")
         .unwrap();
+    // The sequencer does not sync from upstreams
+    if sequencer_config.is_none() {
+        if let Some(runner_config) = &rollup_config.runner {
+            runner_config
+                .validate()
+                .context("Invalid runner configuration")
+                .unwrap();
+        }
+    }
     let rollup_blueprint = S::new();
 
+    let shutdown = CancellationToken::new();
+    tokio::spawn(cancel_on_shutdown_signal(shutdown.clone()));
+
     if let Some(sequencer_config) = sequencer_config {
         let sequencer_rollup = rollup_blueprint
-            .create_new_sequencer(rt_genesis_paths, rollup_config.clone(), sequencer_config)
+            .create_new_sequencer(
+                rt_genesis_paths,
+                rollup_config.clone(),
+                sequencer_config,
+                shutdown,
+            )
             .await
             .expect("Could not start sequencer");
         if let Err(e) = sequencer_rollup.run().await {
@@ -157,8 +177,13 @@ where
         )
         .await
         .expect("Coult not start prover");
-        if let Err(e) = prover.run().await {
-            error!("Error: {}", e);
+        tokio::select! {
+            res = prover.run() => {
+                if let Err(e) = res {
+                    error!("Error: {}", e);
+                }
+            }
+            _ = shutdown.cancelled() => {}
         }
     } else {
         let rollup = CitreaRollupBlueprint::create_new_rollup(
@@ -168,10 +193,49 @@ where
         )
         .await
         .expect("Could not start full-node");
-        if let Err(e) = rollup.run().await {
-            error!("Error: {}", e);
+        tokio::select! {
+            res = rollup.run() => {
+                if let Err(e) = res {
+                    error!("Error: {}", e);
+                }
+            }
+            _ = shutdown.cancelled() => {}
         }
     }
 
     Ok(())
 }
+
+/// Cancels `shutdown` on SIGINT, or SIGTERM on unix.
+async fn cancel_on_shutdown_signal(shutdown: CancellationToken) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for ctrl-c: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, stopping gracefully");
+    shutdown.cancel();
+}
@@ -16,7 +16,7 @@ use sov_prover_storage_manager::ProverStorageManager;
 use sov_rollup_interface::da::DaVerifier;
 use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
-use sov_stf_runner::{FullNodeConfig, ParallelProverService, ProverConfig};
+use sov_stf_runner::{FullNodeConfig, ParallelProverService, ProverConfig, RpcConfig};
 use tracing::instrument;
 
 use crate::CitreaRollupBlueprint;
@@ -59,7 +59,8 @@ impl RollupBlueprint for BitcoinRollup {
         storage: &<Self::NativeContext as Spec>::Storage,
         ledger_db: &LedgerDB,
         da_service: &Self::DaService,
-        sequencer_client_url: Option<String>,
+        rpc_config: &RpcConfig,
+        sequencer_client_urls: Option<Vec<String>>,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error> {
         // unused inside register RPC
         let sov_sequencer = Address::new([0; 32]);
@@ -74,8 +75,10 @@ impl RollupBlueprint for BitcoinRollup {
         crate::eth::register_ethereum::<Self::DaService>(
             da_service.clone(),
             storage.clone(),
+            ledger_db,
             &mut rpc_methods,
-            sequencer_client_url,
+            rpc_config,
+            sequencer_client_urls,
         )?;
 
         Ok(rpc_methods)
@@ -96,6 +99,7 @@ impl RollupBlueprint for BitcoinRollup {
     ) -> Result<Self::StorageManager, anyhow::Error> {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
+            pruning: rollup_config.storage.pruning,
         };
         ProverStorageManager::new(storage_config)
     }
@@ -111,6 +115,11 @@ impl RollupBlueprint for BitcoinRollup {
                 rollup_name: ROLLUP_NAME.to_string(),
                 reveal_tx_id_prefix: DA_TX_ID_LEADING_ZEROS.to_vec(),
             },
+            vec![
+                rollup_config.public_keys.sequencer_da_pub_key.clone(),
+                rollup_config.public_keys.prover_da_pub_key.clone(),
+            ],
+            &rollup_config.storage.path,
         )
         .await
     }
@@ -12,7 +12,7 @@ use sov_modules_stf_blueprint::StfBlueprint;
 use sov_prover_storage_manager::ProverStorageManager;
 use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
-use sov_stf_runner::{FullNodeConfig, ParallelProverService, ProverConfig};
+use sov_stf_runner::{FullNodeConfig, ParallelProverService, ProverConfig, RpcConfig};
 
 use crate::CitreaRollupBlueprint;
 
@@ -53,7 +53,8 @@ impl RollupBlueprint for MockDemoRollup {
         storage: &<Self::NativeContext as Spec>::Storage,
         ledger_db: &LedgerDB,
         da_service: &Self::DaService,
-        sequencer_client_url: Option<String>,
+        rpc_config: &RpcConfig,
+        sequencer_client_urls: Option<Vec<String>>,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error> {
         // TODO set the sequencer address
         let sequencer = Address::new([0; 32]);
@@ -68,8 +69,10 @@ impl RollupBlueprint for MockDemoRollup {
         crate::eth::register_ethereum::<Self::DaService>(
             da_service.clone(),
             storage.clone(),
+            ledger_db,
             &mut rpc_methods,
-            sequencer_client_url,
+            rpc_config,
+            sequencer_client_urls,
         )?;
 
         Ok(rpc_methods)
@@ -117,6 +120,7 @@ impl RollupBlueprint for MockDemoRollup {
     ) -> anyhow::Result<Self::StorageManager> {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
+            pruning: rollup_config.storage.pruning,
         };
         ProverStorageManager::new(storage_config)
     }
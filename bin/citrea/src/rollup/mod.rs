@@ -10,6 +10,7 @@ use sov_modules_rollup_blueprint::RollupBlueprint;
 use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, StfBlueprint};
 use sov_state::storage::NativeStorage;
 use sov_stf_runner::{FullNodeConfig, InitVariant, ProverConfig};
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 mod bitcoin;
 mod mock;
@@ -17,7 +18,8 @@ mod mock;
 /// Overrides RollupBlueprint methods
 #[async_trait]
 pub trait CitreaRollupBlueprint: RollupBlueprint {
-    /// Creates a new sequencer
+    /// Creates a new sequencer.
+    /// The sequencer and its DA service stop gracefully once `shutdown` is cancelled.
     #[instrument(level = "trace", skip_all)]
     async fn create_new_sequencer(
         &self,
@@ -27,6 +29,7 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
         >>::GenesisPaths,
         rollup_config: FullNodeConfig<Self::DaConfig>,
         sequencer_config: SequencerConfig,
+        shutdown: CancellationToken,
     ) -> Result<Sequencer<Self>, anyhow::Error>
     where
         <Self::NativeContext as Spec>::Storage: NativeStorage,
@@ -49,8 +52,13 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
             .transpose()?;
 
         // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218)
-        let rpc_methods =
-            self.create_rpc_methods(&prover_storage, &ledger_db, &da_service, None)?;
+        let rpc_methods = self.create_rpc_methods(
+            &prover_storage,
+            &ledger_db,
+            &da_service,
+            &rollup_config.rpc,
+            None,
+        )?;
 
         let native_stf = StfBlueprint::new();
 
@@ -80,6 +88,7 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
         Ok(Sequencer {
             runner: seq,
             rpc_methods,
+            shutdown,
         })
     }
 
@@ -108,18 +117,54 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
         let mut storage_manager = self.create_storage_manager(&rollup_config)?;
         let prover_storage = storage_manager.create_finalized_storage()?;
 
-        let prev_root = ledger_db
-            .get_head_soft_batch()?
-            .map(|(number, _)| prover_storage.get_root_hash(number.0 + 1))
+        let runner_config = rollup_config.runner.expect("Runner config is missing");
+
+        // A database is either synced from upstreams or reconstructed from DA, one cannot be continued as the other
+        let reconstruct_from_da = runner_config.reconstruct_from_da.unwrap_or(false);
+        let da_reconstruction_progress = ledger_db.get_da_reconstruction_progress()?;
+        if reconstruct_from_da && ledger_db.get_head_soft_batch()?.is_some() {
+            anyhow::bail!(
+                "Cannot reconstruct state from DA: the database already has soft batches synced from upstreams"
+            );
+        }
+        if !reconstruct_from_da && da_reconstruction_progress.is_some() {
+            anyhow::bail!(
+                "Cannot sync soft batches: the database has state reconstructed from DA, set reconstruct_from_da or use a new database"
+            );
+        }
+
+        // Storage version `n + 1` is the state after L2 height `n`, version 1 being genesis.
+        // When reconstructing from DA, versions count the applied state diffs instead:
+        // version `n + 1` is the state after the first `n` applied state diffs.
+        let last_l2_height = if reconstruct_from_da {
+            let applied_state_diffs = da_reconstruction_progress
+                .map(|(_, applied_state_diffs)| applied_state_diffs)
+                .unwrap_or(0);
+            // State diffs are finalized in storage before their progress is saved
+            if prover_storage
+                .get_root_hash(applied_state_diffs + 2)
+                .is_ok()
+            {
+                anyhow::bail!(
+                    "Cannot reconstruct state from DA: the storage has state diffs applied after the {} saved ones, reconstruct with a new database",
+                    applied_state_diffs
+                );
+            }
+            (applied_state_diffs > 0).then_some(applied_state_diffs)
+        } else {
+            ledger_db.get_head_soft_batch()?.map(|(number, _)| number.0)
+        };
+        let prev_root = last_l2_height
+            .map(|height| prover_storage.get_root_hash(height + 1))
             .transpose()?;
 
-        let runner_config = rollup_config.runner.expect("Runner config is missing");
         // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218)
         let rpc_methods = self.create_rpc_methods(
             &prover_storage,
             &ledger_db,
             &da_service,
-            Some(runner_config.sequencer_client_url.clone()),
+            &rollup_config.rpc,
+            Some(runner_config.sequencer_client_urls.clone()),
         )?;
 
         let native_stf = StfBlueprint::new();
@@ -196,7 +241,8 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
             &prover_storage,
             &ledger_db,
             &da_service,
-            Some(runner_config.sequencer_client_url.clone()),
+            &rollup_config.rpc,
+            Some(runner_config.sequencer_client_urls.clone()),
         )?;
 
         let native_stf = StfBlueprint::new();
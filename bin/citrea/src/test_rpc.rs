@@ -98,6 +98,9 @@ fn test_helper(
             max_request_body_size: 10 * 1024 * 1024,
             max_response_body_size: 10 * 1024 * 1024,
             batch_requests_limit: 50,
+            max_filters_per_connection: 100,
+            max_filters: 10_000,
+            filter_idle_timeout_secs: 300,
         };
 
         queries_test_runner(test_queries, rpc_config).await;
@@ -11,7 +11,7 @@ use citrea_evm::smart_contracts::SimpleStorageContract;
 use citrea_evm::system_contracts::BitcoinLightClient;
 use citrea_evm::SYSTEM_SIGNER;
 use citrea_primitives::TEST_PRIVATE_KEY;
-use citrea_sequencer::{SequencerConfig, SequencerMempoolConfig};
+use citrea_sequencer::{L1ReorgOutcome, SequencerConfig, SequencerMempoolConfig};
 use citrea_stf::genesis_config::GenesisPaths;
 use ethereum_rpc::CitreaStatus;
 use reth_primitives::{Address, BlockNumberOrTag, TxHash, U256};
@@ -27,9 +27,9 @@ use tokio::time::sleep;
 use crate::evm::{init_test_rollup, make_test_client};
 use crate::test_client::TestClient;
 use crate::test_helpers::{
-    create_default_sequencer_config, start_rollup, tempdir_with_children, wait_for_l1_block,
-    wait_for_l2_block, wait_for_postgres_commitment, wait_for_postgres_proofs, wait_for_proof,
-    wait_for_prover_l1_height, NodeMode,
+    create_default_rollup_config, create_default_sequencer_config, start_rollup,
+    tempdir_with_children, wait_for_l1_block, wait_for_l2_block, wait_for_postgres_commitment,
+    wait_for_postgres_proofs, wait_for_proof, wait_for_prover_l1_height, NodeMode,
 };
 use crate::{
     DEFAULT_DEPOSIT_MEMPOOL_FETCH_LIMIT, DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
@@ -851,6 +851,144 @@ async fn test_soft_confirmations_on_different_blocks() -> Result<(), anyhow::Err
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reanchor_on_l1_reorg() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging(tracing::Level::INFO);
+    let storage_dir = tempdir_with_children(&["DA", "sequencer", "full-node"]);
+    let da_db_dir = storage_dir.path().join("DA").to_path_buf();
+    let sequencer_db_dir = storage_dir.path().join("sequencer").to_path_buf();
+    let fullnode_db_dir = storage_dir.path().join("full-node").to_path_buf();
+
+    let da_service = MockDaService::new(MockAddress::default(), &da_db_dir.clone());
+
+    let (seq_test_client, full_node_test_client, seq_task, full_node_task, _) =
+        initialize_test(TestConfig {
+            // Only the commitment that ends at the reorged L1 block
+            seq_min_soft_confirmations: 1000,
+            da_path: da_db_dir.clone(),
+            sequencer_path: sequencer_db_dir.clone(),
+            fullnode_path: fullnode_db_dir.clone(),
+            ..Default::default()
+        })
+        .await;
+
+    for _ in 1..=3 {
+        seq_test_client.send_publish_batch_request().await;
+    }
+    wait_for_l2_block(&seq_test_client, 3, None).await;
+
+    da_service.publish_test_block().await.unwrap();
+    wait_for_l1_block(&da_service, 2, None).await;
+    sleep(Duration::from_secs(1)).await;
+
+    for _ in 4..=6 {
+        seq_test_client.send_publish_batch_request().await;
+    }
+    wait_for_l2_block(&seq_test_client, 6, None).await;
+
+    let reorged_block = da_service.get_block_at(2).await.unwrap();
+    let soft_batch = seq_test_client
+        .ledger_get_soft_batch_by_number::<MockDaSpec>(6)
+        .await
+        .unwrap();
+    assert_eq!(soft_batch.da_slot_height, 2);
+    assert_eq!(soft_batch.da_slot_hash, reorged_block.header.hash.0);
+
+    // Replace the finalized L1 block 2 with a longer fork
+    da_service
+        .reorg_at(1, vec![vec![2], vec![3], vec![4]])
+        .await
+        .unwrap();
+    wait_for_l1_block(&da_service, 4, None).await;
+
+    let mut reorg_status = seq_test_client.citrea_get_l1_reorg_status().await;
+    for _ in 0..100 {
+        if !reorg_status.reorgs.is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+        reorg_status = seq_test_client.citrea_get_l1_reorg_status().await;
+    }
+    assert!(!reorg_status.halted);
+    assert_eq!(reorg_status.reorgs.len(), 1);
+    let reorg = &reorg_status.reorgs[0];
+    assert_eq!(reorg.common_ancestor_height, 1);
+    assert_eq!(
+        reorg.reorged_hashes,
+        vec![hex::encode(reorged_block.header.hash.0)]
+    );
+    assert_eq!(
+        reorg.outcome,
+        L1ReorgOutcome::Reanchored {
+            first_l2_height: 4,
+            last_l2_height: 6,
+            next_l1_height: 3,
+            requeued_deposits: 0,
+        }
+    );
+
+    // Soft confirmations 1-6 are committed before building on the new fork
+    let genesis_block = da_service.get_block_at(1).await.unwrap();
+    let mut blobs = da_service.get_block_at(5).await.unwrap().blobs;
+    assert_eq!(blobs.len(), 1);
+    let mut blob = blobs.pop().unwrap();
+    blob.data.advance(blob.data.total_len());
+    let commitment: DaData = borsh::BorshDeserialize::try_from_slice(blob.data.accumulator())?;
+    let DaData::SequencerCommitment(commitment) = commitment else {
+        panic!("Expected a sequencer commitment");
+    };
+    assert_eq!(commitment.l1_start_block_hash, genesis_block.header.hash.0);
+    assert_eq!(commitment.l1_end_block_hash, reorged_block.header.hash.0);
+
+    // Wait for the DA monitor to report the new fork
+    sleep(Duration::from_secs(1)).await;
+    seq_test_client.send_publish_batch_request().await;
+    // An empty soft confirmation is built on L1 block 3, then one on L1 block 4
+    wait_for_l2_block(&seq_test_client, 8, None).await;
+
+    // Published soft confirmations stay on the reorged L1 block
+    let soft_batch = seq_test_client
+        .ledger_get_soft_batch_by_number::<MockDaSpec>(6)
+        .await
+        .unwrap();
+    assert_eq!(soft_batch.da_slot_hash, reorged_block.header.hash.0);
+
+    for (l2_height, l1_height) in [(7, 3), (8, 4)] {
+        let l1_block = da_service.get_block_at(l1_height).await.unwrap();
+        let soft_batch = seq_test_client
+            .ledger_get_soft_batch_by_number::<MockDaSpec>(l2_height)
+            .await
+            .unwrap();
+        assert_eq!(soft_batch.da_slot_height, l1_height);
+        assert_eq!(soft_batch.da_slot_hash, l1_block.header.hash.0);
+    }
+
+    wait_for_l2_block(&full_node_test_client, 8, None).await;
+    for l2_height in 1..=8 {
+        let seq_soft_batch = seq_test_client
+            .ledger_get_soft_batch_by_number::<MockDaSpec>(l2_height)
+            .await
+            .unwrap();
+        let full_node_soft_batch = full_node_test_client
+            .ledger_get_soft_batch_by_number::<MockDaSpec>(l2_height)
+            .await
+            .unwrap();
+        assert_eq!(
+            seq_soft_batch.da_slot_hash,
+            full_node_soft_batch.da_slot_hash
+        );
+        assert_eq!(
+            seq_soft_batch.post_state_root,
+            full_node_soft_batch.post_state_root
+        );
+    }
+
+    seq_task.abort();
+    full_node_task.abort();
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_reopen_sequencer() -> Result<(), anyhow::Error> {
     // open, close without publishing blokcs
@@ -1103,6 +1241,12 @@ async fn test_soft_confirmations_status_one_l1() -> Result<(), anyhow::Error> {
 
         assert_eq!(SoftConfirmationStatus::Trusted, status_node.unwrap());
     }
+    assert_eq!(
+        full_node_test_client
+            .citrea_get_last_committed_l2_height()
+            .await,
+        None
+    );
 
     // publish new da block
     //
@@ -1128,6 +1272,16 @@ async fn test_soft_confirmations_status_one_l1() -> Result<(), anyhow::Error> {
 
         assert_eq!(SoftConfirmationStatus::Finalized, status_node.unwrap());
     }
+    assert_eq!(
+        full_node_test_client
+            .citrea_get_last_committed_l2_height()
+            .await,
+        Some(6)
+    );
+    let safe_block = full_node_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Safe))
+        .await;
+    assert_eq!(safe_block.header.number.unwrap(), 6);
 
     seq_task.abort();
     full_node_task.abort();
@@ -2665,6 +2819,156 @@ async fn full_node_verify_proof_and_store() {
     full_node_task.abort();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn full_node_reconstructs_state_from_da() {
+    // citrea::initialize_logging(tracing::Level::INFO);
+
+    let storage_dir = tempdir_with_children(&["DA", "sequencer", "prover", "full-node"]);
+    let sequencer_db_dir = storage_dir.path().join("sequencer").to_path_buf();
+    let prover_db_dir = storage_dir.path().join("prover").to_path_buf();
+    let fullnode_db_dir = storage_dir.path().join("full-node").to_path_buf();
+    let da_db_dir = storage_dir.path().join("DA").to_path_buf();
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let da_db_dir_cloned = da_db_dir.clone();
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir(TEST_DATA_GENESIS_PATH),
+            None,
+            NodeMode::SequencerNode,
+            sequencer_db_dir,
+            da_db_dir_cloned,
+            4,
+            true,
+            None,
+            None,
+            Some(true),
+            DEFAULT_DEPOSIT_MEMPOOL_FETCH_LIMIT,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let test_client = make_test_client(seq_port).await;
+
+    let da_service = MockDaService::new(MockAddress::from([0; 32]), &da_db_dir);
+
+    let (prover_node_port_tx, prover_node_port_rx) = tokio::sync::oneshot::channel();
+
+    let da_db_dir_cloned = da_db_dir.clone();
+    let prover_node_task = tokio::spawn(async move {
+        start_rollup(
+            prover_node_port_tx,
+            GenesisPaths::from_dir(TEST_DATA_GENESIS_PATH),
+            Some(ProverConfig {
+                proving_mode: sov_stf_runner::ProverGuestRunConfig::Execute,
+                proof_sampling_number: 0,
+                db_config: None,
+            }),
+            NodeMode::Prover(seq_port),
+            prover_db_dir,
+            da_db_dir_cloned,
+            4,
+            true,
+            None,
+            None,
+            Some(true),
+            DEFAULT_DEPOSIT_MEMPOOL_FETCH_LIMIT,
+        )
+        .await;
+    });
+
+    let prover_node_port = prover_node_port_rx.await.unwrap();
+    let prover_node_test_client = make_test_client(prover_node_port).await;
+
+    // The full node is not given any upstream and only follows DA
+    let mut full_node_config = create_default_rollup_config(
+        true,
+        &fullnode_db_dir,
+        &da_db_dir,
+        NodeMode::FullNode(seq_port),
+    );
+    let runner_config = full_node_config.runner.as_mut().unwrap();
+    runner_config.sequencer_client_urls = vec![];
+    runner_config.reconstruct_from_da = Some(true);
+
+    let (full_node_port_tx, full_node_port_rx) = tokio::sync::oneshot::channel();
+
+    let da_db_dir_cloned = da_db_dir.clone();
+    let full_node_task = tokio::spawn(async move {
+        start_rollup(
+            full_node_port_tx,
+            GenesisPaths::from_dir(TEST_DATA_GENESIS_PATH),
+            None,
+            NodeMode::FullNode(seq_port),
+            fullnode_db_dir,
+            da_db_dir_cloned,
+            4,
+            true,
+            Some(full_node_config),
+            None,
+            Some(true),
+            DEFAULT_DEPOSIT_MEMPOOL_FETCH_LIMIT,
+        )
+        .await;
+    });
+
+    let full_node_port = full_node_port_rx.await.unwrap();
+    let full_node_test_client = make_test_client(full_node_port).await;
+
+    da_service.publish_test_block().await.unwrap();
+    wait_for_l1_block(&da_service, 2, None).await;
+
+    for _ in 0..4 {
+        test_client.send_publish_batch_request().await;
+    }
+    wait_for_l2_block(&test_client, 4, None).await;
+
+    // Triggers commitment submission
+    da_service.publish_test_block().await.unwrap();
+    wait_for_l1_block(&da_service, 4, None).await;
+
+    test_client.send_publish_batch_request().await;
+    wait_for_l2_block(&test_client, 5, None).await;
+
+    wait_for_prover_l1_height(
+        &prover_node_test_client,
+        5,
+        Some(Duration::from_secs(DEFAULT_PROOF_WAIT_DURATION)),
+    )
+    .await;
+
+    let prover_proof = prover_node_test_client
+        .ledger_get_proof_by_slot_height(4)
+        .await;
+
+    // The proof is published in L1 block #5, and the state diff is only stored once its root checks out
+    wait_for_l1_block(&da_service, 5, None).await;
+    wait_for_proof(&full_node_test_client, 5, None).await;
+    let full_node_proof = full_node_test_client
+        .ledger_get_verified_proofs_by_slot_height(5)
+        .await
+        .unwrap();
+    assert_eq!(prover_proof.proof, full_node_proof[0].proof);
+    assert_eq!(
+        prover_proof.state_transition,
+        full_node_proof[0].state_transition
+    );
+
+    // No soft batches were synced
+    assert!(full_node_test_client
+        .ledger_get_head_soft_batch()
+        .await
+        .unwrap()
+        .is_none());
+
+    seq_task.abort();
+    prover_node_task.abort();
+    full_node_task.abort();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_all_flow() {
     // citrea::initialize_logging(tracing::Level::DEBUG);
@@ -3310,3 +3614,64 @@ async fn test_full_node_sync_status() {
     seq_task.abort();
     full_node_task.abort();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_txpool_content() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging(tracing::Level::INFO);
+
+    let storage_dir = tempdir_with_children(&["DA", "sequencer", "full-node"]);
+    let da_db_dir = storage_dir.path().join("DA").to_path_buf();
+    let sequencer_db_dir = storage_dir.path().join("sequencer").to_path_buf();
+    let fullnode_db_dir = storage_dir.path().join("full-node").to_path_buf();
+
+    let (seq_test_client, full_node_test_client, seq_task, full_node_task, addr) =
+        initialize_test(TestConfig {
+            da_path: da_db_dir,
+            sequencer_path: sequencer_db_dir,
+            fullnode_path: fullnode_db_dir,
+            ..Default::default()
+        })
+        .await;
+
+    // nonce 0 is executable, nonce 5 has a gap and must be queued
+    let pending_tx = seq_test_client
+        .send_eth(addr, None, None, Some(0), 0u128)
+        .await
+        .unwrap();
+    let queued_tx = seq_test_client
+        .send_eth(addr, None, None, Some(5), 0u128)
+        .await
+        .unwrap();
+
+    let status = seq_test_client.txpool_status().await;
+    assert_eq!(status.pending, 1);
+    assert_eq!(status.queued, 1);
+
+    // full node forwards txpool calls to the sequencer
+    for client in [&seq_test_client, &full_node_test_client] {
+        let content = client.txpool_content().await;
+        assert_eq!(content.pending[&addr]["0"].hash, *pending_tx.tx_hash());
+        assert_eq!(content.queued[&addr]["5"].hash, *queued_tx.tx_hash());
+
+        let content_from = client.txpool_content_from(addr).await;
+        assert_eq!(content_from.pending.len(), 1);
+        assert_eq!(content_from.queued.len(), 1);
+
+        let content_from_other = client.txpool_content_from(Address::ZERO).await;
+        assert!(content_from_other.pending.is_empty());
+        assert!(content_from_other.queued.is_empty());
+    }
+
+    seq_test_client.send_publish_batch_request().await;
+    wait_for_l2_block(&full_node_test_client, 1, None).await;
+
+    // the executed tx leaves the pool, the gapped one stays queued
+    let status = full_node_test_client.txpool_status().await;
+    assert_eq!(status.pending, 0);
+    assert_eq!(status.queued, 1);
+
+    seq_task.abort();
+    full_node_task.abort();
+
+    Ok(())
+}
@@ -4,11 +4,13 @@ use std::str::FromStr;
 use citrea_evm::smart_contracts::{CallerContract, SimpleStorageContract};
 use citrea_stf::genesis_config::GenesisPaths;
 use reth_primitives::{Address, BlockNumberOrTag};
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::GethTrace::{self, CallTracer, FourByteTracer};
 use reth_rpc_types::trace::geth::{
     CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
     GethDebugTracingOptions,
 };
+use reth_rpc_types::trace::parity::Action;
 use serde_json::{self, json};
 
 use crate::evm::make_test_client;
@@ -309,6 +311,48 @@ async fn tracing_tests() -> Result<(), Box<dyn std::error::Error>> {
         CallTracer(expected_top_call_only_call_get_trace)
     );
 
+    // Parity traces of the call to the caller contract, which calls the simple storage contract
+    let traces = test_client.trace_transaction(tx_hash).await.unwrap();
+    assert_eq!(traces.len(), 2);
+    assert_eq!(traces[0].block_number, Some(2));
+    assert_eq!(traces[0].trace.trace_address, Vec::<usize>::new());
+    assert_eq!(traces[1].trace.trace_address, vec![0]);
+    match (&traces[0].trace.action, &traces[1].trace.action) {
+        (Action::Call(call), Action::Call(subcall)) => {
+            assert_eq!(call.to, caller_contract_address);
+            assert_eq!(subcall.from, caller_contract_address);
+            assert_eq!(subcall.to, ss_contract_address);
+        }
+        actions => panic!("Expected call actions, got {:?}", actions),
+    }
+
+    assert_eq!(
+        test_client.trace_block(BlockNumberOrTag::Number(2)).await,
+        traces
+    );
+
+    // Only the subcalls to the simple storage contract match
+    let filtered_traces = test_client
+        .trace_filter(TraceFilter {
+            from_block: Some(2),
+            to_block: Some(3),
+            to_address: vec![ss_contract_address],
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(filtered_traces.len(), 2);
+    assert_eq!(filtered_traces[0], traces[1]);
+    assert!(filtered_traces
+        .iter()
+        .all(|trace| trace.trace.trace_address == vec![0]));
+
+    assert_eq!(
+        test_client
+            .trace_transaction(reth_primitives::TxHash::ZERO)
+            .await,
+        None
+    );
+
     rollup_task.abort();
     Ok(())
 }
@@ -90,6 +90,8 @@ class TestWeb3(unittest.TestCase):
         self.assertEqual(receipt['to'], "0x0000000000000000000000000000000000000000")
         self.assertGreater(int(receipt['diffSize'], 16), 0)
         self.assertGreater(int(receipt['l1FeeRate'], 16), 0)
+        self.assertEqual(receipt['l1DiffSize'], receipt['diffSize'])
+        self.assertEqual(int(receipt['l1Fee'], 16), int(receipt['l1DiffSize'], 16) * int(receipt['l1FeeRate'], 16))
 
     def test_get_transaction_count(self):
         tx_count = self.web3.eth.get_transaction_count("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
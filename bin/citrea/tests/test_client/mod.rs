@@ -8,13 +8,17 @@ use alloy::rpc::types::eth::{Block, Transaction, TransactionReceipt, Transaction
 use alloy::signers::wallet::LocalWallet;
 use alloy::transports::http::{Http, HyperClient};
 use citrea_evm::LogResponse;
+use citrea_sequencer::L1ReorgStatus;
 use ethereum_rpc::CitreaStatus;
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, TxKind, B256, U256, U64};
 // use reth_rpc_types::TransactionReceipt;
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::parity::LocalizedTransactionTrace;
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolStatus};
 use sequencer_client::GetSoftBatchResponse;
 use sov_rollup_interface::rpc::{
     ProofResponse, SequencerCommitmentResponse, SoftBatchResponse, SoftConfirmationStatus,
@@ -530,6 +534,21 @@ impl TestClient {
             .map_err(|e| e.into())
     }
 
+    pub(crate) async fn citrea_get_last_committed_l2_height(&self) -> Option<u64> {
+        self.http_client
+            .request("citrea_getLastCommittedL2Height", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    #[allow(dead_code)]
+    pub(crate) async fn citrea_get_last_proven_l2_height(&self) -> Option<u64> {
+        self.http_client
+            .request("citrea_getLastProvenL2Height", rpc_params![])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn prover_get_last_scanned_l1_height(&self) -> u64 {
         self.http_client
             .request("prover_getLastScannedL1Slot", rpc_params![])
@@ -608,6 +627,13 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn citrea_get_l1_reorg_status(&self) -> L1ReorgStatus {
+        self.http_client
+            .request("citrea_getL1ReorgStatus", rpc_params![])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn debug_trace_transaction(
         &self,
         tx_hash: TxHash,
@@ -641,6 +667,33 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn trace_block(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> Vec<LocalizedTransactionTrace> {
+        self.http_client
+            .request("trace_block", rpc_params![block_number])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.http_client
+            .request("trace_transaction", rpc_params![tx_hash])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_filter(&self, filter: TraceFilter) -> Vec<LocalizedTransactionTrace> {
+        self.http_client
+            .request("trace_filter", rpc_params![filter])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_block_number(&self) -> u64 {
         let block_number: U256 = self
             .http_client
@@ -657,6 +710,27 @@ impl TestClient {
             .await
             .unwrap()
     }
+
+    pub(crate) async fn txpool_content(&self) -> TxpoolContent {
+        self.http_client
+            .request("txpool_content", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_content_from(&self, from: Address) -> TxpoolContentFrom {
+        self.http_client
+            .request("txpool_contentFrom", rpc_params![from])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_status(&self) -> TxpoolStatus {
+        self.http_client
+            .request("txpool_status", rpc_params![])
+            .await
+            .unwrap()
+    }
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -17,6 +17,7 @@ use sov_stf_runner::{
 use tempfile::TempDir;
 use tokio::sync::oneshot;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info_span, instrument, warn, Instrument};
 
 use crate::test_client::TestClient;
@@ -106,6 +107,7 @@ pub async fn start_rollup(
                 &rt_genesis_paths,
                 rollup_config.clone(),
                 sequencer_config,
+                CancellationToken::new(),
             )
             .instrument(span.clone())
             .await
@@ -136,6 +138,7 @@ pub fn create_default_rollup_config(
         },
         storage: StorageConfig {
             path: rollup_path.to_path_buf(),
+            pruning: Default::default(),
         },
         rpc: RpcConfig {
             bind_host: "127.0.0.1".into(),
@@ -144,12 +147,16 @@ pub fn create_default_rollup_config(
             max_request_body_size: 10 * 1024 * 1024,
             max_response_body_size: 10 * 1024 * 1024,
             batch_requests_limit: 50,
+            max_filters_per_connection: 100,
+            max_filters: 10_000,
+            filter_idle_timeout_secs: 300,
         },
         runner: match node_mode {
             NodeMode::FullNode(socket_addr) | NodeMode::Prover(socket_addr) => Some(RunnerConfig {
                 include_tx_body,
-                sequencer_client_url: format!("http://localhost:{}", socket_addr.port()),
+                sequencer_client_urls: vec![format!("http://localhost:{}", socket_addr.port())],
                 accept_public_input_as_proven: Some(true),
+                reconstruct_from_da: None,
             }),
             NodeMode::SequencerNode => None,
         },
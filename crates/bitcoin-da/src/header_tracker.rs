@@ -0,0 +1,282 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use bitcoin::BlockHash;
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::services::da::FinalizedHeaderEvent;
+use tracing::{instrument, warn};
+
+use crate::rpc::{BitcoinNode, RPCError};
+use crate::spec::header::HeaderWrapper;
+
+/// Number of reported finalized headers that are checked for reorgs
+const TRACKED_HEADERS: usize = 100;
+
+/// Access to the best chain of the node, abstracted so the tracker can be tested without a node
+#[async_trait]
+pub(crate) trait HeaderSource {
+    async fn block_count(&self) -> Result<u64, anyhow::Error>;
+
+    /// Hash of the block at `height` on the best chain, `None` if the chain is not that long
+    async fn block_hash(&self, height: u64) -> Result<Option<BlockHash>, anyhow::Error>;
+
+    /// Header of the block at `height` on the best chain
+    async fn header(&self, height: u64) -> Result<HeaderWrapper, anyhow::Error>;
+}
+
+#[async_trait]
+impl HeaderSource for BitcoinNode {
+    async fn block_count(&self) -> Result<u64, anyhow::Error> {
+        self.get_block_count().await
+    }
+
+    async fn block_hash(&self, height: u64) -> Result<Option<BlockHash>, anyhow::Error> {
+        match self.get_block_hash(height).await {
+            Ok(hash) => Ok(Some(BlockHash::from_str(&hash)?)),
+            // "Block height out of range"
+            Err(e) if matches!(e.downcast_ref::<RPCError>(), Some(error) if error.code == -8) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn header(&self, height: u64) -> Result<HeaderWrapper, anyhow::Error> {
+        let hash = self.get_block_hash(height).await?;
+        self.get_block_header(hash).await
+    }
+}
+
+/// Follows the finalized part of the chain, which is `finality_depth` blocks below the tip.
+/// Reported headers are remembered, so that a reorg deeper than the finality depth is noticed
+/// and reported before the headers of the new fork.
+#[derive(Debug)]
+pub(crate) struct HeaderTracker {
+    finality_depth: u64,
+    /// Reported finalized headers, in ascending order of height
+    headers: VecDeque<HeaderWrapper>,
+}
+
+impl HeaderTracker {
+    pub fn new(finality_depth: u64) -> Self {
+        Self {
+            finality_depth,
+            headers: VecDeque::with_capacity(TRACKED_HEADERS),
+        }
+    }
+
+    /// Returns the events since the last poll. The first poll only starts tracking from the
+    /// current finalized block, without reporting it.
+    #[instrument(level = "trace", skip_all, err)]
+    pub async fn poll(
+        &mut self,
+        source: &impl HeaderSource,
+    ) -> Result<Vec<FinalizedHeaderEvent<HeaderWrapper>>, anyhow::Error> {
+        let block_count = source.block_count().await?;
+        let Some(finalized_height) = block_count.checked_sub(self.finality_depth) else {
+            return Ok(vec![]);
+        };
+
+        if self.headers.is_empty() {
+            let header = source.header(finalized_height).await?;
+            self.headers.push_back(header);
+            return Ok(vec![]);
+        }
+
+        let mut events = vec![];
+        if let Some(reorg) = self.check_reorg(source).await? {
+            events.push(reorg);
+        }
+
+        let mut last = self.headers.back().expect("At least one header is tracked");
+        let mut next_height = last.height + 1;
+        while next_height <= finalized_height {
+            let header = source.header(next_height).await?;
+            if header.prev_hash().0 != last.block_hash() {
+                // The chain changed while it was read, which is checked for on the next poll
+                break;
+            }
+
+            if self.headers.len() == TRACKED_HEADERS {
+                self.headers.pop_front();
+            }
+            self.headers.push_back(header.clone());
+            events.push(FinalizedHeaderEvent::Finalized(header));
+
+            last = self.headers.back().expect("Header was just pushed");
+            next_height += 1;
+        }
+
+        Ok(events)
+    }
+
+    /// Drops the tracked headers that are not on the best chain anymore
+    async fn check_reorg(
+        &mut self,
+        source: &impl HeaderSource,
+    ) -> Result<Option<FinalizedHeaderEvent<HeaderWrapper>>, anyhow::Error> {
+        // Nothing is dropped until the common ancestor is found, so RPC errors leave the tracker intact
+        let mut common_ancestor_index = None;
+        for (index, header) in self.headers.iter().enumerate().rev() {
+            if source.block_hash(header.height).await? == Some(header.block_hash()) {
+                common_ancestor_index = Some(index);
+                break;
+            }
+        }
+
+        let Some(common_ancestor_index) = common_ancestor_index else {
+            let oldest = self
+                .headers
+                .front()
+                .expect("At least one header is tracked");
+            let error = anyhow::anyhow!(
+                "Bitcoin reorged below height {}, deeper than the tracked finalized blocks",
+                oldest.height
+            );
+            // Tracking starts over from the new finalized block on the next poll
+            self.headers.clear();
+            return Err(error);
+        };
+
+        let reorged: Vec<_> = self.headers.drain(common_ancestor_index + 1..).collect();
+        if reorged.is_empty() {
+            return Ok(None);
+        }
+
+        let common_ancestor = self.headers[common_ancestor_index].clone();
+        warn!(
+            common_ancestor_height = common_ancestor.height,
+            reorged_blocks = reorged.len(),
+            "Finalized Bitcoin blocks were reorged out"
+        );
+
+        Ok(Some(FinalizedHeaderEvent::Reorg {
+            common_ancestor,
+            reorged,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use bitcoin::block::{Header, Version};
+    use bitcoin::hash_types::{TxMerkleNode, WitnessMerkleNode};
+    use bitcoin::hashes::Hash;
+    use bitcoin::{BlockHash, CompactTarget};
+    use sov_rollup_interface::services::da::FinalizedHeaderEvent;
+
+    use super::{HeaderSource, HeaderTracker};
+    use crate::spec::header::HeaderWrapper;
+
+    /// Best chain of a fake node, index is the height
+    struct Chain(Mutex<Vec<HeaderWrapper>>);
+
+    impl Chain {
+        fn new() -> Self {
+            Self(Mutex::new(vec![]))
+        }
+
+        /// Replaces the blocks from `height` on with `count` new blocks of the given fork
+        fn mine(&self, height: u64, count: u64, fork: u32) {
+            let mut blocks = self.0.lock().unwrap();
+            blocks.truncate(height as usize);
+            for height in height..height + count {
+                let prev_blockhash = blocks
+                    .last()
+                    .map(|header| header.block_hash())
+                    .unwrap_or_else(BlockHash::all_zeros);
+                let header = Header {
+                    version: Version::ONE,
+                    prev_blockhash,
+                    merkle_root: TxMerkleNode::all_zeros(),
+                    time: height as u32,
+                    bits: CompactTarget::from_consensus(0),
+                    nonce: fork,
+                };
+                blocks.push(HeaderWrapper::new(
+                    header,
+                    1,
+                    height,
+                    WitnessMerkleNode::all_zeros(),
+                ));
+            }
+        }
+
+        fn header_at(&self, height: u64) -> HeaderWrapper {
+            self.0.lock().unwrap()[height as usize].clone()
+        }
+    }
+
+    #[async_trait]
+    impl HeaderSource for Chain {
+        async fn block_count(&self) -> Result<u64, anyhow::Error> {
+            Ok(self.0.lock().unwrap().len() as u64 - 1)
+        }
+
+        async fn block_hash(&self, height: u64) -> Result<Option<BlockHash>, anyhow::Error> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .get(height as usize)
+                .map(|header| header.block_hash()))
+        }
+
+        async fn header(&self, height: u64) -> Result<HeaderWrapper, anyhow::Error> {
+            Ok(self.header_at(height))
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_finalized_headers_and_reorgs() {
+        let chain = Chain::new();
+        chain.mine(0, 11, 0);
+        let mut tracker = HeaderTracker::new(4);
+
+        // blocks finalized before the first poll are not reported
+        assert!(tracker.poll(&chain).await.unwrap().is_empty());
+
+        chain.mine(11, 2, 0);
+        assert_eq!(
+            tracker.poll(&chain).await.unwrap(),
+            vec![
+                FinalizedHeaderEvent::Finalized(chain.header_at(7)),
+                FinalizedHeaderEvent::Finalized(chain.header_at(8)),
+            ]
+        );
+
+        // a reorg within the finality depth is not reported
+        chain.mine(10, 3, 1);
+        assert!(tracker.poll(&chain).await.unwrap().is_empty());
+
+        // reorg of the finalized blocks 7 and 8
+        let reorged = vec![chain.header_at(7), chain.header_at(8)];
+        chain.mine(7, 7, 2);
+        assert_eq!(
+            tracker.poll(&chain).await.unwrap(),
+            vec![
+                FinalizedHeaderEvent::Reorg {
+                    common_ancestor: chain.header_at(6),
+                    reorged,
+                },
+                FinalizedHeaderEvent::Finalized(chain.header_at(7)),
+                FinalizedHeaderEvent::Finalized(chain.header_at(8)),
+                FinalizedHeaderEvent::Finalized(chain.header_at(9)),
+            ]
+        );
+
+        // reorg below the tracked headers
+        chain.mine(1, 13, 3);
+        assert!(tracker.poll(&chain).await.is_err());
+        assert!(tracker.poll(&chain).await.unwrap().is_empty());
+        chain.mine(14, 1, 3);
+        assert_eq!(
+            tracker.poll(&chain).await.unwrap(),
+            vec![FinalizedHeaderEvent::Finalized(chain.header_at(10))]
+        );
+    }
+}
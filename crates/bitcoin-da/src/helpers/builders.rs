@@ -1,8 +1,7 @@
 use core::fmt;
 use core::result::Result::Ok;
 use core::str::FromStr;
-use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::io::Write;
 
 use anyhow::anyhow;
 use bitcoin::absolute::LockTime;
@@ -26,9 +25,15 @@ use sov_modules_api::{native_trace, native_warn};
 #[cfg(feature = "native")]
 use tracing::instrument;
 
-use crate::helpers::{BODY_TAG, PUBLICKEY_TAG, RANDOM_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG};
+use crate::helpers::parsers::InscriptionKind;
+use crate::helpers::{PUBLICKEY_TAG, RANDOM_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG};
 use crate::spec::utxo::UTXO;
 
+/// Largest compressed blob that is inscribed in a single reveal tx. Bigger blobs are split into
+/// chunks of this size, which keeps every reveal tx below the standard tx weight of 400k, and a chunk
+/// commit and reveal tx together below the default mempool ancestor size limit of 101 kvB.
+pub const MAX_INSCRIPTION_BODY_SIZE: usize = 390_000;
+
 pub fn compress_blob(blob: &[u8]) -> Vec<u8> {
     let mut writer = CompressorWriter::new(Vec::new(), 4096, 11, 22);
     writer.write_all(blob).unwrap();
@@ -353,6 +358,202 @@ pub fn create_inscription_transactions(
     reveal_fee_rate: f64,
     network: Network,
     reveal_tx_prefix: &[u8],
+) -> Result<(Transaction, TxWithId), anyhow::Error> {
+    create_envelope_transactions(
+        InscriptionKind::Complete,
+        rollup_name,
+        body,
+        signature,
+        sequencer_public_key,
+        prev_tx,
+        utxos,
+        recipient,
+        reveal_value,
+        commit_fee_rate,
+        reveal_fee_rate,
+        network,
+        reveal_tx_prefix,
+    )
+}
+
+// Creates the inscription transactions of a blob that is too big for a single reveal tx.
+// Every chunk gets its own commit and reveal tx, followed by the aggregate inscription which
+// references the chunk reveal txs by wtxid. A chunk reveal tx is close to the ancestor and
+// descendant size limits of the mempool by itself, so the chunk commit txs spend confirmed utxos
+// only and do not depend on each other or on earlier inscriptions. The aggregate continues the
+// chain from `prev_tx` and has to be broadcast after the chunks are confirmed. It is signed by
+// `sign_aggregate` over the wtxids of the chunks. Chunks are `(body, signature)` pairs.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "native", instrument(level = "trace", skip_all, err))]
+pub fn create_chunked_inscription_transactions(
+    rollup_name: &str,
+    chunks: Vec<(Vec<u8>, Vec<u8>)>,
+    sign_aggregate: impl FnOnce(&[u8]) -> Vec<u8>,
+    sequencer_public_key: Vec<u8>,
+    prev_tx: Option<TxWithId>,
+    mut utxos: Vec<UTXO>,
+    recipient: Address,
+    reveal_value: u64,
+    commit_fee_rate: f64,
+    reveal_fee_rate: f64,
+    network: Network,
+    reveal_tx_prefix: &[u8],
+) -> Result<Vec<(Transaction, TxWithId)>, anyhow::Error> {
+    let mut txs = Vec::with_capacity(chunks.len() + 1);
+    let mut chunk_wtxids = Vec::with_capacity(chunks.len() * 32);
+    let mut confirmed_utxos: Vec<UTXO> = utxos
+        .iter()
+        .filter(|utxo| utxo.confirmations > 0)
+        .cloned()
+        .collect();
+
+    for (body, signature) in chunks {
+        let (commit, reveal) = create_envelope_transactions(
+            InscriptionKind::Chunk,
+            rollup_name,
+            body,
+            signature,
+            sequencer_public_key.clone(),
+            None,
+            confirmed_utxos.clone(),
+            recipient.clone(),
+            reveal_value,
+            commit_fee_rate,
+            reveal_fee_rate,
+            network,
+            reveal_tx_prefix,
+        )?;
+
+        // the next chunk commit tx must not spend the same utxos again, nor the change
+        confirmed_utxos.retain(|utxo| !spends_utxo(&commit, utxo));
+        // the aggregate commit tx is broadcast after the chunks are confirmed, so it can spend the change
+        spend_utxos(&mut utxos, &commit, &recipient);
+
+        chunk_wtxids.extend_from_slice(reveal.tx.wtxid().as_byte_array());
+        txs.push((commit, reveal));
+    }
+
+    let aggregate_signature = sign_aggregate(&chunk_wtxids);
+    let aggregate = create_envelope_transactions(
+        InscriptionKind::Aggregate,
+        rollup_name,
+        chunk_wtxids,
+        aggregate_signature,
+        sequencer_public_key,
+        prev_tx,
+        utxos,
+        recipient,
+        reveal_value,
+        commit_fee_rate,
+        reveal_fee_rate,
+        network,
+        reveal_tx_prefix,
+    )?;
+    txs.push(aggregate);
+
+    Ok(txs)
+}
+
+// Replaces the utxos spent by a commit tx that is not broadcast yet with its change output
+fn spend_utxos(utxos: &mut Vec<UTXO>, commit_tx: &Transaction, change_address: &Address) {
+    utxos.retain(|utxo| !spends_utxo(commit_tx, utxo));
+
+    if let Some(change) = commit_tx.output.get(1) {
+        utxos.push(UTXO {
+            tx_id: commit_tx.txid(),
+            vout: 1,
+            address: change_address.to_string(),
+            script_pubkey: change.script_pubkey.to_hex_string(),
+            amount: change.value.to_sat(),
+            confirmations: 0,
+            spendable: true,
+            solvable: true,
+        });
+    }
+}
+
+fn spends_utxo(tx: &Transaction, utxo: &UTXO) -> bool {
+    tx.input.iter().any(|input| {
+        input.previous_output.txid == utxo.tx_id && input.previous_output.vout == utxo.vout
+    })
+}
+
+// Creates a child tx spending the first output of `parent` and some of `utxos`, which pays
+// `fee_for_vsize` of its own size so that miners include the unconfirmed ancestors of `parent`
+// with it (CPFP). The rest is paid to `change_address` in a single output, which the next commit
+// tx spends instead of the output of `parent`.
+pub fn build_cpfp_transaction(
+    parent: &TxWithId,
+    mut utxos: Vec<UTXO>,
+    change_address: &Address,
+    fee_for_vsize: impl Fn(usize) -> u64,
+) -> Result<Transaction, anyhow::Error> {
+    let parent_output = &parent.tx.output[0];
+    let parent_utxo = UTXO {
+        tx_id: parent.id,
+        vout: 0,
+        script_pubkey: parent_output.script_pubkey.to_hex_string(),
+        address: "ANY".into(),
+        amount: parent_output.value.to_sat(),
+        confirmations: 0,
+        spendable: true,
+        solvable: true,
+    };
+    utxos.retain(|utxo| !(utxo.tx_id == parent.id && utxo.vout == 0));
+
+    let to_input = |utxo: &UTXO| TxIn {
+        previous_output: OutPoint {
+            txid: utxo.tx_id,
+            vout: utxo.vout,
+        },
+        script_sig: script::Builder::new().into_script(),
+        witness: Witness::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+    };
+    let change_output = |value: u64| TxOut {
+        value: Amount::from_sat(value),
+        script_pubkey: change_address.script_pubkey(),
+    };
+
+    let mut last_size = get_size(&[to_input(&parent_utxo)], &[change_output(546)], None, None);
+
+    loop {
+        let fee = fee_for_vsize(last_size);
+
+        let (chosen_utxos, sum) = choose_utxos(Some(parent_utxo.clone()), &utxos, fee + 546)?;
+
+        let tx = Transaction {
+            lock_time: LockTime::ZERO,
+            version: bitcoin::transaction::Version(2),
+            input: chosen_utxos.iter().map(to_input).collect(),
+            output: vec![change_output(sum - fee)],
+        };
+
+        // a smaller tx pays a bit more than needed, which is fine
+        let size = get_size(&tx.input, &tx.output, None, None);
+        if size <= last_size {
+            break Ok(tx);
+        }
+
+        last_size = size;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_envelope_transactions(
+    kind: InscriptionKind,
+    rollup_name: &str,
+    body: Vec<u8>,
+    signature: Vec<u8>,
+    sequencer_public_key: Vec<u8>,
+    prev_tx: Option<TxWithId>,
+    utxos: Vec<UTXO>,
+    recipient: Address,
+    reveal_value: u64,
+    commit_fee_rate: f64,
+    reveal_fee_rate: f64,
+    network: Network,
+    reveal_tx_prefix: &[u8],
 ) -> Result<(Transaction, TxWithId), anyhow::Error> {
     // Create commit key
     let secp256k1 = Secp256k1::new();
@@ -400,7 +601,7 @@ pub fn create_inscription_transactions(
         // push first random number and body tag
         reveal_script_builder = reveal_script_builder
             .push_int(nonce)
-            .push_slice(PushBytesBuf::try_from(BODY_TAG.to_vec()).expect("Cannot push body tag"));
+            .push_slice(PushBytesBuf::try_from(kind.tag().to_vec()).expect("Cannot push body tag"));
 
         // push body in chunks of 520 bytes
         for chunk in body.chunks(520) {
@@ -538,12 +739,6 @@ pub fn create_inscription_transactions(
     }
 }
 
-pub fn write_reveal_tx(tx: &[u8], tx_id: String) {
-    let reveal_tx_file = File::create(format!("reveal_{}.tx", tx_id)).unwrap();
-    let mut reveal_tx_writer = BufWriter::new(reveal_tx_file);
-    reveal_tx_writer.write_all(tx).unwrap();
-}
-
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
@@ -554,8 +749,8 @@ mod tests {
     use bitcoin::taproot::ControlBlock;
     use bitcoin::{Address, Amount, ScriptBuf, TxOut, Txid};
 
-    use crate::helpers::builders::{compress_blob, decompress_blob};
-    use crate::helpers::parsers::parse_transaction;
+    use crate::helpers::builders::{compress_blob, decompress_blob, sign_blob_with_private_key};
+    use crate::helpers::parsers::{parse_complete_inscriptions, parse_transaction};
     use crate::spec::utxo::UTXO;
 
     #[test]
@@ -583,20 +778,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn write_reveal_tx() {
-        let tx = vec![100, 100, 100];
-        let tx_id = "test_tx".to_string();
-
-        super::write_reveal_tx(tx.as_slice(), tx_id);
-
-        let file = std::fs::read("reveal_test_tx.tx").unwrap();
-
-        assert_eq!(tx, file);
-
-        std::fs::remove_file("reveal_test_tx.tx").unwrap();
-    }
-
     #[allow(clippy::type_complexity)]
     fn get_mock_data() -> (&'static str, Vec<u8>, Vec<u8>, Vec<u8>, Address, Vec<UTXO>) {
         let rollup_name = "test_rollup";
@@ -698,6 +879,45 @@ mod tests {
         assert_eq!(format!("{}", res.unwrap_err()), "not enough UTXOs");
     }
 
+    #[test]
+    fn build_cpfp_transaction() {
+        let (_, _, _, _, address, utxos) = get_mock_data();
+
+        let parent = super::TxWithId {
+            id: Txid::from_str("1b5e9a8fd2bd1bcbd5bc5e5ae8dde76b5e4b0fb9b0d1dfcc2e8e1e1efbe4b9a1")
+                .unwrap(),
+            tx: bitcoin::Transaction {
+                lock_time: bitcoin::absolute::LockTime::ZERO,
+                version: bitcoin::transaction::Version(2),
+                input: vec![],
+                output: vec![TxOut {
+                    value: Amount::from_sat(546),
+                    script_pubkey: address.script_pubkey(),
+                }],
+            },
+        };
+
+        let fee_for_vsize = |vsize: usize| 5_000 + vsize as u64 * 10;
+        let tx =
+            super::build_cpfp_transaction(&parent, utxos.clone(), &address, fee_for_vsize).unwrap();
+
+        // the output of the parent is spent first, the smallest utxo covering the fee is added
+        assert_eq!(tx.input.len(), 2);
+        assert_eq!(tx.input[0].previous_output.txid, parent.id);
+        assert_eq!(tx.input[0].previous_output.vout, 0);
+        assert_eq!(tx.input[1].previous_output.txid, utxos[2].tx_id);
+
+        assert_eq!(tx.output.len(), 1);
+        assert_eq!(tx.output[0].script_pubkey, address.script_pubkey());
+
+        let size = super::get_size(&tx.input, &tx.output, None, None);
+        let fee = 546 + utxos[2].amount - tx.output[0].value.to_sat();
+        assert_eq!(fee, fee_for_vsize(size));
+
+        // the fee cannot be paid
+        assert!(super::build_cpfp_transaction(&parent, utxos, &address, |_| 100_000_000).is_err());
+    }
+
     #[test]
     fn build_commit_transaction() {
         let (_, _, _, _, address, utxos) = get_mock_data();
@@ -1058,4 +1278,82 @@ mod tests {
             "sequencer public key should be correct"
         );
     }
+
+    #[test]
+    fn create_chunked_inscription_transactions() {
+        let (rollup_name, _, _, _, address, utxos) = get_mock_data();
+        let private_key = bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+
+        let body: Vec<u8> = (0..900).map(|i| i as u8).collect();
+        let chunks = body
+            .chunks(300)
+            .map(|chunk| {
+                let (signature, _) = sign_blob_with_private_key(chunk, &private_key).unwrap();
+                (chunk.to_vec(), signature)
+            })
+            .collect::<Vec<_>>();
+        let (_, public_key) = sign_blob_with_private_key(&body, &private_key).unwrap();
+
+        let tx_prefix = &[0u8];
+        let txs = super::create_chunked_inscription_transactions(
+            rollup_name,
+            chunks,
+            |wtxids| sign_blob_with_private_key(wtxids, &private_key).unwrap().0,
+            public_key.clone(),
+            None,
+            utxos.clone(),
+            address,
+            546,
+            12.0,
+            10.0,
+            bitcoin::Network::Bitcoin,
+            tx_prefix,
+        )
+        .unwrap();
+
+        assert_eq!(txs.len(), 4, "3 chunks and the aggregate");
+
+        // the chunk commit txs only spend confirmed utxos, not the outputs of each other
+        for (commit, _) in txs[..3].iter() {
+            for input in commit.input.iter() {
+                assert!(
+                    utxos
+                        .iter()
+                        .any(|utxo| utxo.tx_id == input.previous_output.txid
+                            && utxo.vout == input.previous_output.vout),
+                    "chunk commit should spend a confirmed utxo"
+                );
+            }
+        }
+
+        let mut spent = txs
+            .iter()
+            .flat_map(|(commit, _)| commit.input.iter().map(|input| input.previous_output))
+            .collect::<Vec<_>>();
+        let spent_count = spent.len();
+        spent.sort();
+        spent.dedup();
+        assert_eq!(spent.len(), spent_count, "no output should be spent twice");
+
+        // the chunks are found in the same block or by wtxid in earlier blocks
+        let reveals = txs
+            .iter()
+            .map(|(_, reveal)| reveal.tx.clone())
+            .collect::<Vec<_>>();
+        for (block, earlier_chunks) in [(&reveals[..], &[][..]), (&reveals[3..], &reveals[..3])] {
+            let inscriptions =
+                parse_complete_inscriptions(block.iter(), earlier_chunks, rollup_name);
+            assert_eq!(inscriptions.len(), 1);
+            let (inscription, hash) = &inscriptions[0];
+            assert_eq!(inscription.body, body);
+            assert_eq!(inscription.public_key, public_key);
+            assert_eq!(
+                hash,
+                &bitcoin::hashes::sha256d::Hash::hash(&body).to_byte_array()
+            );
+        }
+
+        // an aggregate with a missing chunk is not a blob
+        assert!(parse_complete_inscriptions(reveals[1..].iter(), &[], rollup_name).is_empty());
+    }
 }
@@ -3,7 +3,10 @@ const ROLLUP_NAME_TAG: &[u8] = &[1];
 const SIGNATURE_TAG: &[u8] = &[2];
 const PUBLICKEY_TAG: &[u8] = &[3];
 const RANDOM_TAG: &[u8] = &[4];
+// The tag before the body also tells what the body holds
 const BODY_TAG: &[u8] = &[];
+const AGGREGATE_TAG: &[u8] = &[5];
+const CHUNK_TAG: &[u8] = &[6];
 
 pub mod builders;
 pub mod parsers;
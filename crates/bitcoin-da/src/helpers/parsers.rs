@@ -1,4 +1,5 @@
 use core::iter::Peekable;
+use std::collections::HashMap;
 
 use bitcoin::blockdata::opcodes::all::{OP_ENDIF, OP_IF};
 use bitcoin::blockdata::script::{Instruction, Instructions};
@@ -14,10 +15,48 @@ use bitcoin::secp256k1::{ecdsa, Message, Secp256k1};
 use bitcoin::{secp256k1, Script, Transaction};
 use serde::{Deserialize, Serialize};
 
-use super::{BODY_TAG, PUBLICKEY_TAG, RANDOM_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG};
+use super::{
+    AGGREGATE_TAG, BODY_TAG, CHUNK_TAG, PUBLICKEY_TAG, RANDOM_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG,
+};
+
+/// What the body of an inscription holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InscriptionKind {
+    /// The whole compressed blob
+    Complete,
+    /// A part of a compressed blob that does not fit into a single transaction
+    Chunk,
+    /// The wtxids of the chunk reveal transactions, in the order their bodies make up the blob.
+    /// The signature is over the wtxids, which bind the chunks to it.
+    Aggregate,
+}
+
+impl InscriptionKind {
+    /// The tag pushed before the body
+    pub(crate) fn tag(self) -> &'static [u8] {
+        match self {
+            InscriptionKind::Complete => BODY_TAG,
+            InscriptionKind::Chunk => CHUNK_TAG,
+            InscriptionKind::Aggregate => AGGREGATE_TAG,
+        }
+    }
+
+    fn from_tag(tag: &[u8]) -> Option<Self> {
+        if tag == BODY_TAG {
+            Some(InscriptionKind::Complete)
+        } else if tag == CHUNK_TAG {
+            Some(InscriptionKind::Chunk)
+        } else if tag == AGGREGATE_TAG {
+            Some(InscriptionKind::Aggregate)
+        } else {
+            None
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedInscription {
+    pub kind: InscriptionKind,
     pub body: Vec<u8>,
     pub signature: Vec<u8>,
     pub public_key: Vec<u8>,
@@ -44,6 +83,20 @@ impl ParsedInscription {
             None
         }
     }
+
+    /// Returns the wtxids of the chunks if this is an aggregate inscription
+    pub fn chunk_wtxids(&self) -> Option<Vec<[u8; 32]>> {
+        if self.kind != InscriptionKind::Aggregate || self.body.len() % 32 != 0 {
+            return None;
+        }
+
+        Some(
+            self.body
+                .chunks_exact(32)
+                .map(|wtxid| wtxid.try_into().expect("chunk is 32 bytes"))
+                .collect(),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -64,6 +117,83 @@ pub fn parse_transaction(
     parse_relevant_inscriptions(&mut instructions, rollup_name)
 }
 
+/// Parses the inscriptions of the rollup in `txs` and returns the complete blobs among them, in order,
+/// with their hash. Aggregate inscriptions are reassembled from their chunks, which are looked up
+/// by wtxid among `txs` and `earlier_chunks`, the chunk reveal txs of earlier blocks.
+/// Anyone can publish an aggregate, so one whose chunks are missing or invalid is not a blob.
+pub fn parse_complete_inscriptions<'a>(
+    txs: impl IntoIterator<Item = &'a Transaction>,
+    earlier_chunks: &[Transaction],
+    rollup_name: &str,
+) -> Vec<(ParsedInscription, [u8; 32])> {
+    let parsed: Vec<([u8; 32], ParsedInscription)> = txs
+        .into_iter()
+        .filter_map(|tx| {
+            parse_transaction(tx, rollup_name)
+                .ok()
+                .map(|inscription| (tx.wtxid().to_byte_array(), inscription))
+        })
+        .collect();
+
+    let earlier_chunks: Vec<([u8; 32], ParsedInscription)> = earlier_chunks
+        .iter()
+        .filter_map(|tx| {
+            parse_transaction(tx, rollup_name)
+                .ok()
+                .map(|inscription| (tx.wtxid().to_byte_array(), inscription))
+        })
+        .collect();
+
+    let chunks: HashMap<[u8; 32], &ParsedInscription> = earlier_chunks
+        .iter()
+        .chain(parsed.iter())
+        .filter(|(_, inscription)| inscription.kind == InscriptionKind::Chunk)
+        .map(|(wtxid, inscription)| (*wtxid, inscription))
+        .collect();
+
+    parsed
+        .iter()
+        .filter_map(|(_, inscription)| match inscription.kind {
+            InscriptionKind::Complete => inscription
+                .get_sig_verified_hash()
+                .map(|hash| (inscription.clone(), hash)),
+            InscriptionKind::Chunk => None,
+            InscriptionKind::Aggregate => {
+                // an aggregate is signed over the wtxids of its chunks
+                inscription.get_sig_verified_hash()?;
+                let chunk_wtxids = inscription.chunk_wtxids()?;
+                reassemble_aggregate(inscription, &chunk_wtxids, &chunks)
+            }
+        })
+        .collect()
+}
+
+// Concatenates the bodies of the chunks of the aggregate into a complete inscription.
+// Every chunk must be present and signed by the same key as the aggregate.
+fn reassemble_aggregate(
+    aggregate: &ParsedInscription,
+    chunk_wtxids: &[[u8; 32]],
+    chunks: &HashMap<[u8; 32], &ParsedInscription>,
+) -> Option<(ParsedInscription, [u8; 32])> {
+    let mut body = Vec::new();
+    for wtxid in chunk_wtxids {
+        let chunk = chunks.get(wtxid)?;
+        if chunk.public_key != aggregate.public_key || chunk.get_sig_verified_hash().is_none() {
+            return None;
+        }
+        body.extend_from_slice(&chunk.body);
+    }
+
+    let hash = sha256d::Hash::hash(&body).to_byte_array();
+    let inscription = ParsedInscription {
+        kind: InscriptionKind::Complete,
+        body,
+        signature: aggregate.signature.clone(),
+        public_key: aggregate.public_key.clone(),
+    };
+    Some((inscription, hash))
+}
+
 // Returns the script from the first input of the transaction
 fn get_script(tx: &Transaction) -> Result<&Script, ParserError> {
     tx.input[0]
@@ -82,6 +212,7 @@ fn parse_relevant_inscriptions(
     let mut inside_envelope = false;
     let mut inside_envelope_index = 0;
 
+    let mut kind = InscriptionKind::Complete;
     let mut body: Vec<u8> = Vec::new();
     let mut signature: Vec<u8> = Vec::new();
     let mut public_key: Vec<u8> = Vec::new();
@@ -142,9 +273,11 @@ fn parse_relevant_inscriptions(
                         || (inside_envelope_index == 2 && bytes.as_bytes() != SIGNATURE_TAG)
                         || (inside_envelope_index == 4 && bytes.as_bytes() != PUBLICKEY_TAG)
                         || (inside_envelope_index == 6 && bytes.as_bytes() != RANDOM_TAG)
-                        || (inside_envelope_index == 8 && bytes.as_bytes() != BODY_TAG)
                     {
                         return Err(ParserError::EnvelopeHasIncorrectFormat);
+                    } else if inside_envelope_index == 8 {
+                        kind = InscriptionKind::from_tag(bytes.as_bytes())
+                            .ok_or(ParserError::EnvelopeHasIncorrectFormat)?;
                     } else if inside_envelope_index == 1
                         && bytes.as_bytes() != rollup_name.as_bytes()
                     {
@@ -178,6 +311,7 @@ fn parse_relevant_inscriptions(
     }
 
     Ok(ParsedInscription {
+        kind,
         body,
         signature,
         public_key,
@@ -204,10 +338,10 @@ mod tests {
     use bitcoin::Transaction;
 
     use super::{
-        parse_relevant_inscriptions, BODY_TAG, PUBLICKEY_TAG, RANDOM_TAG, ROLLUP_NAME_TAG,
-        SIGNATURE_TAG,
+        parse_relevant_inscriptions, AGGREGATE_TAG, BODY_TAG, PUBLICKEY_TAG, RANDOM_TAG,
+        ROLLUP_NAME_TAG, SIGNATURE_TAG,
     };
-    use crate::helpers::parsers::{parse_transaction, ParserError};
+    use crate::helpers::parsers::{parse_transaction, InscriptionKind, ParserError};
 
     #[test]
     fn correct() {
@@ -242,6 +376,62 @@ mod tests {
         assert_eq!(result.public_key, vec![0u8; 64]);
     }
 
+    #[test]
+    fn aggregate() {
+        let reveal_script_builder = script::Builder::new()
+            .push_x_only_key(&XOnlyPublicKey::from_slice(&[1; 32]).unwrap())
+            .push_opcode(OP_CHECKSIG)
+            .push_opcode(OP_FALSE)
+            .push_opcode(OP_IF)
+            .push_slice(PushBytesBuf::try_from(ROLLUP_NAME_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from("sov-btc".as_bytes().to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(SIGNATURE_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(vec![0u8; 64]).unwrap())
+            .push_slice(PushBytesBuf::try_from(PUBLICKEY_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(vec![0u8; 64]).unwrap())
+            .push_slice(PushBytesBuf::try_from(RANDOM_TAG.to_vec()).unwrap())
+            .push_int(0)
+            .push_slice(PushBytesBuf::try_from(AGGREGATE_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from([[1u8; 32], [2u8; 32]].concat()).unwrap())
+            .push_opcode(OP_ENDIF);
+
+        let reveal_script = reveal_script_builder.into_script();
+
+        let result =
+            parse_relevant_inscriptions(&mut reveal_script.instructions().peekable(), "sov-btc")
+                .unwrap();
+
+        assert_eq!(result.kind, InscriptionKind::Aggregate);
+        assert_eq!(result.chunk_wtxids(), Some(vec![[1u8; 32], [2u8; 32]]));
+    }
+
+    #[test]
+    fn unknown_body_tag() {
+        let reveal_script_builder = script::Builder::new()
+            .push_x_only_key(&XOnlyPublicKey::from_slice(&[1; 32]).unwrap())
+            .push_opcode(OP_CHECKSIG)
+            .push_opcode(OP_FALSE)
+            .push_opcode(OP_IF)
+            .push_slice(PushBytesBuf::try_from(ROLLUP_NAME_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from("sov-btc".as_bytes().to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(SIGNATURE_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(vec![0u8; 64]).unwrap())
+            .push_slice(PushBytesBuf::try_from(PUBLICKEY_TAG.to_vec()).unwrap())
+            .push_slice(PushBytesBuf::try_from(vec![0u8; 64]).unwrap())
+            .push_slice(PushBytesBuf::try_from(RANDOM_TAG.to_vec()).unwrap())
+            .push_int(0)
+            .push_slice(PushBytesBuf::try_from(vec![7u8]).unwrap())
+            .push_slice(PushBytesBuf::try_from(vec![0u8; 128]).unwrap())
+            .push_opcode(OP_ENDIF);
+
+        let reveal_script = reveal_script_builder.into_script();
+
+        let result =
+            parse_relevant_inscriptions(&mut reveal_script.instructions().peekable(), "sov-btc");
+
+        assert_eq!(result.unwrap_err(), ParserError::EnvelopeHasIncorrectFormat);
+    }
+
     #[test]
     fn wrong_rollup_tag() {
         let reveal_script_builder = script::Builder::new()
@@ -91,6 +91,7 @@ pub(crate) fn get_mock_data() -> (
             .map(|t| t.wtxid().to_byte_array())
             .collect(),
         coinbase_tx: block_txs[0].clone(),
+        earlier_chunks: vec![],
     };
 
     // Coinbase tx wtxid should be [0u8;32]
@@ -1,10 +1,16 @@
+#[cfg(feature = "native")]
+mod header_tracker;
 mod helpers;
 #[cfg(feature = "native")]
+mod outbox;
+#[cfg(feature = "native")]
 mod rpc;
 pub mod spec;
 
 #[cfg(feature = "native")]
 pub mod service;
 pub mod verifier;
+#[cfg(feature = "native")]
+mod wallet;
 
 const REVEAL_OUTPUT_AMOUNT: u64 = 546;
@@ -0,0 +1,401 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bitcoin::consensus::encode;
+use bitcoin::Transaction;
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::builders::TxWithId;
+
+/// Name of the outbox file in the storage directory of the node if `outbox_path` is not set in the config
+pub const OUTBOX_FILE_NAME: &str = "bitcoin_da_outbox.json";
+
+/// How far an inscription got in being published
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InscriptionState {
+    /// Commit and reveal txs are signed, nothing is broadcast yet
+    Built,
+    /// Chunk txs are broadcast, the commit tx waits for them to be confirmed
+    ChunksBroadcast,
+    /// Commit tx is broadcast
+    CommitBroadcast,
+    /// Reveal tx is broadcast
+    RevealBroadcast,
+    /// Reveal tx is included in a block
+    Confirmed,
+}
+
+/// Commit/reveal pair inscribing a chunk of a blob that is too big for a single reveal tx
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChunkTxs {
+    /// Signed commit tx, hex encoded
+    pub commit_tx: String,
+    /// Signed reveal tx, hex encoded
+    pub reveal_tx: String,
+    /// Signed txs bumping the fee of the chunk (CPFP), hex encoded.
+    /// Each spends the first output of the previous one, starting from the reveal tx.
+    #[serde(default)]
+    pub cpfp_txs: Vec<String>,
+}
+
+impl ChunkTxs {
+    pub fn new(signed_commit_tx: String, reveal_tx: &Transaction) -> Self {
+        Self {
+            commit_tx: signed_commit_tx,
+            reveal_tx: hex::encode(encode::serialize(reveal_tx)),
+            cpfp_txs: vec![],
+        }
+    }
+
+    pub fn reveal(&self) -> Result<TxWithId, anyhow::Error> {
+        decode_tx(&self.reveal_tx)
+    }
+
+    /// The last tx of the chunk, whose first output a CPFP tx has to spend
+    pub fn chain_tail(&self) -> Result<TxWithId, anyhow::Error> {
+        decode_tx(self.cpfp_txs.last().unwrap_or(&self.reveal_tx))
+    }
+}
+
+/// Commit/reveal pair inscribing a single blob
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// sha256d of the uncompressed blob, so the same blob is never inscribed twice
+    pub blob_hash: [u8; 32],
+    /// Uncompressed blob, hex encoded. Kept to rebuild the txs with a higher fee.
+    pub blob: String,
+    /// Signed commit tx, hex encoded
+    pub commit_tx: String,
+    /// Signed reveal tx, hex encoded
+    pub reveal_tx: String,
+    /// Chunks of the blob if it is inscribed in chunks, which are confirmed before the commit tx is
+    /// broadcast. The commit and reveal txs then inscribe the aggregate referencing the chunks.
+    #[serde(default)]
+    pub chunks: Vec<ChunkTxs>,
+    /// Fee rate of both txs in sat/vB
+    pub fee_rate: f64,
+    /// Bitcoin block height when the reveal tx was broadcast, or the chunks while they are waited for
+    pub broadcast_height: Option<u64>,
+    pub state: InscriptionState,
+    /// Signed txs bumping the fee of the unconfirmed inscriptions up to this one (CPFP), hex encoded.
+    /// Each spends the first output of the previous one, starting from the reveal tx.
+    #[serde(default)]
+    pub cpfp_txs: Vec<String>,
+    /// Bitcoin block height when the last CPFP tx was broadcast
+    #[serde(default)]
+    pub bumped_height: Option<u64>,
+}
+
+impl OutboxEntry {
+    pub fn new(
+        blob_hash: [u8; 32],
+        blob: &[u8],
+        signed_commit_tx: String,
+        reveal_tx: &Transaction,
+        fee_rate: f64,
+    ) -> Self {
+        Self {
+            blob_hash,
+            blob: hex::encode(blob),
+            commit_tx: signed_commit_tx,
+            reveal_tx: hex::encode(encode::serialize(reveal_tx)),
+            chunks: vec![],
+            fee_rate,
+            broadcast_height: None,
+            state: InscriptionState::Built,
+            cpfp_txs: vec![],
+            bumped_height: None,
+        }
+    }
+
+    pub fn with_chunks(mut self, chunks: Vec<ChunkTxs>) -> Self {
+        self.chunks = chunks;
+        self
+    }
+
+    /// All signed txs of the inscription in the order they have to be broadcast
+    pub fn raw_txs(&self) -> Vec<String> {
+        self.chunk_raw_txs()
+            .into_iter()
+            .chain([self.commit_tx.clone(), self.reveal_tx.clone()])
+            .chain(self.cpfp_txs.iter().cloned())
+            .collect()
+    }
+
+    /// Signed txs of the chunks, which are broadcast before the others
+    pub fn chunk_raw_txs(&self) -> Vec<String> {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| {
+                [chunk.commit_tx.clone(), chunk.reveal_tx.clone()]
+                    .into_iter()
+                    .chain(chunk.cpfp_txs.iter().cloned())
+            })
+            .collect()
+    }
+
+    pub fn reveal(&self) -> Result<TxWithId, anyhow::Error> {
+        decode_tx(&self.reveal_tx)
+    }
+
+    /// The last tx of the inscription, whose first output the next commit tx has to spend:
+    /// the last CPFP tx if the inscription was bumped, otherwise the reveal tx
+    pub fn chain_tail(&self) -> Result<TxWithId, anyhow::Error> {
+        decode_tx(self.cpfp_txs.last().unwrap_or(&self.reveal_tx))
+    }
+}
+
+fn decode_tx(raw_tx: &str) -> Result<TxWithId, anyhow::Error> {
+    let tx: Transaction = encode::deserialize(&hex::decode(raw_tx)?)?;
+    Ok(TxWithId { id: tx.txid(), tx })
+}
+
+/// Inscriptions that are being published, persisted to disk so that publishing can be resumed
+/// after a restart. Entries are kept in the order they were built, which is also the order of
+/// the UTXO chain: every commit spends the output of the previous reveal.
+#[derive(Debug)]
+pub struct InscriptionOutbox {
+    path: PathBuf,
+    entries: Vec<OutboxEntry>,
+}
+
+impl InscriptionOutbox {
+    /// Loads the outbox from `path`, or creates an empty one if the file does not exist
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, anyhow::Error> {
+        let path = path.into();
+        let entries = if path.exists() {
+            serde_json::from_slice(&fs::read(&path)?)?
+        } else {
+            vec![]
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn get(&self, blob_hash: &[u8; 32]) -> Option<&OutboxEntry> {
+        self.entries
+            .iter()
+            .find(|entry| &entry.blob_hash == blob_hash)
+    }
+
+    /// Entries whose reveal tx is not known to be confirmed yet
+    pub fn pending(&self) -> Vec<OutboxEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state != InscriptionState::Confirmed)
+            .cloned()
+            .collect()
+    }
+
+    /// Last tx of the latest inscription, whose output the next commit tx has to spend
+    pub fn chain_tail(&self) -> Result<Option<TxWithId>, anyhow::Error> {
+        self.entries.last().map(OutboxEntry::chain_tail).transpose()
+    }
+
+    pub fn insert(&mut self, entry: OutboxEntry) -> Result<(), anyhow::Error> {
+        self.entries.push(entry);
+        self.save()
+    }
+
+    pub fn set_state(
+        &mut self,
+        blob_hash: &[u8; 32],
+        state: InscriptionState,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| &entry.blob_hash == blob_hash)
+        {
+            entry.state = state;
+        }
+        self.save()
+    }
+
+    pub fn set_broadcast_height(
+        &mut self,
+        blob_hash: &[u8; 32],
+        height: u64,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| &entry.blob_hash == blob_hash)
+        {
+            entry.broadcast_height = Some(height);
+        }
+        self.save()
+    }
+
+    /// Appends a CPFP tx to the inscription, which has to be the latest one
+    pub fn add_cpfp_tx(
+        &mut self,
+        blob_hash: &[u8; 32],
+        raw_tx: String,
+        height: u64,
+    ) -> Result<(), anyhow::Error> {
+        match self.entries.last_mut() {
+            Some(entry) if &entry.blob_hash == blob_hash => {
+                entry.cpfp_txs.push(raw_tx);
+                entry.bumped_height = Some(height);
+            }
+            _ => anyhow::bail!("Only the latest inscription can be bumped"),
+        }
+        self.save()
+    }
+
+    /// Appends a CPFP tx to a chunk of the inscription, which has to be the latest one
+    pub fn add_chunk_cpfp_tx(
+        &mut self,
+        blob_hash: &[u8; 32],
+        chunk_index: usize,
+        raw_tx: String,
+        height: u64,
+    ) -> Result<(), anyhow::Error> {
+        match self.entries.last_mut() {
+            Some(entry) if &entry.blob_hash == blob_hash => {
+                let chunk = entry
+                    .chunks
+                    .get_mut(chunk_index)
+                    .ok_or_else(|| anyhow::anyhow!("Inscription has no chunk {}", chunk_index))?;
+                chunk.cpfp_txs.push(raw_tx);
+                entry.bumped_height = Some(height);
+            }
+            _ => anyhow::bail!("Only the latest inscription can be bumped"),
+        }
+        self.save()
+    }
+
+    pub fn remove(&mut self, blob_hash: &[u8; 32]) -> Result<(), anyhow::Error> {
+        self.entries.retain(|entry| &entry.blob_hash != blob_hash);
+        self.save()
+    }
+
+    /// Drops confirmed entries. The latest entry is always kept to continue the UTXO chain.
+    pub fn prune_confirmed(&mut self) -> Result<(), anyhow::Error> {
+        let Some(last) = self.entries.pop() else {
+            return Ok(());
+        };
+        self.entries
+            .retain(|entry| entry.state != InscriptionState::Confirmed);
+        self.entries.push(last);
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), anyhow::Error> {
+        // Write to a temporary file first so a crash cannot leave a truncated outbox behind
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&self.entries)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::consensus::encode;
+    use bitcoin::{Amount, ScriptBuf, Transaction, TxOut};
+
+    use super::{ChunkTxs, InscriptionOutbox, InscriptionState, OutboxEntry};
+
+    fn tx(value: u64) -> Transaction {
+        Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn outbox_is_persisted() {
+        let path = std::env::temp_dir().join(format!(
+            "bitcoin_da_outbox_test_{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut outbox = InscriptionOutbox::load(&path).unwrap();
+        outbox
+            .insert(OutboxEntry::new(
+                [1; 32],
+                &[1],
+                "01".to_string(),
+                &tx(2),
+                1.0,
+            ))
+            .unwrap();
+        outbox
+            .insert(
+                OutboxEntry::new([2; 32], &[2], "03".to_string(), &tx(4), 1.0)
+                    .with_chunks(vec![ChunkTxs::new("05".to_string(), &tx(6))]),
+            )
+            .unwrap();
+        outbox
+            .set_state(&[1; 32], InscriptionState::Confirmed)
+            .unwrap();
+        outbox
+            .set_state(&[2; 32], InscriptionState::CommitBroadcast)
+            .unwrap();
+
+        let mut outbox = InscriptionOutbox::load(&path).unwrap();
+        assert_eq!(outbox.pending().len(), 1);
+        assert_eq!(
+            outbox.get(&[2; 32]).unwrap().state,
+            InscriptionState::CommitBroadcast
+        );
+        assert_eq!(outbox.chain_tail().unwrap().unwrap().id, tx(4).txid());
+        assert_eq!(
+            outbox.get(&[2; 32]).unwrap().raw_txs(),
+            vec![
+                "05".to_string(),
+                hex::encode(bitcoin::consensus::encode::serialize(&tx(6))),
+                "03".to_string(),
+                hex::encode(bitcoin::consensus::encode::serialize(&tx(4))),
+            ]
+        );
+
+        // the next commit tx spends the last CPFP tx of the latest inscription
+        assert!(outbox
+            .add_cpfp_tx(&[1; 32], encode::serialize_hex(&tx(7)), 100)
+            .is_err());
+        outbox
+            .add_cpfp_tx(&[2; 32], encode::serialize_hex(&tx(8)), 100)
+            .unwrap();
+        let mut outbox = InscriptionOutbox::load(&path).unwrap();
+        assert_eq!(outbox.chain_tail().unwrap().unwrap().id, tx(8).txid());
+        assert_eq!(outbox.get(&[2; 32]).unwrap().bumped_height, Some(100));
+        assert_eq!(outbox.get(&[2; 32]).unwrap().raw_txs().len(), 5);
+
+        // chunks are bumped on their own, their CPFP txs are broadcast with them
+        outbox
+            .add_chunk_cpfp_tx(&[2; 32], 0, encode::serialize_hex(&tx(9)), 101)
+            .unwrap();
+        assert!(outbox
+            .add_chunk_cpfp_tx(&[2; 32], 1, encode::serialize_hex(&tx(10)), 101)
+            .is_err());
+        let mut outbox = InscriptionOutbox::load(&path).unwrap();
+        let entry = outbox.get(&[2; 32]).unwrap();
+        assert_eq!(entry.chunks[0].chain_tail().unwrap().id, tx(9).txid());
+        assert_eq!(entry.chunk_raw_txs()[2], encode::serialize_hex(&tx(9)));
+        assert_eq!(entry.raw_txs().len(), 6);
+        assert_eq!(outbox.chain_tail().unwrap().unwrap().id, tx(8).txid());
+
+        // the latest entry is kept even if confirmed
+        outbox
+            .set_state(&[2; 32], InscriptionState::Confirmed)
+            .unwrap();
+        outbox.prune_confirmed().unwrap();
+        assert!(outbox.get(&[1; 32]).is_none());
+        assert!(outbox.get(&[2; 32]).is_some());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
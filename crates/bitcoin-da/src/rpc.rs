@@ -4,7 +4,7 @@ use core::str::FromStr;
 use bitcoin::block::{Header, Version};
 use bitcoin::hash_types::{TxMerkleNode, WitnessMerkleNode};
 use bitcoin::hashes::Hash;
-use bitcoin::{merkle_tree, BlockHash, CompactTarget, Wtxid};
+use bitcoin::{merkle_tree, Amount, BlockHash, CompactTarget, ScriptBuf, TxOut, Wtxid};
 use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
@@ -29,6 +29,26 @@ impl Display for RPCError {
     }
 }
 
+// PrevOut is an output spent by a tx to sign, in the format of signrawtransactionwithwallet
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrevOut {
+    pub txid: String,
+    pub vout: u32,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: String,
+    // in BTC
+    pub amount: f64,
+}
+
+// MempoolAncestors is the package of an unconfirmed tx and its unconfirmed ancestors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MempoolAncestors {
+    // virtual size of the package in vB
+    pub vsize: u64,
+    // fees of the package in sat
+    pub fees: u64,
+}
+
 // Response is a struct that represents a response returned by the Bitcoin RPC
 // It is generic over the type of the result field, which is usually a String in Bitcoin Core
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -96,7 +116,13 @@ impl BitcoinNode {
             return Ok(Err(error));
         }
 
-        Ok(Ok(response.result.unwrap()))
+        match response.result {
+            Some(result) => Ok(Ok(result)),
+            // null is a valid result for some calls, like gettxout for a spent output
+            None => Ok(Ok(
+                serde_json::from_str("null").expect("RPC returned a null result")
+            )),
+        }
     }
 
     // TODO: add max retries
@@ -213,6 +239,7 @@ impl BitcoinNode {
         Ok(BitcoinBlock {
             header: HeaderWrapper::new(header, txs.len() as u32, height, witness_root),
             txdata: txs,
+            earlier_chunks: vec![],
         })
     }
 
@@ -247,12 +274,17 @@ impl BitcoinNode {
     }
 
     // sign_raw_transaction_with_wallet signs a raw transaction with the wallet of bitcoind
+    // prev_outs describe the spent outputs of txs the node does not know about yet
     pub async fn sign_raw_transaction_with_wallet(
         &self,
         tx: String,
+        prev_outs: Vec<PrevOut>,
     ) -> Result<String, anyhow::Error> {
         let result = self
-            .call::<Box<RawValue>>("signrawtransactionwithwallet", vec![to_value(tx)?])
+            .call::<Box<RawValue>>(
+                "signrawtransactionwithwallet",
+                vec![to_value(tx)?, to_value(prev_outs)?],
+            )
             .await?
             .to_string();
 
@@ -267,6 +299,91 @@ impl BitcoinNode {
             .await
     }
 
+    // get_mempool_ancestors returns the size and fees of an unconfirmed tx together with its
+    // unconfirmed ancestors, or None if the tx is not in the mempool
+    pub async fn get_mempool_ancestors(
+        &self,
+        txid: String,
+    ) -> Result<Option<MempoolAncestors>, anyhow::Error> {
+        let result = match self
+            .call::<Box<RawValue>>("getmempoolentry", vec![to_value(txid)?])
+            .await
+        {
+            Ok(result) => result.to_string(),
+            // RPC_INVALID_ADDRESS_OR_KEY: the tx is not in the mempool
+            Err(e) if matches!(e.downcast_ref::<RPCError>(), Some(e) if e.code == -5) => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let entry: serde_json::Value = serde_json::from_str(&result)?;
+
+        let vsize = entry["ancestorsize"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("getmempoolentry returned no ancestorsize"))?;
+        let fees = Amount::from_btc(
+            entry["fees"]["ancestor"]
+                .as_f64()
+                .ok_or_else(|| anyhow::anyhow!("getmempoolentry returned no ancestor fees"))?,
+        )?;
+
+        Ok(Some(MempoolAncestors {
+            vsize,
+            fees: fees.to_sat(),
+        }))
+    }
+
+    // get_tx_confirmations returns the number of confirmations of a wallet transaction
+    pub async fn get_tx_confirmations(&self, txid: String) -> Result<u64, anyhow::Error> {
+        let result = self
+            .call::<Box<RawValue>>("gettransaction", vec![to_value(txid)?])
+            .await?
+            .to_string();
+
+        let tx: serde_json::Value = serde_json::from_str(&result)?;
+
+        // confirmations is negative if the tx conflicts with the chain
+        Ok(tx["confirmations"].as_i64().unwrap_or(0).max(0) as u64)
+    }
+
+    // get_tx_out returns an unspent output and its number of confirmations,
+    // or None if the output is spent (also in the mempool) or does not exist
+    pub async fn get_tx_out(
+        &self,
+        txid: String,
+        vout: u32,
+    ) -> Result<Option<(TxOut, u64)>, anyhow::Error> {
+        let result = self
+            .call::<Box<RawValue>>("gettxout", vec![to_value(txid)?, to_value(vout)?])
+            .await?
+            .to_string();
+
+        let tx_out: serde_json::Value = serde_json::from_str(&result)?;
+        if tx_out.is_null() {
+            return Ok(None);
+        }
+
+        let script_pubkey = ScriptBuf::from_hex(
+            tx_out["scriptPubKey"]["hex"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("gettxout returned no scriptPubKey"))?,
+        )?;
+        let value = Amount::from_btc(
+            tx_out["value"]
+                .as_f64()
+                .ok_or_else(|| anyhow::anyhow!("gettxout returned no value"))?,
+        )?;
+
+        Ok(Some((
+            TxOut {
+                value,
+                script_pubkey,
+            },
+            tx_out["confirmations"].as_u64().unwrap_or(0),
+        )))
+    }
+
     pub async fn list_wallets(&self) -> Result<Vec<String>, anyhow::Error> {
         let res = self.call::<Vec<String>>("listwallets", vec![]).await;
         match res {
@@ -5,26 +5,35 @@ use core::result::Result::Ok;
 use core::str::FromStr;
 use core::time::Duration;
 
-// use std::sync::Arc;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use bitcoin::consensus::encode;
 use bitcoin::hashes::{sha256d, Hash};
 use bitcoin::secp256k1::SecretKey;
-use bitcoin::{Address, BlockHash, Txid};
+use bitcoin::{Address, BlockHash, OutPoint, Transaction, TxOut, Txid};
 use hex::ToHex;
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::services::da::{BlobWithNotifier, DaService};
-use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tokio::sync::oneshot::channel as oneshot_channel;
-use tracing::{error, info, instrument, trace};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, trace, warn};
 
+use crate::header_tracker::HeaderTracker;
 use crate::helpers::builders::{
-    compress_blob, create_inscription_transactions, decompress_blob, sign_blob_with_private_key,
-    write_reveal_tx, TxWithId,
+    build_cpfp_transaction, compress_blob, create_chunked_inscription_transactions,
+    create_inscription_transactions, decompress_blob, sign_blob_with_private_key, TxWithId,
+    MAX_INSCRIPTION_BODY_SIZE,
 };
-use crate::helpers::parsers::parse_transaction;
-use crate::rpc::{BitcoinNode, RPCError};
+use crate::helpers::parsers::{parse_complete_inscriptions, parse_transaction};
+use crate::outbox::{ChunkTxs, InscriptionOutbox, InscriptionState, OutboxEntry, OUTBOX_FILE_NAME};
+use crate::rpc::{BitcoinNode, MempoolAncestors, PrevOut, RPCError};
 use crate::spec::blob::BlobWithSender;
 use crate::spec::block::BitcoinBlock;
 use crate::spec::header_stream::BitcoinHeaderStream;
@@ -32,6 +41,7 @@ use crate::spec::proof::InclusionMultiProof;
 use crate::spec::utxo::UTXO;
 use crate::spec::{BitcoinSpec, RollupParams};
 use crate::verifier::BitcoinVerifier;
+use crate::wallet::{NativeWallet, NATIVE_WALLET_FILE_NAME};
 use crate::REVEAL_OUTPUT_AMOUNT;
 
 /// A service that provides data and data availability proofs for Bitcoin
@@ -43,6 +53,16 @@ pub struct BitcoinService {
     da_private_key: Option<SecretKey>,
     reveal_tx_id_prefix: Vec<u8>,
     inscribes_queue: UnboundedSender<BlobWithNotifier<TxidWrapper>>,
+    fee_policy: FeePolicy,
+    // signs with da_private_key instead of the bitcoind wallet if set
+    native_wallet: Option<Arc<Mutex<NativeWallet>>>,
+    finality_depth: u64,
+    polling_interval: Duration,
+    // DA public keys of the sequencer and the prover, the only ones whose chunks are looked up in earlier blocks
+    known_da_public_keys: Vec<Vec<u8>>,
+    // cancelled to stop the inscription queue from accepting new requests
+    inscription_queue_closed: CancellationToken,
+    inscription_worker: Arc<Mutex<Option<JoinHandle<()>>>>,
 }
 
 /// Runtime configuration for the DA service
@@ -61,14 +81,78 @@ pub struct DaServiceConfig {
 
     // number of last paid fee rates to average if estimation fails
     pub fee_rates_to_avg: Option<usize>,
+
+    // file that keeps the inscriptions being published, so they can be resumed after a restart.
+    // defaults to a file in the storage directory of the node.
+    pub outbox_path: Option<String>,
+
+    // number of blocks an inscription can stay unconfirmed before its fee is bumped
+    pub fee_bump_after_blocks: Option<u64>,
+
+    // highest fee rate in sat/vB paid for inscriptions, including bumped ones
+    pub max_fee_rate: Option<f64>,
+
+    // sign with da_private_key in process and track its UTXOs instead of using the bitcoind wallet
+    pub native_wallet: Option<bool>,
+
+    // block height the native wallet starts looking for its UTXOs from.
+    // must be set if native_wallet is enabled, and not be pruned on the node on the first start.
+    // the wallet is kept in the storage directory of the node, and continues from its last block.
+    pub native_wallet_start_height: Option<u64>,
+
+    // number of blocks on top of a block for it to be considered finalized
+    pub finality_depth: Option<u64>,
+
+    // seconds between polls of the node for new blocks
+    pub polling_interval: Option<u64>,
 }
 
-const FINALITY_DEPTH: u64 = 4; // blocks
-const POLLING_INTERVAL: u64 = 10; // seconds
+/// How fees are paid for inscriptions
+#[derive(Debug, Clone, Copy)]
+struct FeePolicy {
+    bump_after_blocks: u64,
+    max_fee_rate: f64,
+}
+
+impl FeePolicy {
+    fn from_config(config: &DaServiceConfig) -> Self {
+        Self {
+            bump_after_blocks: config
+                .fee_bump_after_blocks
+                .unwrap_or(DEFAULT_FEE_BUMP_AFTER_BLOCKS),
+            max_fee_rate: config.max_fee_rate.unwrap_or(DEFAULT_MAX_FEE_RATE),
+        }
+    }
+}
+
+const DEFAULT_FINALITY_DEPTH: u64 = 4; // blocks
+const DEFAULT_POLLING_INTERVAL: u64 = 10; // seconds
+const DEFAULT_FEE_BUMP_AFTER_BLOCKS: u64 = 3;
+const DEFAULT_MAX_FEE_RATE: f64 = 200.0; // sat/vB
+
+// every bump raises the fee rate of the stuck inscriptions at least by this factor
+const FEE_BUMP_MULTIPLIER: f64 = 1.5;
+// default -minrelaytxfee of bitcoind, which every tx has to pay for itself
+const MIN_RELAY_FEE_RATE: f64 = 1.0; // sat/vB
+
+// how many earlier blocks are searched for the chunks of an aggregate inscription
+const CHUNK_LOOKBACK_BLOCKS: u64 = 144;
 
 impl BitcoinService {
     // Create a new instance of the DA service from the given configuration.
-    pub async fn new(config: DaServiceConfig, chain_params: RollupParams) -> Self {
+    // Unless configured otherwise, the outbox is kept in `storage_path`, the storage directory of the node.
+    // The native wallet is kept there as well.
+    pub async fn new(
+        config: DaServiceConfig,
+        chain_params: RollupParams,
+        known_da_public_keys: Vec<Vec<u8>>,
+        storage_path: &Path,
+    ) -> Self {
+        let fee_policy = FeePolicy::from_config(&config);
+        let finality_depth = config.finality_depth.unwrap_or(DEFAULT_FINALITY_DEPTH);
+        let polling_interval =
+            Duration::from_secs(config.polling_interval.unwrap_or(DEFAULT_POLLING_INTERVAL));
+
         let network =
             bitcoin::Network::from_str(&config.network).expect("Invalid bitcoin network name");
 
@@ -78,7 +162,22 @@ impl BitcoinService {
             .da_private_key
             .map(|pk| SecretKey::from_str(&pk).expect("Invalid private key"));
 
-        let (tx, mut rx) = unbounded_channel::<BlobWithNotifier<TxidWrapper>>();
+        let native_wallet = create_native_wallet(
+            config.native_wallet,
+            config.native_wallet_start_height,
+            private_key,
+            network,
+            Some(storage_path.join(NATIVE_WALLET_FILE_NAME)),
+        );
+
+        let outbox_path = config
+            .outbox_path
+            .map(PathBuf::from)
+            .unwrap_or_else(|| storage_path.join(OUTBOX_FILE_NAME));
+        let outbox =
+            InscriptionOutbox::load(outbox_path).expect("Failed to load inscription outbox");
+
+        let (tx, rx) = unbounded_channel::<BlobWithNotifier<TxidWrapper>>();
 
         let this = Self::with_client(
             client,
@@ -87,65 +186,43 @@ impl BitcoinService {
             private_key,
             chain_params.reveal_tx_id_prefix,
             tx,
+            fee_policy,
+            native_wallet,
+            finality_depth,
+            polling_interval,
+            known_da_public_keys,
         )
         .await;
 
-        let serv = this.clone();
+        let mut worker = InscriptionWorker {
+            service: this.clone(),
+            outbox,
+        };
+        let closed = this.inscription_queue_closed.clone();
 
         // This is a queue of inscribe requests
-        tokio::task::spawn_blocking(|| {
-            let this = serv;
+        let handle = tokio::task::spawn_blocking(move || {
             tokio::runtime::Handle::current().block_on(async move {
-                // TODO https://github.com/chainwayxyz/citrea/issues/537
-                // TODO find last tx by utxo chain
-                let mut prev_tx = None;
+                // Finish publishing the inscriptions that were interrupted by a restart
+                worker.service.resume_inscriptions(&mut worker.outbox).await;
 
                 trace!("BitcoinDA queue is initialized. Waiting for the first request...");
 
-                // We execute commit and reveal txs one by one to chain them
-                while let Some(request) = rx.recv().await {
-                    trace!("A new request is received");
-                    let prev = prev_tx.take();
-                    loop {
-                        // Build and send tx with retries:
-                        let blob = request.blob.clone();
-                        let fee_sat_per_vbyte = match this.get_fee_rate().await {
-                            Ok(rate) => rate,
-                            Err(e) => {
-                                error!(?e, "Failed to call get_fee_rate. Retrying...");
-                                tokio::time::sleep(Duration::from_secs(1)).await;
-                                continue;
-                            }
-                        };
-                        match this
-                            .send_transaction_with_fee_rate(prev.clone(), blob, fee_sat_per_vbyte)
-                            .await
-                        {
-                            Ok(tx) => {
-                                let tx_id = TxidWrapper(tx.id);
-                                info!(%tx.id, "Sent tx to BitcoinDA");
-                                prev_tx = Some(tx);
-                                let _ = request.notify.send(Ok(tx_id));
-                            }
-                            Err(e) => {
-                                error!(?e, "Failed to send transaction to DA layer");
-                                tokio::time::sleep(Duration::from_secs(1)).await;
-                                continue;
-                            }
-                        }
-                        break;
-                    }
-                }
-
-                error!("BitcoinDA queue stopped");
+                run_inscription_queue(&mut worker, rx, closed, polling_interval).await;
             });
         });
+        *this.inscription_worker.lock().await = Some(handle);
 
         this
     }
 
     #[cfg(test)]
     pub async fn new_without_client(config: DaServiceConfig, chain_params: RollupParams) -> Self {
+        let fee_policy = FeePolicy::from_config(&config);
+        let finality_depth = config.finality_depth.unwrap_or(DEFAULT_FINALITY_DEPTH);
+        let polling_interval =
+            Duration::from_secs(config.polling_interval.unwrap_or(DEFAULT_POLLING_INTERVAL));
+
         let network =
             bitcoin::Network::from_str(&config.network).expect("Invalid bitcoin network name");
 
@@ -155,6 +232,14 @@ impl BitcoinService {
             .da_private_key
             .map(|pk| SecretKey::from_str(&pk).expect("Invalid private key"));
 
+        let native_wallet = create_native_wallet(
+            config.native_wallet,
+            config.native_wallet_start_height,
+            private_key,
+            network,
+            None,
+        );
+
         let (tx, _rx) = unbounded_channel();
 
         Self {
@@ -164,9 +249,17 @@ impl BitcoinService {
             da_private_key: private_key,
             reveal_tx_id_prefix: chain_params.reveal_tx_id_prefix,
             inscribes_queue: tx,
+            fee_policy,
+            native_wallet,
+            finality_depth,
+            polling_interval,
+            known_da_public_keys: vec![],
+            inscription_queue_closed: CancellationToken::new(),
+            inscription_worker: Arc::new(Mutex::new(None)),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn with_client(
         client: BitcoinNode,
         rollup_name: String,
@@ -174,14 +267,22 @@ impl BitcoinService {
         da_private_key: Option<SecretKey>,
         reveal_tx_id_prefix: Vec<u8>,
         inscribes_queue: UnboundedSender<BlobWithNotifier<TxidWrapper>>,
+        fee_policy: FeePolicy,
+        native_wallet: Option<Arc<Mutex<NativeWallet>>>,
+        finality_depth: u64,
+        polling_interval: Duration,
+        known_da_public_keys: Vec<Vec<u8>>,
     ) -> Self {
-        let wallets = client
-            .list_wallets()
-            .await
-            .expect("Failed to list loaded wallets");
-
-        if wallets.is_empty() {
-            tracing::warn!("No loaded wallet found!");
+        // the native wallet does not need a wallet on the node
+        if native_wallet.is_none() {
+            let wallets = client
+                .list_wallets()
+                .await
+                .expect("Failed to list loaded wallets");
+
+            if wallets.is_empty() {
+                tracing::warn!("No loaded wallet found!");
+            }
         }
 
         Self {
@@ -191,12 +292,22 @@ impl BitcoinService {
             da_private_key,
             reveal_tx_id_prefix,
             inscribes_queue,
+            fee_policy,
+            native_wallet,
+            finality_depth,
+            polling_interval,
+            known_da_public_keys,
+            inscription_queue_closed: CancellationToken::new(),
+            inscription_worker: Arc::new(Mutex::new(None)),
         }
     }
 
     #[instrument(level = "trace", skip_all, ret)]
     async fn get_utxos(&self) -> Result<Vec<UTXO>, anyhow::Error> {
-        let utxos = self.client.get_utxos().await?;
+        let utxos = match &self.native_wallet {
+            Some(wallet) => wallet.lock().await.get_utxos(&self.client).await?,
+            None => self.client.get_utxos().await?,
+        };
         if utxos.is_empty() {
             return Err(anyhow::anyhow!("There are no UTXOs"));
         }
@@ -212,21 +323,113 @@ impl BitcoinService {
         Ok(utxos)
     }
 
-    #[instrument(level = "trace", fields(prev_tx), ret, err)]
-    pub async fn send_transaction_with_fee_rate(
+    /// Publishes the inscriptions left in the outbox by a previous run and marks the confirmed ones.
+    async fn resume_inscriptions(&self, outbox: &mut InscriptionOutbox) {
+        self.update_confirmed_inscriptions(outbox).await;
+
+        let pending = outbox.pending();
+        if !pending.is_empty() {
+            info!("Resuming {} inscriptions from the outbox", pending.len());
+        }
+
+        let mut publishing_failed = false;
+        for entry in pending {
+            // The txs may have been dropped from the mempool while the service was down
+            let broadcast_txs = match entry.state {
+                InscriptionState::RevealBroadcast => entry.raw_txs(),
+                InscriptionState::ChunksBroadcast => entry.chunk_raw_txs(),
+                _ => vec![],
+            };
+            for raw_tx in broadcast_txs {
+                if let Err(e) = self.broadcast_tx(raw_tx).await {
+                    warn!(?e, "Failed to rebroadcast inscription");
+                }
+            }
+            if entry.state == InscriptionState::RevealBroadcast {
+                continue;
+            }
+
+            // inscriptions are published in order, so the ones after a failed one wait as well
+            if !publishing_failed {
+                match self.publish_inscription(outbox, &entry.blob_hash).await {
+                    Ok(_) => continue,
+                    Err(e) => {
+                        error!(?e, "Failed to resume inscription");
+                        publishing_failed = true;
+                    }
+                }
+            }
+            self.requeue_blob(&entry);
+        }
+    }
+
+    /// Queues the blob of an inscription that could not be published on resume. The queue
+    /// continues publishing it if it is still in the outbox, e.g. while its chunks are not
+    /// confirmed, otherwise the txs were rejected for good and the blob is inscribed again.
+    fn requeue_blob(&self, entry: &OutboxEntry) {
+        let blob = match hex::decode(&entry.blob) {
+            Ok(blob) => blob,
+            Err(e) => {
+                error!(?e, "Failed to decode the blob of a resumed inscription");
+                return;
+            }
+        };
+        // Nobody waits for the result of a resumed inscription
+        let (notify, _) = oneshot_channel();
+        if self
+            .inscribes_queue
+            .send(BlobWithNotifier { blob, notify })
+            .is_err()
+        {
+            error!("BitcoinDA queue is closed, the blob of a resumed inscription is lost");
+        }
+    }
+
+    /// Inscribes the blob. If the blob is already in the outbox, publishing continues from where
+    /// it was left instead of building new transactions, so a blob is never published twice.
+    #[instrument(level = "trace", skip_all, ret, err)]
+    async fn inscribe(
+        &self,
+        outbox: &mut InscriptionOutbox,
+        blob: &[u8],
+    ) -> Result<TxWithId, anyhow::Error> {
+        let blob_hash = sha256d::Hash::hash(blob).to_byte_array();
+
+        if outbox.get(&blob_hash).is_some() {
+            info!("Blob is already in the outbox, continuing to publish it");
+        } else {
+            self.update_confirmed_inscriptions(outbox).await;
+
+            let fee_sat_per_vbyte = self.get_fee_rate().await?.min(self.fee_policy.max_fee_rate);
+            // every commit tx spends the output of the last inscription to chain them
+            let prev_tx = outbox.chain_tail()?;
+            let entry = self
+                .build_inscription(prev_tx, blob, fee_sat_per_vbyte)
+                .await?;
+            outbox.insert(entry)?;
+        }
+
+        self.publish_inscription(outbox, &blob_hash).await
+    }
+
+    /// Builds and signs the commit and reveal txs of the blob. If the compressed blob does not fit
+    /// into a single reveal tx, it is inscribed in chunks followed by an aggregate inscription.
+    #[instrument(level = "trace", fields(prev_tx), skip(blob), ret, err)]
+    async fn build_inscription(
         &self,
         prev_tx: Option<TxWithId>,
-        blob: Vec<u8>,
+        blob: &[u8],
         fee_sat_per_vbyte: f64,
-    ) -> Result<TxWithId, anyhow::Error> {
-        let client = self.client.clone();
+    ) -> Result<OutboxEntry, anyhow::Error> {
         let network = self.network;
 
         let rollup_name = self.rollup_name.clone();
         let da_private_key = self.da_private_key.expect("No private key set");
 
+        let blob_hash = sha256d::Hash::hash(blob).to_byte_array();
+
         // Compress the blob
-        let blob = compress_blob(&blob);
+        let compressed_blob = compress_blob(blob);
 
         // get all available utxos
         let utxos: Vec<UTXO> = self.get_utxos().await?;
@@ -238,13 +441,48 @@ impl BitcoinService {
             .expect("Invalid network for address");
 
         // sign the blob for authentication of the sequencer
-        let (signature, public_key) =
-            sign_blob_with_private_key(&blob, &da_private_key).expect("Sequencer sign the blob");
+        let (signature, public_key) = sign_blob_with_private_key(&compressed_blob, &da_private_key)
+            .expect("Sequencer sign the blob");
+
+        if compressed_blob.len() > MAX_INSCRIPTION_BODY_SIZE {
+            let chunks = compressed_blob
+                .chunks(MAX_INSCRIPTION_BODY_SIZE)
+                .map(|chunk| {
+                    let (chunk_signature, _) = sign_blob_with_private_key(chunk, &da_private_key)
+                        .expect("Sequencer sign the chunk");
+                    (chunk.to_vec(), chunk_signature)
+                })
+                .collect::<Vec<_>>();
+            info!("Blob is inscribed in {} chunks", chunks.len());
+
+            let txs = create_chunked_inscription_transactions(
+                &rollup_name,
+                chunks,
+                |chunk_wtxids| {
+                    sign_blob_with_private_key(chunk_wtxids, &da_private_key)
+                        .expect("Sequencer sign the aggregate")
+                        .0
+                },
+                public_key,
+                prev_tx,
+                utxos,
+                address,
+                REVEAL_OUTPUT_AMOUNT,
+                fee_sat_per_vbyte,
+                fee_sat_per_vbyte,
+                network,
+                self.reveal_tx_id_prefix.as_slice(),
+            )?;
+
+            return self
+                .sign_chunked_inscription(blob_hash, blob, txs, fee_sat_per_vbyte)
+                .await;
+        }
 
         // create inscribe transactions
         let (unsigned_commit_tx, reveal_tx) = create_inscription_transactions(
             &rollup_name,
-            blob,
+            compressed_blob,
             signature,
             public_key,
             prev_tx,
@@ -258,33 +496,418 @@ impl BitcoinService {
         )?;
 
         // sign inscribe transactions
-        let serialized_unsigned_commit_tx = &encode::serialize(&unsigned_commit_tx);
-        let signed_raw_commit_tx = client
-            .sign_raw_transaction_with_wallet(serialized_unsigned_commit_tx.encode_hex())
-            .await?;
+        let signed_raw_commit_tx = self.sign_wallet_tx(unsigned_commit_tx, &[]).await?;
+
+        Ok(OutboxEntry::new(
+            blob_hash,
+            blob,
+            signed_raw_commit_tx,
+            &reveal_tx.tx,
+            fee_sat_per_vbyte,
+        ))
+    }
+
+    /// Signs the commit txs of a chunked inscription. The aggregate commit tx can spend the change
+    /// of the chunk commit txs, which the node does not know about until they are broadcast.
+    async fn sign_chunked_inscription(
+        &self,
+        blob_hash: [u8; 32],
+        blob: &[u8],
+        txs: Vec<(Transaction, TxWithId)>,
+        fee_sat_per_vbyte: f64,
+    ) -> Result<OutboxEntry, anyhow::Error> {
+        let mut unbroadcast_txs: Vec<Transaction> = Vec::with_capacity(txs.len() * 2);
+        let mut signed_txs = Vec::with_capacity(txs.len());
+
+        for (unsigned_commit_tx, reveal_tx) in txs {
+            let signed_raw_commit_tx = self
+                .sign_wallet_tx(unsigned_commit_tx.clone(), &unbroadcast_txs)
+                .await?;
+
+            unbroadcast_txs.push(unsigned_commit_tx);
+            unbroadcast_txs.push(reveal_tx.tx.clone());
+            signed_txs.push((signed_raw_commit_tx, reveal_tx));
+        }
 
-        // send inscribe transactions
-        client.send_raw_transaction(signed_raw_commit_tx).await?;
+        // the aggregate inscription comes last
+        let (signed_raw_commit_tx, reveal_tx) = signed_txs
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Chunked inscription has no txs"))?;
+        let chunks = signed_txs
+            .into_iter()
+            .map(|(signed_commit_tx, reveal_tx)| ChunkTxs::new(signed_commit_tx, &reveal_tx.tx))
+            .collect();
 
-        // serialize reveal tx
-        let serialized_reveal_tx = &encode::serialize(&reveal_tx.tx);
+        Ok(OutboxEntry::new(
+            blob_hash,
+            blob,
+            signed_raw_commit_tx,
+            &reveal_tx.tx,
+            fee_sat_per_vbyte,
+        )
+        .with_chunks(chunks))
+    }
 
-        // write reveal tx to file, it can be used to continue revealing blob if something goes wrong
-        write_reveal_tx(
-            serialized_reveal_tx,
-            unsigned_commit_tx.txid().to_raw_hash().to_string(),
-        );
+    /// Signs a tx spending outputs of the wallet, which may be outputs of `unbroadcast_txs`
+    async fn sign_wallet_tx(
+        &self,
+        mut unsigned_tx: Transaction,
+        unbroadcast_txs: &[Transaction],
+    ) -> Result<String, anyhow::Error> {
+        let Some(wallet) = &self.native_wallet else {
+            let prev_outs = unbroadcast_prev_outs(&unsigned_tx, unbroadcast_txs);
+            return self
+                .client
+                .sign_raw_transaction_with_wallet(
+                    encode::serialize(&unsigned_tx).encode_hex(),
+                    prev_outs,
+                )
+                .await;
+        };
+
+        let mut wallet = wallet.lock().await;
+
+        let mut prevouts = Vec::with_capacity(unsigned_tx.input.len());
+        for input in unsigned_tx.input.iter() {
+            let outpoint = input.previous_output;
+            let prevout = match find_output(unbroadcast_txs, &outpoint)
+                .or_else(|| wallet.output(&outpoint).cloned())
+            {
+                Some(prevout) => prevout,
+                // e.g. the output of the last reveal tx after a restart
+                None => self
+                    .client
+                    .get_tx_out(outpoint.txid.to_string(), outpoint.vout)
+                    .await?
+                    .map(|(prevout, _)| prevout)
+                    .ok_or_else(|| anyhow::anyhow!("Spent output {} is not found", outpoint))?,
+            };
+            prevouts.push(prevout);
+        }
 
-        // send reveal tx
-        let reveal_tx_hash = client
-            .send_raw_transaction(serialized_reveal_tx.encode_hex())
-            .await?;
+        wallet.sign(&mut unsigned_tx, &prevouts)?;
 
-        info!("Blob inscribe tx sent. Hash: {}", reveal_tx_hash);
+        Ok(encode::serialize(&unsigned_tx).encode_hex())
+    }
+
+    /// Number of confirmations of one of our txs
+    async fn get_tx_confirmations(&self, txid: Txid) -> Result<u64, anyhow::Error> {
+        match &self.native_wallet {
+            Some(wallet) => {
+                let mut wallet = wallet.lock().await;
+                wallet.sync(&self.client).await?;
+                Ok(wallet.tx_confirmations(&txid))
+            }
+            None => self.client.get_tx_confirmations(txid.to_string()).await,
+        }
+    }
+
+    /// Broadcasts the txs of the inscription that were not broadcast yet.
+    /// If the node rejects them, the inscription is dropped from the outbox so it gets rebuilt.
+    /// The chunks of an inscription are broadcast first, and the rest once they are confirmed.
+    async fn publish_inscription(
+        &self,
+        outbox: &mut InscriptionOutbox,
+        blob_hash: &[u8; 32],
+    ) -> Result<TxWithId, anyhow::Error> {
+        let entry = outbox
+            .get(blob_hash)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Inscription is not in the outbox"))?;
+        let reveal_tx = entry.reveal()?;
+
+        if entry.state == InscriptionState::Built && !entry.chunks.is_empty() {
+            if let Err(e) = self.broadcast_chunks(&entry).await {
+                return Err(drop_if_rejected(outbox, blob_hash, e));
+            }
+            outbox.set_state(blob_hash, InscriptionState::ChunksBroadcast)?;
+            // the chunks are bumped if they are not confirmed in time
+            self.record_broadcast_height(outbox, blob_hash).await;
+        }
+
+        if matches!(
+            entry.state,
+            InscriptionState::Built | InscriptionState::ChunksBroadcast
+        ) {
+            // the aggregate must not be mined before its chunks, or its blob could not be
+            // reassembled from the blocks up to it
+            let unconfirmed_chunks = self.count_unconfirmed_chunks(&entry).await?;
+            if unconfirmed_chunks > 0 {
+                return Err(anyhow::anyhow!(
+                    "Waiting for {} chunks of the inscription to be confirmed",
+                    unconfirmed_chunks
+                ));
+            }
+
+            if let Err(e) = self.broadcast_tx(entry.commit_tx.clone()).await {
+                return Err(drop_if_rejected(outbox, blob_hash, e));
+            }
+            outbox.set_state(blob_hash, InscriptionState::CommitBroadcast)?;
+        }
+
+        if matches!(
+            entry.state,
+            InscriptionState::Built
+                | InscriptionState::ChunksBroadcast
+                | InscriptionState::CommitBroadcast
+        ) {
+            if let Err(e) = self.broadcast_tx(entry.reveal_tx).await {
+                return Err(drop_if_rejected(outbox, blob_hash, e));
+            }
+            outbox.set_state(blob_hash, InscriptionState::RevealBroadcast)?;
+            self.record_broadcast_height(outbox, blob_hash).await;
+
+            info!("Blob inscribe tx sent. Hash: {}", reveal_tx.id);
+        }
 
         Ok(reveal_tx)
     }
 
+    /// Broadcasts the commit and reveal txs of the chunks of the inscription
+    async fn broadcast_chunks(&self, entry: &OutboxEntry) -> Result<(), anyhow::Error> {
+        for chunk in entry.chunks.iter() {
+            self.broadcast_tx(chunk.commit_tx.clone()).await?;
+            self.broadcast_tx(chunk.reveal_tx.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Number of chunks of the inscription whose reveal tx is not in a block yet
+    async fn count_unconfirmed_chunks(&self, entry: &OutboxEntry) -> Result<usize, anyhow::Error> {
+        let mut unconfirmed = 0;
+        for chunk in entry.chunks.iter() {
+            if self.get_tx_confirmations(chunk.reveal()?.id).await? == 0 {
+                unconfirmed += 1;
+            }
+        }
+        Ok(unconfirmed)
+    }
+
+    async fn record_broadcast_height(&self, outbox: &mut InscriptionOutbox, blob_hash: &[u8; 32]) {
+        match self.client.get_block_count().await {
+            Ok(height) => {
+                if let Err(e) = outbox.set_broadcast_height(blob_hash, height) {
+                    error!(?e, "Failed to update inscription outbox");
+                }
+            }
+            Err(e) => warn!(?e, "Failed to get block count"),
+        }
+    }
+
+    /// Bumps the fee of the inscriptions if one of them did not confirm within `fee_bump_after_blocks`.
+    /// Inscriptions are chained, so a CPFP tx spending the output of the latest one pays for all
+    /// unconfirmed ones. It does not replace any tx, and becomes the tx the next commit tx spends.
+    async fn bump_stuck_inscriptions(&self, outbox: &mut InscriptionOutbox) {
+        self.update_confirmed_inscriptions(outbox).await;
+
+        let height = match self.client.get_block_count().await {
+            Ok(height) => height,
+            Err(e) => {
+                warn!(?e, "Failed to get block count");
+                return;
+            }
+        };
+
+        let pending = outbox.pending();
+        for entry in pending.iter() {
+            // inscriptions broadcast while the node was unreachable start being tracked now
+            if matches!(
+                entry.state,
+                InscriptionState::ChunksBroadcast | InscriptionState::RevealBroadcast
+            ) && entry.broadcast_height.is_none()
+            {
+                self.record_broadcast_height(outbox, &entry.blob_hash).await;
+            }
+        }
+
+        let Some(latest) = inscription_to_bump(&pending, height, self.fee_policy.bump_after_blocks)
+        else {
+            return;
+        };
+
+        let result = if latest.state == InscriptionState::ChunksBroadcast {
+            self.bump_chunks(outbox, latest, height).await
+        } else {
+            self.bump_inscription(outbox, latest, height).await
+        };
+        if let Err(e) = result {
+            error!(?e, "Failed to bump the fee of stuck inscriptions");
+        }
+    }
+
+    /// Broadcasts a CPFP tx spending the output of the latest inscription, which brings the fee
+    /// rate of the unconfirmed inscriptions to a higher one.
+    async fn bump_inscription(
+        &self,
+        outbox: &mut InscriptionOutbox,
+        latest: &OutboxEntry,
+        height: u64,
+    ) -> Result<(), anyhow::Error> {
+        let parent = latest.chain_tail()?;
+        if let Some(signed_raw_cpfp_tx) = self.broadcast_cpfp_tx(&parent).await? {
+            outbox.add_cpfp_tx(&latest.blob_hash, signed_raw_cpfp_tx, height)?;
+        }
+        Ok(())
+    }
+
+    /// Broadcasts a CPFP tx for every unconfirmed chunk of the inscription. The chunks do not
+    /// depend on each other, so each of them is bumped on its own.
+    async fn bump_chunks(
+        &self,
+        outbox: &mut InscriptionOutbox,
+        latest: &OutboxEntry,
+        height: u64,
+    ) -> Result<(), anyhow::Error> {
+        for (index, chunk) in latest.chunks.iter().enumerate() {
+            if self.get_tx_confirmations(chunk.reveal()?.id).await? > 0 {
+                continue;
+            }
+            let parent = chunk.chain_tail()?;
+            if let Some(signed_raw_cpfp_tx) = self.broadcast_cpfp_tx(&parent).await? {
+                outbox.add_chunk_cpfp_tx(&latest.blob_hash, index, signed_raw_cpfp_tx, height)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Broadcasts a CPFP tx spending the first output of `parent`, which brings the fee rate of it
+    /// and its unconfirmed ancestors to a higher one. Returns the signed CPFP tx, or `None` if
+    /// `parent` is not in the mempool or its fee rate is already at the cap.
+    async fn broadcast_cpfp_tx(&self, parent: &TxWithId) -> Result<Option<String>, anyhow::Error> {
+        let Some(ancestors) = self
+            .client
+            .get_mempool_ancestors(parent.id.to_string())
+            .await?
+        else {
+            // confirmed in the meantime, or dropped and rebroadcast on the next restart
+            warn!(%parent.id, "Stuck inscription tx is not in the mempool");
+            return Ok(None);
+        };
+
+        let current_fee_rate = ancestors.fees as f64 / ancestors.vsize as f64;
+        let fee_rate = (current_fee_rate * FEE_BUMP_MULTIPLIER)
+            .max(self.get_fee_rate().await?)
+            .min(self.fee_policy.max_fee_rate);
+        if fee_rate <= current_fee_rate {
+            warn!(
+                max_fee_rate = self.fee_policy.max_fee_rate,
+                "Inscriptions are stuck but their fee rate is already at the cap"
+            );
+            return Ok(None);
+        }
+
+        // Unconfirmed utxos would add more ancestors for the CPFP tx to pay for
+        let utxos: Vec<UTXO> = self
+            .get_utxos()
+            .await?
+            .into_iter()
+            .filter(|utxo| utxo.confirmations > 0)
+            .collect();
+        let change_address =
+            Address::from_script(&parent.tx.output[0].script_pubkey, self.network)?;
+
+        let unsigned_cpfp_tx = build_cpfp_transaction(parent, utxos, &change_address, |vsize| {
+            cpfp_fee(ancestors, vsize, fee_rate)
+        })?;
+        let signed_raw_cpfp_tx = self.sign_wallet_tx(unsigned_cpfp_tx, &[]).await?;
+
+        self.broadcast_tx(signed_raw_cpfp_tx.clone()).await?;
+
+        info!(
+            current_fee_rate,
+            fee_rate, "Bumped the fee of stuck inscriptions"
+        );
+        Ok(Some(signed_raw_cpfp_tx))
+    }
+
+    /// Marks the inscriptions whose reveal tx is in a block as confirmed and drops them from the outbox.
+    async fn update_confirmed_inscriptions(&self, outbox: &mut InscriptionOutbox) {
+        for entry in outbox.pending() {
+            if entry.state != InscriptionState::RevealBroadcast {
+                continue;
+            }
+            let Ok(reveal_tx) = entry.reveal() else {
+                continue;
+            };
+            match self.get_tx_confirmations(reveal_tx.id).await {
+                Ok(confirmations) if confirmations > 0 => {
+                    if let Err(e) = outbox.set_state(&entry.blob_hash, InscriptionState::Confirmed)
+                    {
+                        error!(?e, "Failed to update inscription outbox");
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(?e, %reveal_tx.id, "Failed to get reveal tx confirmations");
+                }
+            }
+        }
+
+        if let Err(e) = outbox.prune_confirmed() {
+            error!(?e, "Failed to prune inscription outbox");
+        }
+    }
+
+    /// Sends a signed tx, a tx that is already in the chain is not an error.
+    async fn broadcast_tx(&self, raw_tx: String) -> Result<(), anyhow::Error> {
+        match self.client.send_raw_transaction(raw_tx).await {
+            Ok(_) => Ok(()),
+            // RPC_VERIFY_ALREADY_IN_CHAIN: it was broadcast before a restart and is already mined
+            Err(e) if matches!(e.downcast_ref::<RPCError>(), Some(e) if e.code == -27) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetches the chunks that the aggregate inscriptions of the block reference from earlier
+    /// blocks, up to `CHUNK_LOOKBACK_BLOCKS` deep. Only the aggregates signed by a known DA key
+    /// are looked up, as anyone can publish one. A chunk that is not found is not an error,
+    /// the aggregate is then not a blob.
+    async fn fetch_earlier_chunks(&self, block: &mut BitcoinBlock) -> Result<(), anyhow::Error> {
+        let prefix = self.reveal_tx_id_prefix.as_slice();
+        let is_relevant =
+            |tx: &&Transaction| tx.txid().to_byte_array().as_slice().starts_with(prefix);
+
+        let mut missing: HashSet<[u8; 32]> = block
+            .txdata
+            .iter()
+            .filter(is_relevant)
+            .filter_map(|tx| parse_transaction(tx, &self.rollup_name).ok())
+            .filter(|inscription| self.known_da_public_keys.contains(&inscription.public_key))
+            .filter_map(|inscription| {
+                // only signed aggregates are reassembled
+                let chunk_wtxids = inscription.chunk_wtxids()?;
+                inscription.get_sig_verified_hash().map(|_| chunk_wtxids)
+            })
+            .flatten()
+            .collect();
+        for tx in block.txdata.iter() {
+            missing.remove(&tx.wtxid().to_byte_array());
+        }
+
+        let mut prev_hash = block.header.prev_hash();
+        let mut depth = 0;
+        while !missing.is_empty() && depth < CHUNK_LOOKBACK_BLOCKS {
+            let earlier_block = self.client.get_block(prev_hash.to_string()).await?;
+            for tx in earlier_block.txdata.iter().filter(is_relevant) {
+                if missing.remove(&tx.wtxid().to_byte_array()) {
+                    block.earlier_chunks.push(tx.clone());
+                }
+            }
+            prev_hash = earlier_block.header.prev_hash();
+            depth += 1;
+        }
+
+        for wtxid in missing.iter() {
+            warn!(
+                "Chunk {} of an aggregate inscription in block {} is not found in the last {} blocks, skipping the aggregate",
+                hex::encode(wtxid),
+                block.header.block_hash(),
+                CHUNK_LOOKBACK_BLOCKS
+            );
+        }
+        Ok(())
+    }
+
     #[instrument(level = "trace", skip_all, ret)]
     pub async fn get_fee_rate(&self) -> Result<f64, anyhow::Error> {
         if self.network == bitcoin::Network::Regtest {
@@ -296,6 +919,210 @@ impl BitcoinService {
     }
 }
 
+/// Sends the blobs of the inscription queue to the DA layer
+#[async_trait]
+trait InscriptionQueue {
+    /// Inscribes the blob, returns the id of the reveal tx
+    async fn inscribe(&mut self, blob: &[u8]) -> Result<Txid, anyhow::Error>;
+
+    /// Called periodically while the queue is open, e.g. to bump the fee of stuck inscriptions
+    async fn on_tick(&mut self);
+}
+
+/// The inscription queue of a [`BitcoinService`], together with the inscriptions being published
+struct InscriptionWorker {
+    service: BitcoinService,
+    outbox: InscriptionOutbox,
+}
+
+#[async_trait]
+impl InscriptionQueue for InscriptionWorker {
+    async fn inscribe(&mut self, blob: &[u8]) -> Result<Txid, anyhow::Error> {
+        let tx = self.service.inscribe(&mut self.outbox, blob).await?;
+        Ok(tx.id)
+    }
+
+    async fn on_tick(&mut self) {
+        self.service.bump_stuck_inscriptions(&mut self.outbox).await;
+    }
+}
+
+/// Sends the requests of `rx` one by one, retrying each until it is sent, so that the commit tx of
+/// every inscription spends the output of the previous reveal tx.
+/// Once `closed` is cancelled no new requests are accepted, and the function returns after
+/// sending the requests that were already queued.
+async fn run_inscription_queue<Q: InscriptionQueue>(
+    queue: &mut Q,
+    mut rx: UnboundedReceiver<BlobWithNotifier<TxidWrapper>>,
+    closed: CancellationToken,
+    tick_interval: Duration,
+) {
+    let mut closing = false;
+    // Stuck inscriptions are checked for on every tick
+    let mut tick = tokio::time::interval(tick_interval);
+
+    loop {
+        let request = tokio::select! {
+            request = rx.recv() => request,
+            _ = tick.tick(), if !closing => {
+                queue.on_tick().await;
+                continue;
+            }
+            _ = closed.cancelled(), if !closing => {
+                info!("BitcoinDA queue is closing, sending {} queued requests", rx.len());
+                closing = true;
+                rx.close();
+                continue;
+            }
+        };
+        let Some(request) = request else {
+            break;
+        };
+        trace!("A new request is received");
+        loop {
+            // Build and send tx with retries:
+            match queue.inscribe(&request.blob).await {
+                Ok(tx_id) => {
+                    info!(%tx_id, "Sent tx to BitcoinDA");
+                    let _ = request.notify.send(Ok(TxidWrapper(tx_id)));
+                }
+                Err(e) => {
+                    error!(?e, "Failed to send transaction to DA layer");
+                    // stuck inscriptions are bumped while retrying, e.g. chunks the request waits for
+                    tokio::select! {
+                        _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                        _ = tick.tick() => queue.on_tick().await,
+                    }
+                    continue;
+                }
+            }
+            break;
+        }
+    }
+
+    if closing {
+        info!("BitcoinDA queue stopped");
+    } else {
+        error!("BitcoinDA queue stopped");
+    }
+}
+
+fn create_native_wallet(
+    enabled: Option<bool>,
+    start_height: Option<u64>,
+    private_key: Option<SecretKey>,
+    network: bitcoin::Network,
+    path: Option<PathBuf>,
+) -> Option<Arc<Mutex<NativeWallet>>> {
+    if enabled != Some(true) {
+        return None;
+    }
+
+    let private_key = private_key.expect("The native wallet needs da_private_key");
+    let start_height = start_height.expect("The native wallet needs native_wallet_start_height");
+
+    let wallet = NativeWallet::new(&private_key, network, start_height, path)
+        .expect("Failed to load the native wallet");
+    Some(Arc::new(Mutex::new(wallet)))
+}
+
+fn find_output(txs: &[Transaction], outpoint: &OutPoint) -> Option<TxOut> {
+    txs.iter()
+        .find(|tx| tx.txid() == outpoint.txid)?
+        .output
+        .get(outpoint.vout as usize)
+        .cloned()
+}
+
+/// Outputs spent by `tx` that belong to txs which are built but not broadcast yet
+fn unbroadcast_prev_outs(tx: &Transaction, unbroadcast_txs: &[Transaction]) -> Vec<PrevOut> {
+    tx.input
+        .iter()
+        .filter_map(|input| {
+            let outpoint = input.previous_output;
+            let output = find_output(unbroadcast_txs, &outpoint)?;
+            Some(PrevOut {
+                txid: outpoint.txid.to_string(),
+                vout: outpoint.vout,
+                script_pub_key: output.script_pubkey.to_hex_string(),
+                amount: output.value.to_btc(),
+            })
+        })
+        .collect()
+}
+
+/// The latest inscription if the unconfirmed inscriptions have to be bumped: one of them has not
+/// confirmed within `bump_after_blocks` of being broadcast, and neither has the last bump.
+/// Only the latest inscription can be bumped, once all of its txs are broadcast, or its chunks
+/// while its commit tx waits for them.
+fn inscription_to_bump(
+    pending: &[OutboxEntry],
+    height: u64,
+    bump_after_blocks: u64,
+) -> Option<&OutboxEntry> {
+    let latest = pending.last()?;
+
+    let is_due = |since: u64| height >= since + bump_after_blocks;
+    let is_stuck = match latest.state {
+        // every unconfirmed inscription is bumped through the latest one
+        InscriptionState::RevealBroadcast => pending
+            .iter()
+            .any(|entry| entry.broadcast_height.is_some_and(is_due)),
+        // the chunks do not depend on the earlier inscriptions
+        InscriptionState::ChunksBroadcast => latest.broadcast_height.is_some_and(is_due),
+        _ => return None,
+    };
+    let last_bump_is_due = latest.bumped_height.map_or(true, is_due);
+
+    (is_stuck && last_bump_is_due).then_some(latest)
+}
+
+/// Fee of a CPFP tx of `vsize` vB, so that it and its unconfirmed ancestors pay `fee_rate` together.
+/// It pays at least the minimum relay fee for its own size.
+fn cpfp_fee(ancestors: MempoolAncestors, vsize: usize, fee_rate: f64) -> u64 {
+    let package_fee = ((ancestors.vsize + vsize as u64) as f64 * fee_rate).ceil() as u64;
+    let min_fee = (vsize as f64 * MIN_RELAY_FEE_RATE).ceil() as u64;
+    package_fee.saturating_sub(ancestors.fees).max(min_fee)
+}
+
+/// Drops the inscription from the outbox if the node rejected its txs for good, so it gets rebuilt.
+/// Other errors, like connection errors or a too low fee, are retried with the same txs, as
+/// building new ones could publish the blob twice.
+fn drop_if_rejected(
+    outbox: &mut InscriptionOutbox,
+    blob_hash: &[u8; 32],
+    error: anyhow::Error,
+) -> anyhow::Error {
+    if is_definitely_rejected(&error) {
+        warn!(
+            ?error,
+            "Inscription is rejected by the node, it will be rebuilt"
+        );
+        if let Err(e) = outbox.remove(blob_hash) {
+            error!(?e, "Failed to update inscription outbox");
+        }
+    }
+    error
+}
+
+/// Whether the node rejected a tx because its inputs are missing or spent by a conflicting tx.
+/// Such a tx can never be accepted, unlike one rejected by the mempool policy.
+fn is_definitely_rejected(error: &anyhow::Error) -> bool {
+    let Some(error) = error.downcast_ref::<RPCError>() else {
+        return false;
+    };
+    match error.code {
+        // RPC_VERIFY_ERROR: inputs are missing or already spent
+        -25 => true,
+        // RPC_VERIFY_REJECTED: only conflicts are definite, e.g. a too low fee is not
+        -26 => {
+            error.message.contains("txn-mempool-conflict")
+                || error.message.contains("bad-txns-inputs-missingorspent")
+        }
+        _ => false,
+    }
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
 pub struct TxidWrapper(Txid);
 impl From<TxidWrapper> for [u8; 32] {
@@ -333,7 +1160,7 @@ impl DaService for BitcoinService {
                         Some(error) => {
                             if error.code == -8 {
                                 info!("Block not found, waiting");
-                                tokio::time::sleep(Duration::from_secs(POLLING_INTERVAL)).await;
+                                tokio::time::sleep(self.polling_interval).await;
                                 continue;
                             } else {
                                 // other error, return message
@@ -349,7 +1176,8 @@ impl DaService for BitcoinService {
 
             break;
         }
-        let block = self.client.get_block(block_hash).await?;
+        let mut block = self.client.get_block(block_hash).await?;
+        self.fetch_earlier_chunks(&mut block).await?;
 
         Ok(block)
     }
@@ -361,18 +1189,51 @@ impl DaService for BitcoinService {
     ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
         let block_count = self.client.get_block_count().await?;
 
-        let finalized_blockhash = self
-            .client
-            .get_block_hash(block_count - FINALITY_DEPTH)
-            .await?;
+        let finalized_height = block_count
+            .checked_sub(self.finality_depth)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No finalized block yet, block count is {} and finality depth is {}",
+                    block_count,
+                    self.finality_depth
+                )
+            })?;
+
+        let finalized_blockhash = self.client.get_block_hash(finalized_height).await?;
 
         let finalized_block_header = self.client.get_block_header(finalized_blockhash).await?;
 
         Ok(finalized_block_header)
     }
 
+    // Poll the node for newly finalized blocks in the background, until the stream is dropped.
+    // Reorgs of reported blocks are sent as events, errors of a poll are sent as they are.
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
-        unimplemented!()
+        let (tx, rx) = unbounded_channel();
+        let client = self.client.clone();
+        let mut tracker = HeaderTracker::new(self.finality_depth);
+        let mut interval = tokio::time::interval(self.polling_interval);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => break,
+                }
+
+                let events = match tracker.poll(&client).await {
+                    Ok(events) => events.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                for event in events {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(BitcoinHeaderStream::new(rx))
     }
 
     // Fetch the head block of DA.
@@ -400,34 +1261,26 @@ impl DaService for BitcoinService {
             block.header.block_hash()
         );
 
-        // iterate over all transactions in the block
-        for tx in block.txdata.iter() {
-            if !tx
-                .txid()
+        // only the txs with the prefix can be inscriptions
+        let relevant_txs = block.txdata.iter().filter(|tx| {
+            tx.txid()
                 .to_byte_array()
                 .as_slice()
                 .starts_with(self.reveal_tx_id_prefix.as_slice())
-            {
-                continue;
-            }
-
-            // check if the inscription in script is relevant to the rollup
-            let parsed_inscription = parse_transaction(tx, &self.rollup_name);
+        });
 
-            if let Ok(inscription) = parsed_inscription {
-                if inscription.get_sig_verified_hash().is_some() {
-                    // Decompress the blob
-                    let decompressed_blob = decompress_blob(&inscription.body);
+        // check if the inscriptions are relevant to the rollup,
+        // the ones inscribed in chunks are reassembled if all their chunks are in the block
+        // or in its earlier chunks, otherwise they are not blobs
+        let complete_inscriptions =
+            parse_complete_inscriptions(relevant_txs, &block.earlier_chunks, &self.rollup_name);
+        for (inscription, hash) in complete_inscriptions {
+            // Decompress the blob
+            let decompressed_blob = decompress_blob(&inscription.body);
 
-                    let relevant_tx = BlobWithSender::new(
-                        decompressed_blob,
-                        inscription.public_key,
-                        sha256d::Hash::hash(&inscription.body).to_byte_array(),
-                    );
+            let relevant_tx = BlobWithSender::new(decompressed_blob, inscription.public_key, hash);
 
-                    txs.push(relevant_tx);
-                }
-            }
+            txs.push(relevant_tx);
         }
         txs
     }
@@ -471,7 +1324,12 @@ impl DaService for BitcoinService {
         });
 
         (
-            InclusionMultiProof::new(txids, wtxids, block.txdata[0].clone()),
+            InclusionMultiProof::new(
+                txids,
+                wtxids,
+                block.txdata[0].clone(),
+                block.earlier_chunks.clone(),
+            ),
             completeness_proof,
         )
     }
@@ -516,6 +1374,17 @@ impl DaService for BitcoinService {
         self.inscribes_queue.clone()
     }
 
+    #[instrument(level = "trace", skip_all)]
+    async fn close_send_transaction_queue(&self) {
+        self.inscription_queue_closed.cancel();
+        let Some(worker) = self.inscription_worker.lock().await.take() else {
+            return;
+        };
+        if let Err(e) = worker.await {
+            error!(?e, "BitcoinDA queue failed");
+        }
+    }
+
     async fn send_aggregated_zk_proof(
         &self,
         _aggregated_proof_data: &[u8],
@@ -542,7 +1411,8 @@ impl DaService for BitcoinService {
 
         let hash = BlockHash::from_byte_array(hash);
 
-        let block = self.client.get_block(hash.to_string()).await?;
+        let mut block = self.client.get_block(hash.to_string()).await?;
+        self.fetch_earlier_chunks(&mut block).await?;
         Ok(block)
     }
 }
@@ -550,19 +1420,29 @@ impl DaService for BitcoinService {
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
+    use core::time::Duration;
 
     // use futures::{Stream, StreamExt};
     use bitcoin::block::{Header, Version};
     use bitcoin::hash_types::{TxMerkleNode, WitnessMerkleNode};
+    use bitcoin::hashes::{sha256d, Hash};
     use bitcoin::secp256k1::Keypair;
     use bitcoin::string::FromHexStr;
-    use bitcoin::{BlockHash, CompactTarget, Transaction};
+    use bitcoin::{BlockHash, CompactTarget, Transaction, Txid};
     use sov_rollup_interface::da::DaVerifier;
-    use sov_rollup_interface::services::da::{DaService, SlotData};
-
-    use super::BitcoinService;
+    use sov_rollup_interface::services::da::{BlobWithNotifier, DaService, SlotData};
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio::sync::oneshot::channel as oneshot_channel;
+    use tokio_util::sync::CancellationToken;
+
+    use super::{
+        cpfp_fee, inscription_to_bump, is_definitely_rejected, run_inscription_queue,
+        BitcoinService, InscriptionQueue,
+    };
     use crate::helpers::parsers::parse_hex_transaction;
     use crate::helpers::test_utils::{get_mock_data, get_mock_txs};
+    use crate::outbox::{InscriptionState, OutboxEntry};
+    use crate::rpc::{MempoolAncestors, RPCError};
     use crate::service::DaServiceConfig;
     use crate::spec::block::BitcoinBlock;
     use crate::spec::header::HeaderWrapper;
@@ -579,6 +1459,13 @@ mod tests {
                 "E9873D79C6D87DC0FB6A5778633389F4453213303DA61F20BD67FC233AA33262".to_string(), // Test key, safe to publish
             ),
             fee_rates_to_avg: Some(2), // small to speed up tests
+            outbox_path: None,
+            fee_bump_after_blocks: None,
+            max_fee_rate: None,
+            native_wallet: None,
+            native_wallet_start_height: None,
+            finality_depth: None,
+            polling_interval: None,
         };
 
         BitcoinService::new_without_client(
@@ -643,6 +1530,7 @@ mod tests {
         let block = BitcoinBlock {
             header,
             txdata: block_txs,
+            earlier_chunks: vec![],
         };
 
         let txs = da_service.extract_relevant_blobs(&block);
@@ -664,6 +1552,7 @@ mod tests {
         let block = BitcoinBlock {
             header,
             txdata: block_txs,
+            earlier_chunks: vec![],
         };
 
         let (txs, inclusion_proof, completeness_proof) =
@@ -693,6 +1582,13 @@ mod tests {
                 "E9873D79C6D87DC0FB6A5778633389F4453213303DA61F20BD67FC233AA33261".to_string(), // Test key, safe to publish
             ),
             fee_rates_to_avg: Some(2), // small to speed up tests
+            outbox_path: None,
+            fee_bump_after_blocks: None,
+            max_fee_rate: None,
+            native_wallet: None,
+            native_wallet_start_height: None,
+            finality_depth: None,
+            polling_interval: None,
         };
 
         let incorrect_service = BitcoinService::new_without_client(
@@ -740,7 +1636,11 @@ mod tests {
             .map(|tx| parse_hex_transaction(tx).unwrap())
             .collect();
 
-        let block = BitcoinBlock { header, txdata };
+        let block = BitcoinBlock {
+            header,
+            txdata,
+            earlier_chunks: vec![],
+        };
 
         let txs = da_service.extract_relevant_blobs(&block);
 
@@ -800,7 +1700,11 @@ mod tests {
             .map(|tx| parse_hex_transaction(tx).unwrap())
             .collect();
 
-        let block = BitcoinBlock { header, txdata };
+        let block = BitcoinBlock {
+            header,
+            txdata,
+            earlier_chunks: vec![],
+        };
 
         let txs = da_service.extract_relevant_blobs(&block);
 
@@ -810,4 +1714,170 @@ mod tests {
             "Publickey recovered incorrectly!"
         );
     }
+
+    fn pending_entry(
+        id: u8,
+        state: InscriptionState,
+        broadcast_height: Option<u64>,
+    ) -> OutboxEntry {
+        let reveal_tx = Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        OutboxEntry {
+            state,
+            broadcast_height,
+            ..OutboxEntry::new([id; 32], &[id], "00".to_string(), &reveal_tx, 1.0)
+        }
+    }
+
+    #[test]
+    fn stuck_inscriptions_are_bumped_through_the_latest() {
+        let stuck = pending_entry(1, InscriptionState::RevealBroadcast, Some(100));
+        let recent = pending_entry(2, InscriptionState::RevealBroadcast, Some(102));
+
+        // nothing is stuck yet
+        assert!(inscription_to_bump(&[stuck.clone(), recent.clone()], 102, 3).is_none());
+
+        // the first inscription of the chain is stuck, it is bumped through the latest one
+        let pending = [stuck.clone(), recent.clone()];
+        let latest = inscription_to_bump(&pending, 103, 3).unwrap();
+        assert_eq!(latest.blob_hash, [2; 32]);
+
+        // a single stuck inscription is bumped as well
+        let latest = inscription_to_bump(std::slice::from_ref(&stuck), 103, 3).unwrap();
+        assert_eq!(latest.blob_hash, [1; 32]);
+
+        // the last bump has to stay unconfirmed as long before bumping again
+        let bumped = OutboxEntry {
+            bumped_height: Some(104),
+            ..recent.clone()
+        };
+        assert!(inscription_to_bump(&[stuck.clone(), bumped.clone()], 106, 3).is_none());
+        assert!(inscription_to_bump(&[stuck.clone(), bumped], 107, 3).is_some());
+
+        // the latest inscription is not fully broadcast, its output cannot be spent yet
+        let unbroadcast = pending_entry(3, InscriptionState::CommitBroadcast, None);
+        assert!(
+            inscription_to_bump(&[stuck.clone(), recent.clone(), unbroadcast], 110, 3).is_none()
+        );
+
+        // the chunks the commit tx waits for are bumped only if they are stuck themselves
+        let chunks = pending_entry(3, InscriptionState::ChunksBroadcast, Some(108));
+        let pending = [stuck.clone(), recent.clone(), chunks];
+        assert!(inscription_to_bump(&pending, 110, 3).is_none());
+        let latest = inscription_to_bump(&pending, 111, 3).unwrap();
+        assert_eq!(latest.blob_hash, [3; 32]);
+
+        assert!(inscription_to_bump(&[], 110, 3).is_none());
+    }
+
+    #[test]
+    fn cpfp_fee_pays_for_unconfirmed_ancestors() {
+        let ancestors = MempoolAncestors {
+            vsize: 1_000,
+            fees: 2_000,
+        };
+
+        // (1000 + 150) vB * 5 sat/vB - 2000 sat already paid
+        assert_eq!(cpfp_fee(ancestors, 150, 5.0), 3_750);
+        // fractional fees are rounded up
+        assert_eq!(cpfp_fee(ancestors, 151, 2.5), 878);
+        // the ancestors already pay more, the child still pays the relay fee for itself
+        assert_eq!(cpfp_fee(ancestors, 150, 1.5), 150);
+    }
+
+    #[test]
+    fn only_definite_rejections_drop_inscriptions() {
+        let rejection = |code, message: &str| {
+            anyhow::anyhow!(RPCError {
+                code,
+                message: message.to_string(),
+            })
+        };
+
+        assert!(is_definitely_rejected(&rejection(
+            -25,
+            "bad-txns-inputs-missingorspent"
+        )));
+        assert!(is_definitely_rejected(&rejection(
+            -26,
+            "txn-mempool-conflict"
+        )));
+        assert!(!is_definitely_rejected(&rejection(
+            -26,
+            "min relay fee not met"
+        )));
+        assert!(!is_definitely_rejected(&rejection(
+            -26,
+            "too-long-mempool-chain"
+        )));
+        assert!(!is_definitely_rejected(&anyhow::anyhow!(
+            "error sending request"
+        )));
+    }
+
+    /// Inscribes blobs slowly, so that they are still queued when the queue is closed
+    struct SlowQueue {
+        inscribed: Vec<Vec<u8>>,
+    }
+
+    #[async_trait::async_trait]
+    impl InscriptionQueue for SlowQueue {
+        async fn inscribe(&mut self, blob: &[u8]) -> Result<Txid, anyhow::Error> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.inscribed.push(blob.to_vec());
+            Ok(Txid::from_byte_array(
+                sha256d::Hash::hash(blob).to_byte_array(),
+            ))
+        }
+
+        async fn on_tick(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn queued_blobs_are_sent_when_queue_is_closed() {
+        let (tx, rx) = unbounded_channel();
+        let closed = CancellationToken::new();
+
+        let mut notifications = vec![];
+        for i in 0..3u8 {
+            let (notify, notification) = oneshot_channel();
+            tx.send(BlobWithNotifier {
+                blob: vec![i; 4],
+                notify,
+            })
+            .unwrap();
+            notifications.push(notification);
+        }
+
+        let queue_closed = closed.clone();
+        let worker = tokio::spawn(async move {
+            let mut queue = SlowQueue { inscribed: vec![] };
+            run_inscription_queue(&mut queue, rx, queue_closed, Duration::from_secs(3600)).await;
+            queue
+        });
+
+        closed.cancel();
+        let queue = worker.await.unwrap();
+
+        assert_eq!(
+            queue.inscribed,
+            vec![vec![0u8; 4], vec![1u8; 4], vec![2u8; 4]]
+        );
+        for notification in notifications {
+            assert!(notification.await.unwrap().is_ok());
+        }
+
+        // New requests are not accepted anymore
+        let (notify, _) = oneshot_channel();
+        assert!(tx
+            .send(BlobWithNotifier {
+                blob: vec![3; 4],
+                notify,
+            })
+            .is_err());
+    }
 }
@@ -11,6 +11,9 @@ use crate::verifier::ChainValidityCondition;
 pub struct BitcoinBlock {
     pub header: HeaderWrapper,
     pub txdata: Vec<Transaction>,
+    /// Chunk reveal txs of earlier blocks that the aggregate inscriptions of this block reference
+    #[serde(default)]
+    pub earlier_chunks: Vec<Transaction>,
 }
 
 impl SlotData for BitcoinBlock {
@@ -1,49 +1,36 @@
 use std::pin::Pin;
-use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Duration;
 
-use futures::{FutureExt, Stream};
+use futures::Stream;
 use pin_project::pin_project;
 use sov_rollup_interface::da::DaSpec;
-use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::services::da::{DaService, FinalizedHeaderEvent};
+use tokio::sync::mpsc::UnboundedReceiver;
 
 use crate::service::BitcoinService;
 
+type HeaderEventResult = Result<
+    FinalizedHeaderEvent<<<BitcoinService as DaService>::Spec as DaSpec>::BlockHeader>,
+    <BitcoinService as DaService>::Error,
+>;
+
+/// Stream of finalized headers and reorgs, which are received from the task that polls the node
 #[pin_project]
 pub struct BitcoinHeaderStream {
-    #[pin]
-    service: Arc<BitcoinService>,
-    interval: Duration,
-    timer: tokio::time::Interval,
+    receiver: UnboundedReceiver<HeaderEventResult>,
 }
 
 impl BitcoinHeaderStream {
-    pub fn new(service: Arc<BitcoinService>, interval: Duration) -> Self {
-        Self {
-            service,
-            interval,
-            timer: tokio::time::interval(interval),
-        }
+    pub fn new(receiver: UnboundedReceiver<HeaderEventResult>) -> Self {
+        Self { receiver }
     }
 }
 
 impl Stream for BitcoinHeaderStream {
-    type Item = Result<
-        <<BitcoinService as DaService>::Spec as DaSpec>::BlockHeader,
-        <BitcoinService as DaService>::Error,
-    >;
+    type Item = HeaderEventResult;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.project();
-        if this.timer.poll_tick(cx).is_ready() {
-            let header = futures::ready!(this
-                .service
-                .get_last_finalized_block_header()
-                .boxed()
-                .poll_unpin(cx));
-            return Poll::Ready(Some(header));
-        }
-        Poll::Pending
+        this.receiver.poll_recv(cx)
     }
 }
@@ -8,6 +8,9 @@ pub struct InclusionMultiProof {
     pub txids: Vec<[u8; 32]>,
     pub wtxids: Vec<[u8; 32]>,
     pub coinbase_tx: Transaction,
+    /// Chunk reveal txs of earlier blocks that the aggregate inscriptions of the block reference.
+    /// They are bound to the aggregates by their wtxids.
+    pub earlier_chunks: Vec<Transaction>,
 }
 
 impl InclusionMultiProof {
@@ -15,11 +18,13 @@ impl InclusionMultiProof {
         txids: Vec<[u8; 32]>,
         wtxids: Vec<[u8; 32]>,
         coinbase_tx: Transaction,
+        earlier_chunks: Vec<Transaction>,
     ) -> Self {
         InclusionMultiProof {
             txids,
             wtxids,
             coinbase_tx,
+            earlier_chunks,
         }
     }
 }
@@ -35,6 +40,7 @@ impl Default for InclusionMultiProof {
                 input: vec![],
                 output: vec![],
             },
+            earlier_chunks: vec![],
         }
     }
 }
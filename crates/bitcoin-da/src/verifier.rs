@@ -10,7 +10,7 @@ use sov_rollup_interface::zk::ValidityCondition;
 use thiserror::Error;
 
 use crate::helpers::builders::decompress_blob;
-use crate::helpers::parsers::parse_transaction;
+use crate::helpers::parsers::parse_complete_inscriptions;
 use crate::spec::BitcoinSpec;
 
 pub struct BitcoinVerifier {
@@ -168,16 +168,13 @@ impl DaVerifier for BitcoinVerifier {
             }
         }
 
-        // create hash set of blobs
-        let mut blobs_iter = blobs.iter();
-
         let mut inclusion_iter = inclusion_proof.txids.iter();
 
         let prefix = self.reveal_tx_id_prefix.as_slice();
         // Check starting bytes tx that parsed correctly is in blobs
         let mut completeness_tx_hashes = HashSet::new();
 
-        for (index_completeness, tx) in completeness_proof.iter().enumerate() {
+        for tx in completeness_proof.iter() {
             let txid = tx.txid().to_raw_hash().to_byte_array();
 
             // make sure it starts with the correct prefix
@@ -195,40 +192,43 @@ impl DaVerifier for BitcoinVerifier {
                 return Err(ValidationError::RelevantTxNotFoundInBlock);
             }
 
-            // it must be parsed correctly
-            if let Ok(parsed_tx) = parse_transaction(tx, &self.rollup_name) {
-                if let Some(blob_hash) = parsed_tx.get_sig_verified_hash() {
-                    let blob = blobs_iter.next();
+            completeness_tx_hashes.insert(txid);
+        }
 
-                    if blob.is_none() {
-                        return Err(ValidationError::ValidBlobNotFoundInBlobs);
-                    }
+        let mut blobs_iter = blobs.iter();
 
-                    let blob = blob.unwrap();
-                    if blob.hash != blob_hash {
-                        return Err(ValidationError::BlobWasTamperedWith);
-                    }
+        // every blob in the completeness proof must be in blobs, in the same order.
+        // blobs inscribed in chunks are reassembled from the chunks in this block or earlier ones.
+        let complete_inscriptions = parse_complete_inscriptions(
+            completeness_proof.iter(),
+            &inclusion_proof.earlier_chunks,
+            &self.rollup_name,
+        );
+        for (parsed_tx, blob_hash) in complete_inscriptions {
+            let blob = blobs_iter
+                .next()
+                .ok_or(ValidationError::ValidBlobNotFoundInBlobs)?;
 
-                    if parsed_tx.public_key != blob.sender.0 {
-                        return Err(ValidationError::IncorrectSenderInBlob);
-                    }
+            if blob.hash != blob_hash {
+                return Err(ValidationError::BlobWasTamperedWith);
+            }
 
-                    // decompress the blob
-                    let decompressed_blob = decompress_blob(&parsed_tx.body);
+            if parsed_tx.public_key != blob.sender.0 {
+                return Err(ValidationError::IncorrectSenderInBlob);
+            }
 
-                    // read the supplied blob from txs
-                    let mut blob_content = blobs[index_completeness].blob.clone();
-                    blob_content.advance(blob_content.total_len());
-                    let blob_content = blob_content.accumulator();
+            // decompress the blob
+            let decompressed_blob = decompress_blob(&parsed_tx.body);
 
-                    // assert tx content is not modified
-                    if blob_content != decompressed_blob {
-                        return Err(ValidationError::BlobContentWasModified);
-                    }
-                }
-            }
+            // read the supplied blob from txs
+            let mut blob_content = blob.blob.clone();
+            blob_content.advance(blob_content.total_len());
+            let blob_content = blob_content.accumulator();
 
-            completeness_tx_hashes.insert(txid);
+            // assert tx content is not modified
+            if blob_content != decompressed_blob {
+                return Err(ValidationError::BlobContentWasModified);
+            }
         }
 
         // assert no extra txs than the ones in the completeness proof are left
@@ -375,6 +375,7 @@ mod tests {
                 .map(|t| t.wtxid().to_byte_array())
                 .collect(),
             coinbase_tx: block_txs[0].clone(),
+            earlier_chunks: vec![],
         };
 
         // There should not be any blobs
@@ -446,6 +447,7 @@ mod tests {
                 .map(|t| t.wtxid().to_byte_array())
                 .collect(),
             coinbase_tx: block_txs[0].clone(),
+            earlier_chunks: vec![],
         };
 
         // Coinbase tx wtxid should be [0u8;32]
@@ -537,6 +539,7 @@ mod tests {
                 .map(|t| t.wtxid().to_byte_array())
                 .collect(),
             coinbase_tx: block_txs[0].clone(),
+            earlier_chunks: vec![],
         };
 
         // Coinbase tx wtxid should be [0u8;32]
@@ -648,6 +651,7 @@ mod tests {
                 .map(|t| t.wtxid().to_byte_array())
                 .collect(),
             coinbase_tx: block_txs[0].clone(),
+            earlier_chunks: vec![],
         };
 
         // Coinbase tx wtxid should be [0u8;32]
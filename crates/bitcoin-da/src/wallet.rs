@@ -0,0 +1,533 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+use bitcoin::hashes::Hash;
+use bitcoin::key::{TapTweak, TweakedKeypair};
+use bitcoin::secp256k1::{self, All, Keypair, Secp256k1, SecretKey};
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use bitcoin::{Address, Network, OutPoint, ScriptBuf, Transaction, TxOut, Txid, Witness};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::rpc::BitcoinNode;
+use crate::spec::utxo::UTXO;
+
+/// Name of the file in the storage directory of the node that keeps the native wallet
+pub const NATIVE_WALLET_FILE_NAME: &str = "bitcoin_da_native_wallet.json";
+
+/// Number of the last scanned blocks whose changes to the wallet are kept, so that they can be
+/// undone when the blocks are reorged out
+const UNDO_DEPTH: usize = 100;
+
+/// Changes a scanned block made to the wallet
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ScannedBlock {
+    height: u64,
+    hash: String,
+    /// Outputs of the wallet spent in the block
+    spent: Vec<(OutPoint, TxOut)>,
+    /// Outputs paying to the wallet created in the block
+    created: Vec<OutPoint>,
+    /// Txs of the wallet in the block
+    txids: Vec<Txid>,
+}
+
+/// What the wallet keeps on disk, so that it does not have to scan the blocks again after a
+/// restart, which is not possible on a pruned node
+#[derive(Debug, Serialize, Deserialize)]
+struct WalletState {
+    address: String,
+    outputs: Vec<(OutPoint, TxOut)>,
+    tx_heights: Vec<(Txid, u64)>,
+    next_height: u64,
+    blocks: VecDeque<ScannedBlock>,
+}
+
+/// Wallet that signs with the DA private key in process instead of using the wallet of bitcoind.
+/// The funds are held in a taproot key path output of the key, which the wallet finds by scanning
+/// blocks. Before they are spent, outputs are checked with `gettxout`, which also works on a
+/// pruned node without a wallet.
+#[derive(Debug)]
+pub struct NativeWallet {
+    secp: Secp256k1<All>,
+    keypair: TweakedKeypair,
+    address: Address,
+    script_pubkey: ScriptBuf,
+    /// Outputs paying to the wallet. The ones spent in a block are removed when the block is scanned.
+    outputs: HashMap<OutPoint, TxOut>,
+    /// Heights of the scanned blocks that include a tx of the wallet
+    tx_heights: HashMap<Txid, u64>,
+    start_height: u64,
+    next_height: u64,
+    /// The last scanned blocks, oldest first
+    blocks: VecDeque<ScannedBlock>,
+    /// File the wallet is saved to after every change, if any
+    path: Option<PathBuf>,
+}
+
+impl NativeWallet {
+    /// Creates a wallet that looks for its outputs starting from the block at `start_height`.
+    /// If `path` is set, the wallet is loaded from it and continues from the last scanned block.
+    pub fn new(
+        private_key: &SecretKey,
+        network: Network,
+        start_height: u64,
+        path: Option<PathBuf>,
+    ) -> Result<Self, anyhow::Error> {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_secret_key(&secp, private_key);
+        let (internal_key, _parity) = keypair.x_only_public_key();
+        let address = Address::p2tr(&secp, internal_key, None, network);
+        let script_pubkey = address.script_pubkey();
+
+        info!(%address, "Using the native wallet");
+
+        let mut wallet = Self {
+            keypair: keypair.tap_tweak(&secp, None),
+            secp,
+            address,
+            script_pubkey,
+            outputs: HashMap::new(),
+            tx_heights: HashMap::new(),
+            start_height,
+            next_height: start_height,
+            blocks: VecDeque::new(),
+            path,
+        };
+
+        if let Some(path) = wallet.path.as_ref().filter(|path| path.exists()) {
+            let state: WalletState = serde_json::from_slice(&fs::read(path)?)?;
+            if state.address == wallet.address.to_string() {
+                wallet.outputs = state.outputs.into_iter().collect();
+                wallet.tx_heights = state.tx_heights.into_iter().collect();
+                wallet.next_height = state.next_height;
+                wallet.blocks = state.blocks;
+                info!(
+                    "Native wallet continues scanning from block {}",
+                    wallet.next_height
+                );
+            } else {
+                warn!(
+                    saved_address = %state.address,
+                    "Saved native wallet is for another key, scanning from the start height"
+                );
+            }
+        }
+
+        Ok(wallet)
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Undoes the scanned blocks that were reorged out and scans the blocks that arrived since
+    /// the last sync
+    #[instrument(level = "trace", skip_all, err)]
+    pub async fn sync(&mut self, client: &BitcoinNode) -> Result<(), anyhow::Error> {
+        let tip = client.get_block_count().await?;
+        let reorged = self.disconnect_reorged_blocks(client, tip).await?;
+        if tip < self.next_height {
+            if reorged {
+                self.save()?;
+            }
+            return Ok(());
+        }
+
+        if tip - self.next_height > 100 {
+            info!(
+                "Native wallet is scanning blocks {} to {}",
+                self.next_height, tip
+            );
+        }
+
+        for height in self.next_height..=tip {
+            let block_hash = client.get_block_hash(height).await?;
+            let block = client.get_block(block_hash.clone()).await?;
+            self.scan_block(&block.txdata, height, block_hash);
+        }
+
+        self.save()
+    }
+
+    /// Undoes the scanned blocks that are not in the best chain anymore, newest first.
+    /// Returns whether there were any.
+    async fn disconnect_reorged_blocks(
+        &mut self,
+        client: &BitcoinNode,
+        tip: u64,
+    ) -> Result<bool, anyhow::Error> {
+        let mut reorged = false;
+        while let Some(block) = self.blocks.back() {
+            if block.height <= tip && client.get_block_hash(block.height).await? == block.hash {
+                break;
+            }
+
+            let block = self.blocks.pop_back().expect("The block is checked above");
+            warn!(
+                height = block.height,
+                hash = %block.hash,
+                "Native wallet undoes a reorged block"
+            );
+            self.disconnect_block(block);
+            reorged = true;
+
+            if self.blocks.is_empty() && self.next_height > self.start_height {
+                warn!(
+                    "Reorg is deeper than the {} blocks the native wallet can undo, scanning from the start height",
+                    UNDO_DEPTH
+                );
+                self.outputs.clear();
+                self.tx_heights.clear();
+                self.next_height = self.start_height;
+            }
+        }
+        Ok(reorged)
+    }
+
+    fn scan_block(&mut self, txs: &[Transaction], height: u64, hash: String) {
+        let mut scanned = ScannedBlock {
+            height,
+            hash,
+            spent: vec![],
+            created: vec![],
+            txids: vec![],
+        };
+
+        for tx in txs {
+            let mut is_wallet_tx = false;
+
+            for input in tx.input.iter() {
+                if let Some(output) = self.outputs.remove(&input.previous_output) {
+                    scanned.spent.push((input.previous_output, output));
+                    is_wallet_tx = true;
+                }
+            }
+
+            let txid = tx.txid();
+            for (vout, output) in tx.output.iter().enumerate() {
+                if output.script_pubkey == self.script_pubkey {
+                    let outpoint = OutPoint::new(txid, vout as u32);
+                    self.outputs.insert(outpoint, output.clone());
+                    scanned.created.push(outpoint);
+                    is_wallet_tx = true;
+                }
+            }
+
+            if is_wallet_tx {
+                self.tx_heights.insert(txid, height);
+                scanned.txids.push(txid);
+            }
+        }
+
+        self.blocks.push_back(scanned);
+        if self.blocks.len() > UNDO_DEPTH {
+            self.blocks.pop_front();
+        }
+        self.next_height = height + 1;
+    }
+
+    /// Reverts the changes of a scanned block: the outputs it spent are unspent again, and the
+    /// outputs it created are gone
+    fn disconnect_block(&mut self, block: ScannedBlock) {
+        // outputs both created and spent in the block are removed again below
+        for (outpoint, output) in block.spent {
+            self.outputs.insert(outpoint, output);
+        }
+        for outpoint in block.created {
+            self.outputs.remove(&outpoint);
+        }
+        for txid in block.txids {
+            self.tx_heights.remove(&txid);
+        }
+        self.next_height = block.height;
+    }
+
+    fn save(&self) -> Result<(), anyhow::Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let state = WalletState {
+            address: self.address.to_string(),
+            outputs: self
+                .outputs
+                .iter()
+                .map(|(outpoint, output)| (*outpoint, output.clone()))
+                .collect(),
+            tx_heights: self
+                .tx_heights
+                .iter()
+                .map(|(txid, height)| (*txid, *height))
+                .collect(),
+            next_height: self.next_height,
+            blocks: self.blocks.clone(),
+        };
+
+        // Write to a temporary file first so a crash cannot leave a truncated wallet behind
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&state)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Keeps track of the outputs of `tx` paying to the wallet, before the tx is in a block.
+    /// Returns whether there were any.
+    pub fn record_outputs(&mut self, tx: &Transaction) -> bool {
+        let txid = tx.txid();
+        let mut found = false;
+        for (vout, output) in tx.output.iter().enumerate() {
+            if output.script_pubkey == self.script_pubkey {
+                self.outputs
+                    .insert(OutPoint::new(txid, vout as u32), output.clone());
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Returns an output of the wallet, even if it is spent
+    pub fn output(&self, outpoint: &OutPoint) -> Option<&TxOut> {
+        self.outputs.get(outpoint)
+    }
+
+    /// Returns the unspent outputs of the wallet, including the unconfirmed ones
+    #[instrument(level = "trace", skip_all, err)]
+    pub async fn get_utxos(&mut self, client: &BitcoinNode) -> Result<Vec<UTXO>, anyhow::Error> {
+        self.sync(client).await?;
+
+        let mut utxos = Vec::with_capacity(self.outputs.len());
+        for outpoint in self.outputs.keys() {
+            // outputs spent in the mempool, or created by txs that were replaced, are skipped.
+            // they are kept in case the spending tx is dropped or the creating tx is mined later.
+            let Some((output, confirmations)) = client
+                .get_tx_out(outpoint.txid.to_string(), outpoint.vout)
+                .await?
+            else {
+                continue;
+            };
+
+            utxos.push(UTXO {
+                tx_id: outpoint.txid,
+                vout: outpoint.vout,
+                address: self.address.to_string(),
+                script_pubkey: output.script_pubkey.to_hex_string(),
+                amount: output.value.to_sat(),
+                confirmations,
+                spendable: true,
+                solvable: true,
+            });
+        }
+
+        Ok(utxos)
+    }
+
+    /// Number of confirmations of a tx of the wallet as of the last sync
+    pub fn tx_confirmations(&self, txid: &Txid) -> u64 {
+        self.tx_heights
+            .get(txid)
+            .map(|height| self.next_height.saturating_sub(*height))
+            .unwrap_or(0)
+    }
+
+    /// Signs all inputs of `tx`, which must spend outputs of the wallet.
+    /// `prevouts` are the outputs spent by the inputs, in the same order.
+    pub fn sign(&mut self, tx: &mut Transaction, prevouts: &[TxOut]) -> Result<(), anyhow::Error> {
+        if prevouts.len() != tx.input.len() {
+            anyhow::bail!(
+                "Expected {} prevouts, got {}",
+                tx.input.len(),
+                prevouts.len()
+            );
+        }
+
+        let mut signatures = Vec::with_capacity(tx.input.len());
+        let mut sighash_cache = SighashCache::new(&*tx);
+        for (index, prevout) in prevouts.iter().enumerate() {
+            if prevout.script_pubkey != self.script_pubkey {
+                anyhow::bail!("Input {} does not belong to the native wallet", index);
+            }
+
+            let sighash = sighash_cache.taproot_key_spend_signature_hash(
+                index,
+                &Prevouts::All(prevouts),
+                TapSighashType::Default,
+            )?;
+            signatures.push(
+                self.secp.sign_schnorr_with_rng(
+                    &secp256k1::Message::from_digest_slice(sighash.as_byte_array())
+                        .expect("should be cryptographically secure hash"),
+                    &self.keypair.to_inner(),
+                    &mut rand::thread_rng(),
+                ),
+            );
+        }
+
+        for (input, signature) in tx.input.iter_mut().zip(signatures) {
+            input.witness = Witness::new();
+            input.witness.push(signature.as_ref());
+        }
+
+        // the change output can be spent by the next tx before this one is mined
+        if !self.record_outputs(tx) {
+            warn!(txid = %tx.txid(), "Signed tx has no outputs to the native wallet");
+        }
+
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::secp256k1::SecretKey;
+    use bitcoin::{Amount, Network, OutPoint, Sequence, Transaction, TxIn, TxOut, Witness};
+
+    use super::NativeWallet;
+
+    fn tx(inputs: Vec<OutPoint>, outputs: Vec<TxOut>) -> Transaction {
+        Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: Default::default(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: outputs,
+        }
+    }
+
+    #[test]
+    fn scans_and_signs() {
+        let private_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let mut wallet = NativeWallet::new(&private_key, Network::Regtest, 10, None).unwrap();
+
+        let funding = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: wallet.address().script_pubkey(),
+        };
+        let funding_tx = tx(vec![OutPoint::null()], vec![funding.clone()]);
+        let unrelated_tx = tx(
+            vec![OutPoint::null()],
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: Default::default(),
+            }],
+        );
+        wallet.scan_block(
+            &[funding_tx.clone(), unrelated_tx.clone()],
+            10,
+            "10".to_string(),
+        );
+        wallet.next_height = 12;
+
+        let funding_outpoint = OutPoint::new(funding_tx.txid(), 0);
+        assert_eq!(wallet.output(&funding_outpoint), Some(&funding));
+        assert_eq!(wallet.tx_confirmations(&funding_tx.txid()), 2);
+        assert_eq!(wallet.tx_confirmations(&unrelated_tx.txid()), 0);
+
+        let mut spending_tx = tx(
+            vec![funding_outpoint],
+            vec![TxOut {
+                value: Amount::from_sat(9_000),
+                script_pubkey: wallet.address().script_pubkey(),
+            }],
+        );
+        wallet
+            .sign(&mut spending_tx, std::slice::from_ref(&funding))
+            .unwrap();
+        assert_eq!(spending_tx.input[0].witness.len(), 1);
+        // the change is known before the tx is mined
+        assert!(wallet
+            .output(&OutPoint::new(spending_tx.txid(), 0))
+            .is_some());
+
+        // spent outputs are removed once the spending tx is in a block
+        wallet.scan_block(&[spending_tx.clone()], 12, "12".to_string());
+        assert!(wallet.output(&funding_outpoint).is_none());
+
+        // outputs of other scripts cannot be signed for
+        let mut foreign_tx = tx(vec![OutPoint::new(unrelated_tx.txid(), 0)], vec![]);
+        assert!(wallet
+            .sign(&mut foreign_tx, &[unrelated_tx.output[0].clone()])
+            .is_err());
+    }
+
+    #[test]
+    fn reorged_blocks_are_undone() {
+        let private_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let mut wallet = NativeWallet::new(&private_key, Network::Regtest, 10, None).unwrap();
+
+        let funding = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: wallet.address().script_pubkey(),
+        };
+        let funding_tx = tx(vec![OutPoint::null()], vec![funding.clone()]);
+        let funding_outpoint = OutPoint::new(funding_tx.txid(), 0);
+        wallet.scan_block(&[funding_tx.clone()], 10, "10".to_string());
+
+        let spending_tx = tx(vec![funding_outpoint], vec![funding.clone()]);
+        let change_outpoint = OutPoint::new(spending_tx.txid(), 0);
+        wallet.scan_block(&[spending_tx.clone()], 11, "11".to_string());
+        assert!(wallet.output(&funding_outpoint).is_none());
+        assert!(wallet.output(&change_outpoint).is_some());
+        assert_eq!(wallet.tx_confirmations(&spending_tx.txid()), 1);
+
+        // the spend is undone and the created output is gone
+        let block = wallet.blocks.pop_back().unwrap();
+        wallet.disconnect_block(block);
+        assert_eq!(wallet.output(&funding_outpoint), Some(&funding));
+        assert!(wallet.output(&change_outpoint).is_none());
+        assert_eq!(wallet.tx_confirmations(&spending_tx.txid()), 0);
+        assert_eq!(wallet.next_height, 11);
+        assert_eq!(wallet.tx_confirmations(&funding_tx.txid()), 1);
+    }
+
+    #[test]
+    fn wallet_is_persisted() {
+        let path = std::env::temp_dir().join(format!(
+            "bitcoin_da_native_wallet_test_{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let private_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let mut wallet =
+            NativeWallet::new(&private_key, Network::Regtest, 10, Some(path.clone())).unwrap();
+        let funding = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: wallet.address().script_pubkey(),
+        };
+        let funding_tx = tx(vec![OutPoint::null()], vec![funding.clone()]);
+        wallet.scan_block(&[funding_tx.clone()], 10, "10".to_string());
+        wallet.save().unwrap();
+
+        // the wallet continues from the last scanned block after a restart
+        let wallet =
+            NativeWallet::new(&private_key, Network::Regtest, 10, Some(path.clone())).unwrap();
+        assert_eq!(wallet.next_height, 11);
+        assert_eq!(
+            wallet.output(&OutPoint::new(funding_tx.txid(), 0)),
+            Some(&funding)
+        );
+        assert_eq!(wallet.blocks.len(), 1);
+
+        // a wallet of another key starts from scratch
+        let other_key = SecretKey::from_slice(&[2; 32]).unwrap();
+        let wallet =
+            NativeWallet::new(&other_key, Network::Regtest, 10, Some(path.clone())).unwrap();
+        assert_eq!(wallet.next_height, 10);
+        assert!(wallet.blocks.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+//! Support for the `safe` and `finalized` block tags.
+//!
+//! `safe` resolves to the highest L2 block covered by a sequencer commitment on the DA layer and
+//! `finalized` to the highest L2 block covered by a verified ZK proof. Both come from the ledger,
+//! which the EVM module cannot read, so the tags are replaced by block numbers before the
+//! request reaches the method.
+
+use std::sync::Arc;
+
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::core::server::MethodsError;
+use jsonrpsee::core::RegisterMethodError;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{Methods, RpcModule};
+use reth_rpc::eth::error::EthApiError;
+use serde_json::Value;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
+
+/// Methods that take a block, with the position of the block parameter.
+/// `eth_newFilter` resolves the tags of its filter itself, as it installs
+/// the filter for the connection of the request, see [`crate::create_filter_rpc_module`].
+const BLOCK_PARAMS: &[(&str, usize)] = &[
+    ("eth_getBalance", 1),
+    ("eth_getStorageAt", 2),
+    ("eth_getTransactionCount", 1),
+    ("eth_getCode", 1),
+    ("eth_getProof", 2),
+    ("eth_call", 1),
+    ("eth_createAccessList", 1),
+    ("eth_estimateGas", 1),
+    ("eth_estimateDiffSize", 1),
+    ("eth_simulateV1", 1),
+    ("eth_getBlockByNumber", 0),
+    ("eth_getBlockReceipts", 0),
+    ("eth_getBlockTransactionCountByNumber", 0),
+    ("eth_getTransactionByBlockNumberAndIndex", 0),
+    ("eth_feeHistory", 1),
+    ("eth_getLogs", 0),
+    ("debug_traceBlockByNumber", 0),
+    ("debug_traceCall", 1),
+    ("debug_traceCallMany", 1),
+    ("trace_block", 0),
+    ("trace_replayBlockTransactions", 0),
+    ("trace_filter", 0),
+];
+
+/// Fields of object parameters (EIP-1898 block ids, log filters and state contexts) that hold a block.
+const BLOCK_FIELDS: &[&str] = &["blockNumber", "fromBlock", "toBlock"];
+
+/// Wraps every method in `methods` that takes a block, so that `safe` and `finalized`
+/// are resolved with the soft confirmation statuses in the ledger.
+/// Must be called after all such methods are registered.
+pub fn resolve_block_tags<L>(
+    methods: &mut RpcModule<()>,
+    ledger: L,
+) -> Result<(), RegisterMethodError>
+where
+    L: LedgerRpcProvider + Send + Sync + 'static,
+{
+    let inner: Arc<Methods> = Arc::new(methods.clone().into());
+    let ledger = Arc::new(ledger);
+
+    for &(method, position) in BLOCK_PARAMS {
+        if methods.remove_method(method).is_none() {
+            continue;
+        }
+
+        let inner = inner.clone();
+        let ledger = ledger.clone();
+        methods.register_async_method(method, move |params, _| {
+            let inner = inner.clone();
+            let ledger = ledger.clone();
+            async move {
+                let mut values = params.parse::<Option<Vec<Value>>>()?.unwrap_or_default();
+                if let Some(block) = values.get_mut(position) {
+                    resolve_tags(block, ledger.as_ref())?;
+                }
+
+                let mut forwarded = ArrayParams::new();
+                for value in values {
+                    forwarded
+                        .insert(value)
+                        .map_err(|e| to_jsonrpsee_error_object("Invalid params", e))?;
+                }
+
+                inner
+                    .call::<_, Value>(method, forwarded)
+                    .await
+                    .map_err(|e| match e {
+                        MethodsError::JsonRpc(err) => err,
+                        e => to_jsonrpsee_error_object("Internal error", e),
+                    })
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Replaces `safe` and `finalized` in a block parameter, or in the block fields of an object parameter.
+pub(crate) fn resolve_tags<L: LedgerRpcProvider>(
+    block: &mut Value,
+    ledger: &L,
+) -> Result<(), ErrorObjectOwned> {
+    match block {
+        Value::String(tag) => {
+            if let Some(number) = resolve_tag(tag, ledger)? {
+                *block = number;
+            }
+        }
+        Value::Object(fields) => {
+            for field in BLOCK_FIELDS {
+                let Some(Value::String(tag)) = fields.get(*field) else {
+                    continue;
+                };
+                if let Some(number) = resolve_tag(tag, ledger)? {
+                    fields.insert(field.to_string(), number);
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_tag<L: LedgerRpcProvider>(
+    tag: &str,
+    ledger: &L,
+) -> Result<Option<Value>, ErrorObjectOwned> {
+    let l2_height = match tag {
+        "safe" => ledger.get_last_committed_l2_height(),
+        "finalized" => ledger.get_last_proven_l2_height(),
+        _ => return Ok(None),
+    }
+    .map_err(|e| to_jsonrpsee_error_object("Failed to read ledger", e))?
+    .ok_or_else(|| ErrorObjectOwned::from(EthApiError::UnknownBlockNumber))?;
+
+    Ok(Some(Value::String(format!("{l2_height:#x}"))))
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use citrea_evm::{Evm, Filter, FilterBlockOption, FilterError, LogResponse};
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use reth_primitives::{BlockNumberOrTag, B256, U64};
+use serde::Serialize;
+use serde_json::Value;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use tracing::info;
+
+use crate::block_tags::resolve_tags;
+use crate::subscription::{latest_block_number, SubscriptionManager};
+
+/// Limits of the filters installed with `eth_newFilter`, `eth_newBlockFilter`
+/// and `eth_newPendingTransactionFilter`
+#[derive(Clone, Debug)]
+pub struct FilterConfig {
+    /// Max number of filters a connection can have installed at the same time
+    pub max_filters_per_connection: usize,
+    /// Max number of filters all connections together can have installed at the same time
+    pub max_filters: usize,
+    /// Filters that are not polled for this long are uninstalled
+    pub idle_timeout: Duration,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        Self {
+            max_filters_per_connection: 100,
+            max_filters: 10_000,
+            // Same as geth
+            idle_timeout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Changes returned by `eth_getFilterChanges`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FilterChanges {
+    Logs(Vec<LogResponse>),
+    /// Block hashes or transaction hashes
+    Hashes(Vec<B256>),
+}
+
+enum FilterKind {
+    /// `next_block` is the first block whose logs were not returned yet
+    Log {
+        filter: Filter,
+        next_block: u64,
+    },
+    /// `next_block` is the first block whose hash was not returned yet
+    Block {
+        next_block: u64,
+    },
+    PendingTransaction(broadcast::Receiver<B256>),
+}
+
+struct InstalledFilter {
+    kind: FilterKind,
+    connection_id: usize,
+    last_poll: Instant,
+}
+
+/// Keeps the installed filters and their cursors
+pub struct FilterManager<C: sov_modules_api::Context> {
+    storage: C::Storage,
+    subscription_manager: SubscriptionManager,
+    config: FilterConfig,
+    filters: Arc<Mutex<HashMap<U64, InstalledFilter>>>,
+    next_id: AtomicU64,
+}
+
+impl<C: sov_modules_api::Context> FilterManager<C> {
+    /// Spawns a task that uninstalls the filters that are idle for longer than the configured timeout
+    pub fn new(
+        storage: C::Storage,
+        subscription_manager: SubscriptionManager,
+        config: FilterConfig,
+    ) -> Self {
+        let filters: Arc<Mutex<HashMap<U64, InstalledFilter>>> = Default::default();
+
+        let idle_timeout = config.idle_timeout;
+        let installed_filters = filters.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(idle_timeout);
+            loop {
+                interval.tick().await;
+                let now = Instant::now();
+                installed_filters
+                    .lock()
+                    .unwrap()
+                    .retain(|_, filter| now.duration_since(filter.last_poll) < idle_timeout);
+            }
+        });
+
+        Self {
+            storage,
+            subscription_manager,
+            config,
+            filters,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn install(&self, kind: FilterKind, connection_id: usize) -> Result<U64, FilterError> {
+        let mut filters = self.filters.lock().unwrap();
+
+        if filters.len() >= self.config.max_filters {
+            return Err(FilterError::TooManyInstalledFilters(
+                self.config.max_filters,
+            ));
+        }
+
+        let installed_by_connection = filters
+            .values()
+            .filter(|filter| filter.connection_id == connection_id)
+            .count();
+        if installed_by_connection >= self.config.max_filters_per_connection {
+            return Err(FilterError::TooManyFilters(
+                self.config.max_filters_per_connection,
+            ));
+        }
+
+        let id = U64::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+        filters.insert(
+            id,
+            InstalledFilter {
+                kind,
+                connection_id,
+                last_poll: Instant::now(),
+            },
+        );
+
+        Ok(id)
+    }
+
+    fn next_block(&self) -> u64 {
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+        latest_block_number(&evm, &mut working_set) + 1
+    }
+
+    /// Installs a filter for the logs of the blocks published after this call
+    pub fn new_log_filter(&self, filter: Filter, connection_id: usize) -> Result<U64, FilterError> {
+        let next_block = self.next_block();
+        self.install(FilterKind::Log { filter, next_block }, connection_id)
+    }
+
+    /// Installs a filter for the hashes of the blocks published after this call
+    pub fn new_block_filter(&self, connection_id: usize) -> Result<U64, FilterError> {
+        let next_block = self.next_block();
+        self.install(FilterKind::Block { next_block }, connection_id)
+    }
+
+    /// Installs a filter for the hashes of the transactions that become ready for inclusion after this call
+    pub fn new_pending_transaction_filter(&self, connection_id: usize) -> Result<U64, FilterError> {
+        let rx = self.subscription_manager.subscribe_pending_transactions();
+        self.install(FilterKind::PendingTransaction(rx), connection_id)
+    }
+
+    /// Returns whether the filter was installed
+    pub fn uninstall(&self, id: U64) -> bool {
+        self.filters.lock().unwrap().remove(&id).is_some()
+    }
+
+    /// Returns the changes since the last poll of the filter, and moves its cursor past them
+    pub fn changes(&self, id: U64) -> RpcResult<FilterChanges> {
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+
+        let mut filters = self.filters.lock().unwrap();
+        let installed = filters
+            .get_mut(&id)
+            .ok_or(FilterError::FilterNotFound(id))?;
+        installed.last_poll = Instant::now();
+
+        match &mut installed.kind {
+            FilterKind::Log { filter, next_block } => {
+                let latest = latest_block_number(&evm, &mut working_set);
+                let logs = match block_range_since(filter, *next_block, latest) {
+                    Some((from, to)) => {
+                        let range_filter = Filter {
+                            block_option: FilterBlockOption::Range {
+                                from_block: Some(BlockNumberOrTag::Number(from)),
+                                to_block: Some(BlockNumberOrTag::Number(to)),
+                            },
+                            ..filter.clone()
+                        };
+                        evm.eth_get_logs(range_filter, &mut working_set)?
+                    }
+                    None => vec![],
+                };
+                *next_block = (*next_block).max(latest + 1);
+                Ok(FilterChanges::Logs(logs))
+            }
+            FilterKind::Block { next_block } => {
+                let latest = latest_block_number(&evm, &mut working_set);
+                let hashes = (*next_block..=latest)
+                    .map_while(|number| evm.block_hash_from_number(number, &mut working_set))
+                    .collect::<Vec<_>>();
+                *next_block += hashes.len() as u64;
+                Ok(FilterChanges::Hashes(hashes))
+            }
+            FilterKind::PendingTransaction(rx) => {
+                let mut hashes = vec![];
+                loop {
+                    match rx.try_recv() {
+                        Ok(hash) => hashes.push(hash),
+                        // The oldest hashes were dropped, the remaining ones are still returned
+                        Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                        Err(_) => break,
+                    }
+                }
+                Ok(FilterChanges::Hashes(hashes))
+            }
+        }
+    }
+
+    /// Returns all logs matching a log filter
+    pub fn logs(&self, id: U64) -> RpcResult<Vec<LogResponse>> {
+        let filter = {
+            let mut filters = self.filters.lock().unwrap();
+            match filters.get_mut(&id) {
+                Some(InstalledFilter {
+                    kind: FilterKind::Log { filter, .. },
+                    last_poll,
+                    ..
+                }) => {
+                    *last_poll = Instant::now();
+                    filter.clone()
+                }
+                _ => return Err(FilterError::FilterNotFound(id).into()),
+            }
+        };
+
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+        evm.eth_get_logs(filter, &mut working_set)
+    }
+}
+
+/// Blocks from `next_block` up to `latest` that are in the range of the filter.
+/// Filters on a block hash have no new blocks.
+fn block_range_since(filter: &Filter, next_block: u64, latest: u64) -> Option<(u64, u64)> {
+    let (from, to) = match filter.block_option {
+        FilterBlockOption::Range {
+            from_block,
+            to_block,
+        } => (from_block, to_block),
+        FilterBlockOption::AtBlockHash(_) => return None,
+    };
+
+    let from = match from {
+        Some(BlockNumberOrTag::Number(number)) => number.max(next_block),
+        _ => next_block,
+    };
+    let to = match to {
+        Some(BlockNumberOrTag::Number(number)) => number.min(latest),
+        _ => latest,
+    };
+
+    (from <= to).then_some((from, to))
+}
+
+/// Creates the module with the filter methods.
+/// The `safe` and `finalized` tags of log filters are resolved with the soft confirmation statuses in `ledger`.
+pub fn create_filter_rpc_module<C, L>(
+    manager: FilterManager<C>,
+    ledger: L,
+) -> Result<RpcModule<FilterManager<C>>, jsonrpsee::core::RegisterMethodError>
+where
+    C: sov_modules_api::Context,
+    L: LedgerRpcProvider + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(manager);
+    let ledger = Arc::new(ledger);
+
+    rpc.register_async_method_with_details(
+        "eth_newFilter",
+        move |parameters, connection, manager| {
+            let ledger = ledger.clone();
+            async move {
+                info!("eth module: eth_newFilter");
+                let mut filter: Value = parameters.one()?;
+                resolve_tags(&mut filter, ledger.as_ref())?;
+                let filter: Filter = serde_json::from_value(filter)
+                    .map_err(|e| to_jsonrpsee_error_object("Invalid params", e))?;
+                Ok::<U64, ErrorObjectOwned>(manager.new_log_filter(filter, connection.id())?)
+            }
+        },
+    )?;
+
+    rpc.register_async_method_with_details(
+        "eth_newBlockFilter",
+        |_, connection, manager| async move {
+            info!("eth module: eth_newBlockFilter");
+            Ok::<U64, ErrorObjectOwned>(manager.new_block_filter(connection.id())?)
+        },
+    )?;
+
+    rpc.register_async_method_with_details(
+        "eth_newPendingTransactionFilter",
+        |_, connection, manager| async move {
+            info!("eth module: eth_newPendingTransactionFilter");
+            Ok::<U64, ErrorObjectOwned>(manager.new_pending_transaction_filter(connection.id())?)
+        },
+    )?;
+
+    rpc.register_async_method("eth_getFilterChanges", |parameters, manager| async move {
+        info!("eth module: eth_getFilterChanges");
+        let id: U64 = parameters.one()?;
+        manager.changes(id)
+    })?;
+
+    rpc.register_async_method("eth_getFilterLogs", |parameters, manager| async move {
+        info!("eth module: eth_getFilterLogs");
+        let id: U64 = parameters.one()?;
+        manager.logs(id)
+    })?;
+
+    rpc.register_async_method("eth_uninstallFilter", |parameters, manager| async move {
+        info!("eth module: eth_uninstallFilter");
+        let id: U64 = parameters.one()?;
+        Ok::<bool, ErrorObjectOwned>(manager.uninstall(id))
+    })?;
+
+    Ok(rpc)
+}
+
+#[cfg(test)]
+mod tests {
+    use citrea_evm::{Filter, FilterBlockOption};
+    use reth_primitives::{BlockNumberOrTag, B256};
+
+    use super::block_range_since;
+
+    fn filter(from_block: Option<u64>, to_block: Option<u64>) -> Filter {
+        Filter {
+            block_option: FilterBlockOption::Range {
+                from_block: from_block.map(BlockNumberOrTag::Number),
+                to_block: to_block.map(BlockNumberOrTag::Number),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_block_range_since() {
+        assert_eq!(block_range_since(&filter(None, None), 5, 8), Some((5, 8)));
+        // No new blocks since the last poll
+        assert_eq!(block_range_since(&filter(None, None), 9, 8), None);
+        assert_eq!(
+            block_range_since(&filter(Some(7), None), 5, 8),
+            Some((7, 8))
+        );
+        assert_eq!(
+            block_range_since(&filter(None, Some(6)), 5, 8),
+            Some((5, 6))
+        );
+        assert_eq!(block_range_since(&filter(None, Some(4)), 5, 8), None);
+
+        let at_block_hash = Filter {
+            block_option: FilterBlockOption::AtBlockHash(B256::ZERO),
+            ..Default::default()
+        };
+        assert_eq!(block_range_since(&at_block_hash, 5, 8), None);
+    }
+}
@@ -1,32 +1,45 @@
+mod block_tags;
+mod filter;
 mod gas_price;
+mod subscription;
+mod trace;
 
 use std::collections::BTreeMap;
 use std::process::Command;
 use std::sync::Mutex;
 
+pub use block_tags::resolve_block_tags;
 #[cfg(feature = "local")]
 pub use citrea_evm::DevSigner;
-use citrea_evm::Evm;
+use citrea_evm::{Evm, EvmProof, ProofOf, RootOf};
+pub use filter::{create_filter_rpc_module, FilterChanges, FilterConfig, FilterManager};
 pub use gas_price::fee_history::FeeHistoryCacheConfig;
 use gas_price::gas_oracle::GasPriceOracle;
 pub use gas_price::gas_oracle::GasPriceOracleConfig;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
-use reth_primitives::{keccak256, BlockNumberOrTag, Bytes, B256, U256};
+use reth_primitives::{keccak256, Address, BlockId, BlockNumberOrTag, Bytes, B256, U256};
 use reth_rpc::eth::error::EthApiError;
 use reth_rpc_types::trace::geth::{
     CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerConfig,
-    GethDebugTracerType, GethDebugTracingOptions, GethTrace, NoopFrame,
+    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
+    NoopFrame,
 };
-use reth_rpc_types::{FeeHistory, Index};
+use reth_rpc_types::trace::parity::LocalizedTransactionTrace;
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::{Bundle, FeeHistory, Index, StateContext, TransactionRequest};
 use rustc_version_runtime::version;
 use schnellru::{ByLength, LruMap};
 use sequencer_client::SequencerClient;
 use serde_json::json;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_modules_api::WorkingSet;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::CITREA_VERSION;
+use sov_state::storage::NativeStorage;
+pub use subscription::{create_subscription_rpc_module, SubscriptionManager};
+use tokio::sync::broadcast;
 use tracing::{info, instrument};
 
 const MAX_TRACE_BLOCK: u32 = 1000;
@@ -35,6 +48,7 @@ const MAX_TRACE_BLOCK: u32 = 1000;
 pub struct EthRpcConfig {
     pub gas_price_oracle_config: GasPriceOracleConfig,
     pub fee_history_cache_config: FeeHistoryCacheConfig,
+    pub filter_config: FilterConfig,
     #[cfg(feature = "local")]
     pub eth_signer: DevSigner,
 }
@@ -51,22 +65,35 @@ pub enum CitreaStatus {
     Syncing(SyncStatus),
 }
 
-pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
+pub fn get_ethereum_rpc<C, Da, L>(
     da_service: Da,
     eth_rpc_config: EthRpcConfig,
     storage: C::Storage,
-    sequencer_client_url: Option<String>,
-) -> RpcModule<Ethereum<C, Da>> {
+    ledger: L,
+    soft_batch_rx: broadcast::Receiver<u64>,
+    sequencer_client_urls: Option<Vec<String>>,
+) -> RpcModule<Ethereum<C, Da>>
+where
+    C: sov_modules_api::Context,
+    C::Storage: NativeStorage,
+    Da: DaService,
+    L: LedgerRpcProvider + Send + Sync + 'static,
+{
     // Unpack config
     let EthRpcConfig {
         #[cfg(feature = "local")]
         eth_signer,
         gas_price_oracle_config,
         fee_history_cache_config,
+        filter_config,
     } = eth_rpc_config;
 
     // If the node does not have a sequencer client, then it is the sequencer.
-    let is_sequencer = sequencer_client_url.is_none();
+    let is_sequencer = sequencer_client_urls.is_none();
+
+    // The sequencer registers its own subscriptions and filters, which are fed by its mempool
+    let subscription_manager =
+        (!is_sequencer).then(|| SubscriptionManager::new::<C>(storage.clone(), soft_batch_rx));
 
     // If the running node is a full node rpc context should also have sequencer client so that it can send txs to sequencer
     let mut rpc = RpcModule::new(Ethereum::new(
@@ -75,14 +102,39 @@ pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
         fee_history_cache_config,
         #[cfg(feature = "local")]
         eth_signer,
-        storage,
-        sequencer_client_url.map(SequencerClient::new),
+        storage.clone(),
+        sequencer_client_urls.map(SequencerClient::new),
+        subscription_manager.clone(),
     ));
 
     register_rpc_methods(&mut rpc, is_sequencer).expect("Failed to register ethereum RPC methods");
+    trace::register_trace_methods(&mut rpc).expect("Failed to register trace RPC methods");
+    if let Some(subscription_manager) = subscription_manager {
+        let filter_manager =
+            FilterManager::<C>::new(storage, subscription_manager.clone(), filter_config);
+        rpc.merge(
+            create_filter_rpc_module(filter_manager, ledger)
+                .expect("Failed to register ethereum filters"),
+        )
+        .expect("Failed to merge ethereum filters");
+        rpc.merge(
+            create_subscription_rpc_module(subscription_manager)
+                .expect("Failed to register ethereum subscriptions"),
+        )
+        .expect("Failed to merge ethereum subscriptions");
+    }
     rpc
 }
 
+/// Traces of a block that are cached for the `debug_` and `trace_` methods
+#[derive(Clone, Default)]
+struct BlockTraces {
+    /// Traces made with the options of `create_trace_cache_opts`
+    call_traces: Option<Vec<GethTrace>>,
+    /// Traces made with the parity tracer
+    parity_traces: Option<Vec<LocalizedTransactionTrace>>,
+}
+
 pub struct Ethereum<C: sov_modules_api::Context, Da: DaService> {
     #[allow(dead_code)]
     da_service: Da,
@@ -91,8 +143,9 @@ pub struct Ethereum<C: sov_modules_api::Context, Da: DaService> {
     eth_signer: DevSigner,
     storage: C::Storage,
     sequencer_client: Option<SequencerClient>,
+    subscription_manager: Option<SubscriptionManager>,
     web3_client_version: String,
-    trace_cache: Mutex<LruMap<u64, Vec<GethTrace>, ByLength>>,
+    trace_cache: Mutex<LruMap<u64, BlockTraces, ByLength>>,
 }
 
 impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
@@ -103,6 +156,7 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
         #[cfg(feature = "local")] eth_signer: DevSigner,
         storage: C::Storage,
         sequencer_client: Option<SequencerClient>,
+        subscription_manager: Option<SubscriptionManager>,
     ) -> Self {
         let evm = Evm::<C>::default();
         let gas_price_oracle =
@@ -123,6 +177,7 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
             eth_signer,
             storage,
             sequencer_client,
+            subscription_manager,
             web3_client_version: current_version,
             trace_cache,
         }
@@ -149,6 +204,44 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
 
         (U256::from(base_fee), U256::from(suggested_tip))
     }
+
+    fn cached_call_traces(&self, block_number: u64) -> Option<Vec<GethTrace>> {
+        self.trace_cache
+            .lock()
+            .unwrap()
+            .get(&block_number)
+            .and_then(|traces| traces.call_traces.clone())
+    }
+
+    fn cache_call_traces(&self, block_number: u64, traces: Vec<GethTrace>) {
+        if let Some(block_traces) = self
+            .trace_cache
+            .lock()
+            .unwrap()
+            .get_or_insert(block_number, BlockTraces::default)
+        {
+            block_traces.call_traces = Some(traces);
+        }
+    }
+
+    fn cached_parity_traces(&self, block_number: u64) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.trace_cache
+            .lock()
+            .unwrap()
+            .get(&block_number)
+            .and_then(|traces| traces.parity_traces.clone())
+    }
+
+    fn cache_parity_traces(&self, block_number: u64, traces: Vec<LocalizedTransactionTrace>) {
+        if let Some(block_traces) = self
+            .trace_cache
+            .lock()
+            .unwrap()
+            .get_or_insert(block_number, BlockTraces::default)
+        {
+            block_traces.parity_traces = Some(traces);
+        }
+    }
 }
 
 // impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
@@ -171,7 +264,10 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
     rpc: &mut RpcModule<Ethereum<C, Da>>,
     // Checks wether the running node is a sequencer or not, if it is not a sequencer it should also have methods like eth_sendRawTransaction here.
     is_sequencer: bool,
-) -> Result<(), jsonrpsee::core::RegisterMethodError> {
+) -> Result<(), jsonrpsee::core::RegisterMethodError>
+where
+    C::Storage: NativeStorage,
+{
     rpc.register_async_method("web3_clientVersion", |_, ethereum| async move {
         info!("eth module: web3_clientVersion");
 
@@ -254,6 +350,22 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         Ok::<FeeHistory, ErrorObjectOwned>(fee_history)
     })?;
 
+    rpc.register_async_method("eth_getProof", |params, ethereum| async move {
+        info!("eth module: eth_getProof");
+        let mut params = params.sequence();
+
+        let address: Address = params.next()?;
+        let keys: Vec<U256> = params.next()?;
+        let block_id: Option<BlockId> = params.optional_next()?;
+
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+
+        let proof = evm.get_proof(address, keys, block_id, &mut working_set)?;
+
+        Ok::<EvmProof<ProofOf<C>, RootOf<C>>, ErrorObjectOwned>(proof)
+    })?;
+
     #[cfg(feature = "local")]
     rpc.register_async_method("eth_accounts", |_, ethereum| async move {
         info!("eth module: eth_accounts");
@@ -307,7 +419,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
 
     //         // estimate gas limit
     //         let gas_limit = U256::from(
-    //             evm.eth_estimate_gas(call_request, None, &mut working_set)?
+    //             evm.eth_estimate_gas(call_request, None, None, None, &mut working_set)?
     //                 .to::<u64>(),
     //         );
 
@@ -515,7 +627,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 );
             }
 
-            if let Some(traces) = ethereum.trace_cache.lock().unwrap().get(&block_number) {
+            if let Some(traces) = ethereum.cached_call_traces(block_number) {
                 // If traces are found in cache convert them to specified opts and then return
                 let requested_opts = opts.clone().unwrap();
                 let traces = get_traces_with_reuqested_tracer_and_config(
@@ -532,11 +644,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 None,
                 &mut working_set,
             )?;
-            ethereum
-                .trace_cache
-                .lock()
-                .unwrap()
-                .insert(block_number, traces.clone());
+            ethereum.cache_call_traces(block_number, traces.clone());
             // Convert the traces to the requested tracer and config
             let requested_opts = opts.clone().unwrap();
             let tracer_config = requested_opts.tracer_config;
@@ -573,7 +681,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 return evm.trace_block_transactions_by_number(block_number, opts.clone(), None, &mut working_set);
             }
 
-            if let Some(traces) = ethereum.trace_cache.lock().unwrap().get(&block_number) {
+            if let Some(traces) = ethereum.cached_call_traces(block_number) {
                 // If traces are found in cache convert them to specified opts and then return
                 let requested_opts = opts.clone().unwrap();
                 let tracer_config = requested_opts.tracer_config;
@@ -589,11 +697,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                     None,
                     &mut working_set,
                 )?;
-                ethereum
-                    .trace_cache
-                    .lock()
-                    .unwrap()
-                    .insert(block_number, traces.clone());
+                ethereum.cache_call_traces(block_number, traces.clone());
 
             // Convert the traces to the requested tracer and config
             let requested_opts = opts.clone().unwrap();
@@ -649,7 +753,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
             }
 
             // check cache if found convert to requested tracer and config and return
-            if let Some(traces) = ethereum.trace_cache.lock().unwrap().get(&block_number) {
+            if let Some(traces) = ethereum.cached_call_traces(block_number) {
                 let requested_opts = opts.clone().unwrap();
                 let tracer_config = requested_opts.tracer_config;
                 let traces = get_traces_with_reuqested_tracer_and_config(
@@ -667,11 +771,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 None,
                 &mut working_set,
             )?;
-            ethereum
-                .trace_cache
-                .lock()
-                .unwrap()
-                .insert(block_number, traces.clone());
+            ethereum.cache_call_traces(block_number, traces.clone());
             // Convert the traces to the requested tracer and config
             let requested_opts = opts.clone().unwrap();
             let tracer_config = requested_opts.tracer_config;
@@ -685,17 +785,58 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         },
     )?;
 
-    rpc.register_async_method("txpool_content", |_, _| async move {
-        info!("eth module: txpool_content");
+    rpc.register_async_method::<Result<GethTrace, ErrorObjectOwned>, _, _>(
+        "debug_traceCall",
+        |parameters, ethereum| async move {
+            info!("eth module: debug_traceCall");
 
-        // This is a simple mock for serde.
-        let json = json!({
-            "pending": {},
-            "queued": {}
-        });
+            let mut params = parameters.sequence();
 
-        Ok::<_, ErrorObjectOwned>(json)
-    })?;
+            let request: TransactionRequest = params.next()?;
+            let block_id: Option<BlockId> = params.optional_next()?;
+            let opts: Option<GethDebugTracingCallOptions> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+
+            let (opts, requested_opts) = with_trace_cache_opts(opts);
+            let trace = evm.trace_call(request, block_id, Some(opts), &mut working_set)?;
+
+            Ok(get_trace_with_requested_opts(trace, &requested_opts)?)
+        },
+    )?;
+
+    rpc.register_async_method::<Result<Vec<Vec<GethTrace>>, ErrorObjectOwned>, _, _>(
+        "debug_traceCallMany",
+        |parameters, ethereum| async move {
+            info!("eth module: debug_traceCallMany");
+
+            let mut params = parameters.sequence();
+
+            let bundles: Vec<Bundle> = params.next()?;
+            let state_context: Option<StateContext> = params.optional_next()?;
+            let opts: Option<GethDebugTracingCallOptions> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+
+            let (opts, requested_opts) = with_trace_cache_opts(opts);
+            let traces =
+                evm.trace_call_many(bundles, state_context, Some(opts), &mut working_set)?;
+
+            let traces = traces
+                .into_iter()
+                .map(|bundle_traces| {
+                    bundle_traces
+                        .into_iter()
+                        .map(|trace| get_trace_with_requested_opts(trace, &requested_opts))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(traces)
+        },
+    )?;
 
     rpc.register_async_method(
         "eth_getUncleByBlockHashAndIndex",
@@ -729,7 +870,13 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                     .await;
 
                 match tx_hash {
-                    Ok(tx_hash) => Ok(tx_hash),
+                    Ok(tx_hash) => {
+                        // Only the transactions sent through this node are known to its subscribers
+                        if let Some(subscription_manager) = &ethereum.subscription_manager {
+                            subscription_manager.publish_pending_transaction(tx_hash);
+                        }
+                        Ok(tx_hash)
+                    }
                     Err(e) => match e {
                         jsonrpsee::core::client::Error::Call(e_owned) => Err(e_owned),
                         _ => Err(to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e)),
@@ -802,6 +949,67 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
             },
         )?;
 
+        rpc.register_async_method::<Result<TxpoolContent, ErrorObjectOwned>, _, _>(
+            "txpool_content",
+            |_, ethereum| async move {
+                info!("Full Node: txpool_content");
+
+                ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_content()
+                    .await
+                    .map_err(sequencer_client_error)
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolContentFrom, ErrorObjectOwned>, _, _>(
+            "txpool_contentFrom",
+            |parameters, ethereum| async move {
+                let from: Address = parameters.one()?;
+                info!("Full Node: txpool_contentFrom({})", from);
+
+                ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_content_from(from)
+                    .await
+                    .map_err(sequencer_client_error)
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolInspect, ErrorObjectOwned>, _, _>(
+            "txpool_inspect",
+            |_, ethereum| async move {
+                info!("Full Node: txpool_inspect");
+
+                ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_inspect()
+                    .await
+                    .map_err(sequencer_client_error)
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolStatus, ErrorObjectOwned>, _, _>(
+            "txpool_status",
+            |_, ethereum| async move {
+                info!("Full Node: txpool_status");
+
+                ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_status()
+                    .await
+                    .map_err(sequencer_client_error)
+            },
+        )?;
+
         rpc.register_async_method::<Result<CitreaStatus, ErrorObjectOwned>, _, _>(
             "citrea_syncStatus",
             |_, ethereum| async move {
@@ -850,6 +1058,13 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
     Ok(())
 }
 
+fn sequencer_client_error(e: jsonrpsee::core::client::Error) -> ErrorObjectOwned {
+    match e {
+        jsonrpsee::core::client::Error::Call(e_owned) => e_owned,
+        _ => to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e),
+    }
+}
+
 // fn get_call_request_and_params(
 //     from: Address,
 //     chain_id: u64,
@@ -1000,6 +1215,41 @@ fn get_traces_with_reuqested_tracer_and_config(
     }
 }
 
+/// Replaces the requested tracer of the call options with the one of the trace cache,
+/// so that the traces can be converted to any tracer supported by
+/// `get_traces_with_reuqested_tracer_and_config`.
+/// Returns the new options and the requested tracing options.
+fn with_trace_cache_opts(
+    opts: Option<GethDebugTracingCallOptions>,
+) -> (GethDebugTracingCallOptions, GethDebugTracingOptions) {
+    let mut opts = opts.unwrap_or_default();
+    let requested_opts = opts.tracing_options.clone();
+    // The default struct logger can not be converted from the cached call traces
+    if requested_opts.tracer.is_some() {
+        opts.tracing_options = create_trace_cache_opts();
+    }
+    (opts, requested_opts)
+}
+
+/// Converts a trace made with the options of `with_trace_cache_opts` to the requested tracer and config
+fn get_trace_with_requested_opts(
+    trace: GethTrace,
+    requested_opts: &GethDebugTracingOptions,
+) -> Result<GethTrace, EthApiError> {
+    let Some(tracer) = requested_opts.tracer.clone() else {
+        return Ok(trace);
+    };
+    let traces = get_traces_with_reuqested_tracer_and_config(
+        vec![trace],
+        tracer,
+        requested_opts.tracer_config.clone(),
+    )?;
+    Ok(traces
+        .into_iter()
+        .next()
+        .expect("A trace must be converted to the requested tracer"))
+}
+
 pub fn convert_call_trace_into_4byte_frame(call_frames: Vec<CallFrame>) -> FourByteFrame {
     FourByteFrame(convert_call_trace_into_4byte_map(
         call_frames,
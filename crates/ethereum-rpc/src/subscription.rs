@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use citrea_evm::{Evm, Filter, FilterBlockOption, LogResponse};
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use reth_primitives::{BlockNumberOrTag, B256};
+use serde::Serialize;
+use sov_modules_api::WorkingSet;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Number of notifications that are buffered for slow subscribers
+const SUBSCRIPTION_CHANNEL_SIZE: usize = 1000;
+
+/// Publishes the notifications of `eth_subscribe`.
+///
+/// New heads and logs are published when a soft batch is committed to the ledger,
+/// pending transactions when they are accepted by the sequencer.
+#[derive(Clone)]
+pub struct SubscriptionManager {
+    new_heads_tx: broadcast::Sender<reth_rpc_types::Header>,
+    logs_tx: broadcast::Sender<Arc<Vec<LogResponse>>>,
+    pending_transactions_tx: broadcast::Sender<B256>,
+}
+
+impl SubscriptionManager {
+    /// Spawns a task that publishes the blocks and logs of every soft batch
+    /// whose number is received from `soft_batch_rx`.
+    pub fn new<C: sov_modules_api::Context>(
+        storage: C::Storage,
+        mut soft_batch_rx: broadcast::Receiver<u64>,
+    ) -> Self {
+        let new_heads_tx = broadcast::channel(SUBSCRIPTION_CHANNEL_SIZE).0;
+        let logs_tx = broadcast::channel(SUBSCRIPTION_CHANNEL_SIZE).0;
+        let pending_transactions_tx = broadcast::channel(SUBSCRIPTION_CHANNEL_SIZE).0;
+
+        let manager = Self {
+            new_heads_tx,
+            logs_tx,
+            pending_transactions_tx,
+        };
+
+        let publisher = manager.clone();
+        tokio::spawn(async move {
+            let evm = Evm::<C>::default();
+            let mut next_block =
+                latest_block_number(&evm, &mut WorkingSet::<C>::new(storage.clone())) + 1;
+            loop {
+                match soft_batch_rx.recv().await {
+                    // Skipped notifications are caught up on by publishing all blocks since the last one
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+                next_block = publisher.publish_blocks(&evm, storage.clone(), next_block);
+            }
+        });
+
+        manager
+    }
+
+    /// Publishes the hash of a transaction that is ready for inclusion
+    pub fn publish_pending_transaction(&self, hash: B256) {
+        // Sending only fails if there are no subscribers
+        let _ = self.pending_transactions_tx.send(hash);
+    }
+
+    /// Returns a receiver for the hashes of the transactions that are ready for inclusion
+    pub(crate) fn subscribe_pending_transactions(&self) -> broadcast::Receiver<B256> {
+        self.pending_transactions_tx.subscribe()
+    }
+
+    /// Publishes the blocks from `from` up to the latest one, and returns the number of the next block
+    fn publish_blocks<C: sov_modules_api::Context>(
+        &self,
+        evm: &Evm<C>,
+        storage: C::Storage,
+        from: u64,
+    ) -> u64 {
+        let mut working_set = WorkingSet::<C>::new(storage);
+        let latest = latest_block_number(evm, &mut working_set);
+
+        if self.new_heads_tx.receiver_count() == 0 && self.logs_tx.receiver_count() == 0 {
+            return latest + 1;
+        }
+
+        for number in from..=latest {
+            match evm.get_block_by_number(
+                Some(BlockNumberOrTag::Number(number)),
+                None,
+                &mut working_set,
+            ) {
+                Ok(Some(block)) => {
+                    let _ = self.new_heads_tx.send(block.inner.header);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Failed to get block {} for subscribers: {:?}", number, e);
+                    break;
+                }
+            }
+
+            let filter = Filter {
+                block_option: FilterBlockOption::Range {
+                    from_block: Some(BlockNumberOrTag::Number(number)),
+                    to_block: Some(BlockNumberOrTag::Number(number)),
+                },
+                ..Default::default()
+            };
+            match evm.eth_get_logs(filter, &mut working_set) {
+                Ok(logs) if logs.is_empty() => {}
+                Ok(logs) => {
+                    let _ = self.logs_tx.send(Arc::new(logs));
+                }
+                Err(e) => warn!(
+                    "Failed to get logs of block {} for subscribers: {:?}",
+                    number, e
+                ),
+            }
+        }
+
+        latest + 1
+    }
+}
+
+pub(crate) fn latest_block_number<C: sov_modules_api::Context>(
+    evm: &Evm<C>,
+    working_set: &mut WorkingSet<C>,
+) -> u64 {
+    evm.block_number(working_set)
+        .map(|number| number.saturating_to::<u64>())
+        .unwrap_or_default()
+}
+
+/// Creates the module with `eth_subscribe` and `eth_unsubscribe`
+pub fn create_subscription_rpc_module(
+    manager: SubscriptionManager,
+) -> Result<RpcModule<SubscriptionManager>, jsonrpsee::core::RegisterMethodError> {
+    let mut rpc = RpcModule::new(manager);
+
+    rpc.register_subscription(
+        "eth_subscribe",
+        "eth_subscription",
+        "eth_unsubscribe",
+        |parameters, pending, manager| async move {
+            let mut params = parameters.sequence();
+            let kind: String = match params.next() {
+                Ok(kind) => kind,
+                Err(e) => {
+                    pending.reject(e).await;
+                    return;
+                }
+            };
+            info!("eth module: eth_subscribe({})", kind);
+
+            match kind.as_str() {
+                "newHeads" => {
+                    let rx = manager.new_heads_tx.subscribe();
+                    forward(pending, rx, |header| vec![header]).await;
+                }
+                "logs" => {
+                    let filter: Filter = match params.optional_next() {
+                        Ok(filter) => filter.unwrap_or_default(),
+                        Err(e) => {
+                            pending.reject(e).await;
+                            return;
+                        }
+                    };
+                    let rx = manager.logs_tx.subscribe();
+                    forward(pending, rx, move |logs: Arc<Vec<LogResponse>>| {
+                        logs.iter()
+                            .filter(|log| log_matches(&filter, log))
+                            .cloned()
+                            .collect()
+                    })
+                    .await;
+                }
+                "newPendingTransactions" => {
+                    let rx = manager.pending_transactions_tx.subscribe();
+                    forward(pending, rx, |hash| vec![hash]).await;
+                }
+                _ => {
+                    pending
+                        .reject(ErrorObjectOwned::owned(
+                            INVALID_PARAMS_CODE,
+                            "Unsupported subscription",
+                            Some(kind),
+                        ))
+                        .await;
+                }
+            }
+        },
+    )?;
+
+    Ok(rpc)
+}
+
+/// Sends the notifications built by `to_notifications` from the received events
+/// until the subscription is closed
+async fn forward<T, N, F>(
+    pending: PendingSubscriptionSink,
+    mut rx: broadcast::Receiver<T>,
+    to_notifications: F,
+) where
+    T: Clone,
+    N: Serialize,
+    F: Fn(T) -> Vec<N>,
+{
+    let Ok(sink) = pending.accept().await else {
+        return;
+    };
+
+    loop {
+        let event = tokio::select! {
+            _ = sink.closed() => return,
+            event = rx.recv() => event,
+        };
+        let event = match event {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(
+                    "Subscription {:?} skipped {} notifications",
+                    sink.subscription_id(),
+                    skipped
+                );
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        for notification in to_notifications(event) {
+            let Ok(msg) = SubscriptionMessage::from_json(&notification) else {
+                return;
+            };
+            // Sending only fails if the subscriber has canceled, so we can stop sending messages
+            if sink.send(msg).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Whether the log matches the address and topics of the filter.
+/// The block range of the filter is not used, logs are sent as their blocks are published.
+fn log_matches(filter: &Filter, log: &LogResponse) -> bool {
+    filter.address.matches(&log.address)
+        && filter.topics.iter().enumerate().all(|(index, topic)| {
+            topic.is_empty()
+                || log
+                    .topics
+                    .get(index)
+                    .is_some_and(|log_topic| topic.matches(log_topic))
+        })
+}
@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+
+use citrea_evm::Evm;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use reth_primitives::{BlockNumberOrTag, B256};
+use reth_rpc::eth::error::EthApiError;
+use reth_rpc_types::trace::filter::{TraceFilter, TraceFilterMode};
+use reth_rpc_types::trace::parity::{
+    Action, LocalizedTransactionTrace, TraceOutput, TraceResultsWithTransactionHash, TraceType,
+    TransactionTrace,
+};
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::services::da::DaService;
+use tracing::info;
+
+use crate::Ethereum;
+
+/// Max number of blocks `trace_filter` can trace in a single query
+const MAX_TRACE_FILTER_BLOCK_RANGE: u64 = 100;
+
+impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
+    /// Returns the parity traces of the block, from the trace cache if they were traced before
+    fn parity_traces(&self, block_number: u64) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        if let Some(traces) = self.cached_parity_traces(block_number) {
+            return Ok(traces);
+        }
+
+        // Tracing sets the archival version of the working set, so every block needs a new one
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+        let traces = evm.trace_block_parity(block_number, &mut working_set)?;
+        self.cache_parity_traces(block_number, traces.clone());
+
+        Ok(traces)
+    }
+
+    fn resolve_block_number(&self, block_number: BlockNumberOrTag) -> RpcResult<u64> {
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+        match block_number {
+            BlockNumberOrTag::Number(block_number) => Ok(block_number),
+            BlockNumberOrTag::Earliest => Ok(0),
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+                Ok(evm.block_number(&mut working_set)?.saturating_to())
+            }
+            _ => Err(EthApiError::Unsupported(
+                "Safe and finalized are not supported for trace methods",
+            )
+            .into()),
+        }
+    }
+}
+
+pub(crate) fn register_trace_methods<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::RegisterMethodError> {
+    rpc.register_async_method::<Result<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>, _, _>(
+        "trace_block",
+        |parameters, ethereum| async move {
+            info!("eth module: trace_block");
+
+            let block_number: BlockNumberOrTag = parameters.one()?;
+            let block_number = ethereum.resolve_block_number(block_number)?;
+
+            ethereum.parity_traces(block_number)
+        },
+    )?;
+
+    rpc.register_async_method::<Result<Option<Vec<LocalizedTransactionTrace>>, ErrorObjectOwned>, _, _>(
+        "trace_transaction",
+        |parameters, ethereum| async move {
+            info!("eth module: trace_transaction");
+
+            let tx_hash: B256 = parameters.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+            let Some(tx) = evm.get_transaction_by_hash(tx_hash, &mut working_set)? else {
+                return Ok(None);
+            };
+            let block_number = tx
+                .block_number
+                .expect("Block number must be set for tx inside block");
+
+            let traces = ethereum
+                .parity_traces(block_number)?
+                .into_iter()
+                .filter(|trace| trace.transaction_hash == Some(tx_hash))
+                .collect();
+
+            Ok(Some(traces))
+        },
+    )?;
+
+    rpc.register_async_method::<Result<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>, _, _>(
+        "trace_filter",
+        |parameters, ethereum| async move {
+            info!("eth module: trace_filter");
+
+            let filter: TraceFilter = parameters.one()?;
+
+            let latest_block = ethereum.resolve_block_number(BlockNumberOrTag::Latest)?;
+            let from_block = filter.from_block.unwrap_or_default();
+            let to_block = filter.to_block.unwrap_or(latest_block).min(latest_block);
+            if from_block > to_block {
+                return Err(EthApiError::InvalidBlockRange.into());
+            }
+            if to_block - from_block >= MAX_TRACE_FILTER_BLOCK_RANGE {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Block range is too large, the max is {} blocks",
+                    MAX_TRACE_FILTER_BLOCK_RANGE
+                ))
+                .into());
+            }
+
+            let mut traces = vec![];
+            for block_number in from_block..=to_block {
+                traces.extend(
+                    ethereum
+                        .parity_traces(block_number)?
+                        .into_iter()
+                        .filter(|trace| trace_matches(&filter, &trace.trace)),
+                );
+            }
+
+            let traces = traces
+                .into_iter()
+                .skip(filter.after.unwrap_or_default() as usize)
+                .take(filter.count.map_or(usize::MAX, |count| count as usize))
+                .collect();
+
+            Ok(traces)
+        },
+    )?;
+
+    rpc.register_async_method::<Result<Vec<TraceResultsWithTransactionHash>, ErrorObjectOwned>, _, _>(
+        "trace_replayBlockTransactions",
+        |parameters, ethereum| async move {
+            info!("eth module: trace_replayBlockTransactions");
+
+            let mut params = parameters.sequence();
+
+            let block_number: BlockNumberOrTag = params.next()?;
+            let trace_types: HashSet<TraceType> = params.next()?;
+            let block_number = ethereum.resolve_block_number(block_number)?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+
+            evm.trace_replay_block_transactions(block_number, trace_types, &mut working_set)
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Whether the sender or the receiver of the trace is in the addresses of the filter.
+/// An empty address list matches all traces.
+fn trace_matches(filter: &TraceFilter, trace: &TransactionTrace) -> bool {
+    let (from, to) = match &trace.action {
+        Action::Call(call) => (Some(call.from), Some(call.to)),
+        Action::Create(create) => (
+            Some(create.from),
+            match &trace.result {
+                Some(TraceOutput::Create(output)) => Some(output.address),
+                _ => None,
+            },
+        ),
+        Action::Selfdestruct(selfdestruct) => (
+            Some(selfdestruct.address),
+            Some(selfdestruct.refund_address),
+        ),
+        Action::Reward(reward) => (None, Some(reward.author)),
+    };
+
+    let from_matches = from.is_some_and(|from| filter.from_address.contains(&from));
+    let to_matches = to.is_some_and(|to| filter.to_address.contains(&to));
+
+    match (filter.from_address.is_empty(), filter.to_address.is_empty()) {
+        (true, true) => true,
+        (false, true) => from_matches,
+        (true, false) => to_matches,
+        (false, false) => match filter.mode {
+            TraceFilterMode::Union => from_matches || to_matches,
+            TraceFilterMode::Intersection => from_matches && to_matches,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::{Address, Bytes, U256};
+    use reth_rpc_types::trace::filter::{TraceFilter, TraceFilterMode};
+    use reth_rpc_types::trace::parity::{
+        Action, CallAction, CallOutput, CallType, TraceOutput, TransactionTrace,
+    };
+
+    use super::trace_matches;
+
+    fn call_trace(from: Address, to: Address) -> TransactionTrace {
+        TransactionTrace {
+            action: Action::Call(CallAction {
+                from,
+                call_type: CallType::Call,
+                gas: Default::default(),
+                input: Bytes::default(),
+                to,
+                value: U256::from(1),
+            }),
+            error: None,
+            result: Some(TraceOutput::Call(CallOutput {
+                gas_used: Default::default(),
+                output: Bytes::default(),
+            })),
+            subtraces: 0,
+            trace_address: vec![],
+        }
+    }
+
+    #[test]
+    fn test_trace_matches() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let c = Address::with_last_byte(3);
+        let trace = call_trace(a, b);
+
+        assert!(trace_matches(&TraceFilter::default(), &trace));
+
+        let from_a = TraceFilter {
+            from_address: vec![a],
+            ..Default::default()
+        };
+        assert!(trace_matches(&from_a, &trace));
+        assert!(!trace_matches(&from_a, &call_trace(c, b)));
+
+        let to_c = TraceFilter {
+            to_address: vec![c],
+            ..Default::default()
+        };
+        assert!(!trace_matches(&to_c, &trace));
+
+        let union = TraceFilter {
+            from_address: vec![a],
+            to_address: vec![c],
+            mode: TraceFilterMode::Union,
+            ..Default::default()
+        };
+        assert!(trace_matches(&union, &trace));
+
+        let intersection = TraceFilter {
+            mode: TraceFilterMode::Intersection,
+            ..union
+        };
+        assert!(!trace_matches(&intersection, &trace));
+        assert!(trace_matches(&intersection, &call_trace(a, c)));
+    }
+}
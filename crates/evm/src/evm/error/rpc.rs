@@ -9,7 +9,7 @@ use super::pool::{
     Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
     PoolTransactionError,
 };
-use super::result::internal_rpc_err;
+use super::result::{internal_rpc_err, rpc_error_with_code};
 
 // /// Eth Optimism Api Error
 // #[cfg(feature = "optimism")]
@@ -223,3 +223,15 @@ pub(crate) fn ensure_success(result: ExecutionResult) -> EthResult<Bytes> {
         }
     }
 }
+
+/// Error code of queries for the state of a block the node no longer keeps,
+/// same as the pruned history error of reth.
+pub const STATE_PRUNED_ERROR_CODE: i32 = 4444;
+
+/// Returned when the state of the requested block is pruned.
+pub(crate) fn state_pruned_err(block_number: u64) -> ErrorObject<'static> {
+    rpc_error_with_code(
+        STATE_PRUNED_ERROR_CODE,
+        format!("State at block #{block_number} is pruned"),
+    )
+}
@@ -56,7 +56,7 @@ pub(crate) struct DbAccount {
 }
 
 impl DbAccount {
-    fn new(parent_prefix: &Prefix, address: Address) -> Self {
+    pub(crate) fn new(parent_prefix: &Prefix, address: Address) -> Self {
         let prefix = Self::create_storage_prefix(parent_prefix, address);
         Self {
             info: Default::default(),
@@ -78,7 +78,7 @@ impl DbAccount {
         }
     }
 
-    fn create_storage_prefix(parent_prefix: &Prefix, address: Address) -> Prefix {
+    pub(crate) fn create_storage_prefix(parent_prefix: &Prefix, address: Address) -> Prefix {
         let mut prefix = parent_prefix.as_aligned_vec().clone().into_inner();
         prefix.extend_from_slice(address.as_ref());
         Prefix::new(prefix)
@@ -1,5 +1,6 @@
-use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_primitives::{address, Address, Bytes, B256, U256};
 use alloy_sol_types::{sol, SolCall};
+use sha2::{Digest, Sha256};
 
 // BitcoinLightClient wrapper.
 sol! {
@@ -119,4 +120,28 @@ impl Bridge {
         func_selector.extend(params);
         func_selector.into()
     }
+
+    /// Return the Bitcoin wtxid of the deposit encoded in `params`, computed the same way as `Bridge.deposit` does.
+    /// Returns `None` if `params` cannot be decoded as deposit params.
+    pub fn deposit_wtxid(params: &[u8]) -> Option<B256> {
+        let BridgeContract::depositCall { p } =
+            BridgeContract::depositCall::abi_decode_raw(params, true).ok()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(p.version);
+        hasher.update(p.flag);
+        hasher.update(&p.vin);
+        hasher.update(&p.vout);
+        hasher.update(&p.witness);
+        hasher.update(p.locktime);
+
+        Some(B256::from_slice(&Sha256::digest(hasher.finalize())))
+    }
+
+    /// Return input data to query whether a deposit with the given wtxid was already processed
+    pub fn spent_wtx_ids(wtxid: B256) -> Bytes {
+        BridgeContract::spentWtxIdsCall { _0: wtxid }
+            .abi_encode()
+            .into()
+    }
 }
@@ -4,11 +4,13 @@ mod call;
 mod evm;
 mod genesis;
 mod hooks;
+mod proof;
 mod provider_functions;
 
 pub use call::*;
 pub use evm::*;
 pub use genesis::*;
+pub use proof::{verify_evm_proof, EvmProof, JmtProof, ProofOf, RootOf, StorageSlotProof};
 pub use system_events::SYSTEM_SIGNER;
 
 #[cfg(feature = "native")]
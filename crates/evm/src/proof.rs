@@ -0,0 +1,213 @@
+use anyhow::{bail, ensure};
+use reth_primitives::{Address, Bytes, B256, KECCAK_EMPTY, U256};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{Context, Spec};
+use sov_state::codec::BcsCodec;
+use sov_state::storage::{StateValueCodec, StorageKey, StorageProof, StorageValue};
+use sov_state::Storage;
+
+use crate::evm::DbAccount;
+use crate::Evm;
+
+/// Proof of a key in the state Jellyfish Merkle Tree.
+/// A proof without a value proves that the key is absent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JmtProof<P> {
+    /// Key of the value in the tree, before hashing
+    pub key: Bytes,
+    /// BCS encoded value
+    pub value: Option<Bytes>,
+    /// Inclusion or exclusion proof of the key
+    pub proof: P,
+}
+
+impl<P> JmtProof<P> {
+    fn from_storage_proof(storage_proof: StorageProof<P>) -> Self {
+        let StorageProof { key, value, proof } = storage_proof;
+        Self {
+            key: Bytes::from(key.as_ref().clone()),
+            value: value.map(|value| Bytes::copy_from_slice(value.value())),
+            proof,
+        }
+    }
+}
+
+/// Proof of a storage slot of an account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageSlotProof<P> {
+    /// The storage slot
+    pub key: U256,
+    /// Value of the slot, zero if the slot is not set
+    pub value: U256,
+    /// Proof of the slot
+    pub proof: JmtProof<P>,
+}
+
+/// Response of `eth_getProof`: an account and some of its storage slots at an L2 height,
+/// with their proofs against the state root of that height
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmProof<P, R> {
+    /// Address of the account
+    pub address: Address,
+    /// Balance of the account
+    pub balance: U256,
+    /// Nonce of the account
+    pub nonce: u64,
+    /// Hash of the code of the account
+    pub code_hash: B256,
+    /// L2 height of the state
+    pub l2_height: u64,
+    /// Post state root of the soft batch at `l2_height`
+    pub state_root: R,
+    /// Proof of the account
+    pub account_proof: JmtProof<P>,
+    /// Proofs of the requested storage slots
+    pub storage_proof: Vec<StorageSlotProof<P>>,
+}
+
+/// Proof type of the storage of the context
+pub type ProofOf<C> = <<C as Spec>::Storage as Storage>::Proof;
+/// Root type of the storage of the context
+pub type RootOf<C> = <<C as Spec>::Storage as Storage>::Root;
+
+impl<C: Context> Evm<C> {
+    /// Key of the account in the state tree
+    pub(crate) fn account_key(&self, address: &Address) -> StorageKey {
+        StorageKey::new(self.accounts.prefix(), address, &BcsCodec {})
+    }
+
+    /// Key of the storage slot of the account in the state tree
+    pub(crate) fn storage_slot_key(&self, address: Address, slot: &U256) -> StorageKey {
+        let prefix = DbAccount::create_storage_prefix(self.accounts.prefix(), address);
+        StorageKey::new(&prefix, slot, &BcsCodec {})
+    }
+
+    /// Proves the account at `address` and the given storage slots with the storage
+    /// proofs returned by `prove`
+    pub(crate) fn prove_account(
+        &self,
+        address: Address,
+        slots: Vec<U256>,
+        mut prove: impl FnMut(StorageKey) -> anyhow::Result<StorageProof<ProofOf<C>>>,
+    ) -> anyhow::Result<(
+        DbAccount,
+        JmtProof<ProofOf<C>>,
+        Vec<StorageSlotProof<ProofOf<C>>>,
+    )> {
+        let account_proof = prove(self.account_key(&address))?;
+        let account = match &account_proof.value {
+            Some(value) => decode_value::<DbAccount>(value.value())?,
+            None => DbAccount::new(self.accounts.prefix(), address),
+        };
+
+        let storage_proof = slots
+            .into_iter()
+            .map(|slot| {
+                let proof = prove(self.storage_slot_key(address, &slot))?;
+                let value = match &proof.value {
+                    Some(value) => decode_value::<U256>(value.value())?,
+                    None => U256::ZERO,
+                };
+                Ok(StorageSlotProof {
+                    key: slot,
+                    value,
+                    proof: JmtProof::from_storage_proof(proof),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok((
+            account,
+            JmtProof::from_storage_proof(account_proof),
+            storage_proof,
+        ))
+    }
+}
+
+/// Checks that the account and storage values of an `eth_getProof` response are proven
+/// against `state_root`, which should come from a trusted source like a verified batch proof.
+/// The state root in the response itself is not used.
+pub fn verify_evm_proof<C: Context>(
+    evm_proof: &EvmProof<ProofOf<C>, RootOf<C>>,
+    state_root: RootOf<C>,
+) -> anyhow::Result<()> {
+    let evm = Evm::<C>::default();
+
+    let account_value = open_proof::<C>(
+        evm.account_key(&evm_proof.address),
+        &evm_proof.account_proof,
+        state_root.clone(),
+    )?;
+    match account_value {
+        Some(value) => {
+            let account = decode_value::<DbAccount>(&value)?;
+            ensure!(
+                account.info.balance == evm_proof.balance
+                    && account.info.nonce == evm_proof.nonce
+                    && account.info.code_hash == evm_proof.code_hash,
+                "Account {} does not match its proof",
+                evm_proof.address
+            );
+        }
+        None => ensure!(
+            evm_proof.balance.is_zero()
+                && evm_proof.nonce == 0
+                && evm_proof.code_hash == KECCAK_EMPTY,
+            "Account {} is proven to be absent",
+            evm_proof.address
+        ),
+    }
+
+    for slot_proof in &evm_proof.storage_proof {
+        let slot_value = open_proof::<C>(
+            evm.storage_slot_key(evm_proof.address, &slot_proof.key),
+            &slot_proof.proof,
+            state_root.clone(),
+        )?;
+        let value = match slot_value {
+            Some(value) => decode_value::<U256>(&value)?,
+            None => U256::ZERO,
+        };
+        ensure!(
+            value == slot_proof.value,
+            "Storage slot {} of account {} does not match its proof",
+            slot_proof.key,
+            evm_proof.address
+        );
+    }
+
+    Ok(())
+}
+
+/// Opens the proof for `key` and returns the proven value
+fn open_proof<C: Context>(
+    key: StorageKey,
+    jmt_proof: &JmtProof<ProofOf<C>>,
+    state_root: RootOf<C>,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    if jmt_proof.key.as_ref() != key.as_ref().as_slice() {
+        bail!("Proof is not for key {:?}", key);
+    }
+
+    let storage_proof = StorageProof {
+        key,
+        value: jmt_proof
+            .value
+            .as_ref()
+            .map(|value| StorageValue::from(value.to_vec())),
+        proof: jmt_proof.proof.clone(),
+    };
+    let (_, value) = C::Storage::open_proof(state_root, storage_proof)?;
+
+    Ok(value.map(|value| value.value().to_vec()))
+}
+
+fn decode_value<V>(bytes: &[u8]) -> anyhow::Result<V>
+where
+    BcsCodec: StateValueCodec<V>,
+{
+    BcsCodec {}
+        .try_decode_value(bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to decode proven value: {:?}", e))
+}
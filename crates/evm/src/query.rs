@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::ops::{Range, RangeInclusive};
 
 use alloy_primitives::Uint;
@@ -9,16 +9,23 @@ use reth_primitives::constants::GWEI_TO_WEI;
 use reth_primitives::revm::env::tx_env_with_recovered;
 use reth_primitives::TxKind::{Call, Create};
 use reth_primitives::{
-    Block, BlockId, BlockNumberOrTag, SealedHeader, TransactionSignedEcRecovered, U256, U64,
+    Address, Block, BlockId, BlockNumberOrTag, Header, SealedHeader, TransactionSignedEcRecovered,
+    U256, U64,
 };
 use reth_rpc::eth::error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError};
 use reth_rpc_types::other::OtherFields;
-use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::geth::{
+    GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
+};
+use reth_rpc_types::trace::parity::{
+    LocalizedTransactionTrace, TraceResultsWithTransactionHash, TraceType,
+};
 use reth_rpc_types::{
-    AccessListWithGasUsed, AnyReceiptEnvelope, AnyTransactionReceipt, Log, ReceiptWithBloom,
-    TransactionReceipt,
+    AccessListWithGasUsed, AnyReceiptEnvelope, AnyTransactionReceipt, Bundle, Log,
+    ReceiptWithBloom, StateContext, TransactionIndex, TransactionReceipt,
 };
 use reth_rpc_types_compat::block::from_primitive_with_hash;
+use revm::db::CacheDB;
 use revm::primitives::{
     CfgEnvWithHandlerCfg, EVMError, ExecutionResult, HaltReason, InvalidTransaction, TransactTo,
     TxEnv, KECCAK_EMPTY,
@@ -30,16 +37,19 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
+use sov_state::storage::NativeStorage;
 use tracing::debug;
 
 use crate::call::get_cfg_env;
-use crate::error::rpc::{ensure_success, RpcInvalidTransactionErrorExt};
+use crate::error::rpc::{ensure_success, state_pruned_err, RpcInvalidTransactionErrorExt};
 use crate::evm::call::prepare_call_env;
-use crate::evm::db::EvmDb;
+use crate::evm::error::result::internal_rpc_err;
 use crate::evm::primitive_types::{BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered};
 use crate::handler::TxInfo;
 use crate::rpc_helpers::*;
-use crate::{BloomFilter, Evm, EvmChainConfig, FilterBlockOption, FilterError};
+use crate::{
+    BloomFilter, Evm, EvmChainConfig, EvmProof, FilterBlockOption, FilterError, ProofOf, RootOf,
+};
 
 /// Gas per transaction not creating a contract.
 pub const MIN_TRANSACTION_GAS: u64 = 21_000u64;
@@ -86,6 +96,34 @@ pub struct EstimatedDiffSize {
     pub diff_size: U64,
 }
 
+/// Fees paid by a transaction, returned by `citrea_getFeeBreakdown`.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Gas used by the transaction.
+    pub gas_used: U64,
+    /// Base fee per gas of the block.
+    pub base_fee_per_gas: U256,
+    /// Base fee paid, `base_fee_per_gas * gas_used`.
+    pub base_fee: U256,
+    /// Address the base fee is paid to.
+    pub base_fee_recipient: Address,
+    /// Priority fee per gas paid on top of the base fee.
+    pub priority_fee_per_gas: U256,
+    /// Priority fee paid, `priority_fee_per_gas * gas_used`.
+    pub priority_fee: U256,
+    /// Address the priority fee is paid to.
+    pub priority_fee_recipient: Address,
+    /// L1 fee rate of the block.
+    pub l1_fee_rate: U256,
+    /// Size of the state diff of the transaction.
+    pub l1_diff_size: U64,
+    /// L1 fee paid, `l1_fee_rate * l1_diff_size`.
+    pub l1_fee: U256,
+    /// Address the L1 fee is paid to.
+    pub l1_fee_recipient: Address,
+}
+
 #[rpc_gen(client, server)]
 impl<C: sov_modules_api::Context> Evm<C> {
     /// Handler for `net_version`
@@ -291,38 +329,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_balance(
         &self,
         address: reth_primitives::Address,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         debug!("evm module: eth_getBalance");
 
-        let curr_block_number = self
-            .blocks
-            .last(&mut working_set.accessory_state())
-            .expect("Head block must be set")
-            .header
-            .number;
-
-        // Specs from https://ethereum.org/en/developers/docs/apis/json-rpc
-        match block_number {
-            Some(BlockNumberOrTag::Number(num)) => {
-                if num > curr_block_number {
-                    return Err(EthApiError::UnknownBlockNumber.into());
-                }
-                set_state_to_end_of_evm_block(num, working_set);
-            }
-            // Working state here is already at the latest state, so no need to anything
-            Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) | None => {}
-            Some(BlockNumberOrTag::Earliest) => {
-                set_state_to_end_of_evm_block(0, working_set);
-            }
-            _ => {
-                return Err(EthApiError::InvalidParams(
-                    "Please provide a number or earliest/latest tag".to_string(),
-                )
-                .into())
-            }
-        }
+        self.set_state_to_block_id(block_id, working_set)?;
 
         let balance = self
             .accounts
@@ -339,38 +351,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         address: reth_primitives::Address,
         index: reth_primitives::U256,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::B256> {
         debug!("evm module: eth_getStorageAt");
 
-        let curr_block_number = self
-            .blocks
-            .last(&mut working_set.accessory_state())
-            .expect("Head block must be set")
-            .header
-            .number;
-
-        // Specs from https://ethereum.org/en/developers/docs/apis/json-rpc
-        match block_number {
-            Some(BlockNumberOrTag::Number(num)) => {
-                if num > curr_block_number {
-                    return Err(EthApiError::UnknownBlockNumber.into());
-                }
-                set_state_to_end_of_evm_block(num, working_set);
-            }
-            // Working state here is already at the latest state, so no need to anything
-            Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) | None => {}
-            Some(BlockNumberOrTag::Earliest) => {
-                set_state_to_end_of_evm_block(0, working_set);
-            }
-            _ => {
-                return Err(EthApiError::InvalidParams(
-                    "Please provide a number or earliest/latest tag".to_string(),
-                )
-                .into())
-            }
-        }
+        self.set_state_to_block_id(block_id, working_set)?;
 
         let storage_slot = self
             .accounts
@@ -381,43 +367,65 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(storage_slot.into())
     }
 
+    /// Handler for: `eth_getProof`
+    ///
+    /// Returns the account and the given storage slots at the end of the given block, with their
+    /// Jellyfish Merkle Tree proofs against the post state root of the block's soft batch.
+    /// Not an `rpc_method` since it needs a [`NativeStorage`], it is registered by the ethereum-rpc module.
+    pub fn get_proof(
+        &self,
+        address: reth_primitives::Address,
+        keys: Vec<reth_primitives::U256>,
+        block_id: Option<BlockId>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<EvmProof<ProofOf<C>, RootOf<C>>>
+    where
+        C::Storage: NativeStorage,
+    {
+        debug!("evm module: eth_getProof");
+
+        let block_number = match block_id {
+            Some(block_id) => self.block_number_or_tag_for_id(block_id, working_set)?,
+            None => BlockNumberOrTag::Latest,
+        };
+        let block_number = self.block_number_for_id(&block_number, working_set)?;
+        ensure_state_available(block_number, working_set)?;
+        // genesis is committed at db version 1
+        // so every block is offset by 1
+        let version = block_number + 1;
+
+        let state_root = working_set
+            .get_root_hash(version)
+            .map_err(|e| internal_rpc_err(format!("Failed to get state root: {e}")))?;
+        let (account, account_proof, storage_proof) = self
+            .prove_account(address, keys, |key| {
+                working_set.get_with_proof_at_version(key, version)
+            })
+            .map_err(|e| internal_rpc_err(format!("Failed to prove state: {e}")))?;
+
+        Ok(EvmProof {
+            address,
+            balance: account.info.balance,
+            nonce: account.info.nonce,
+            code_hash: account.info.code_hash,
+            l2_height: block_number,
+            state_root,
+            account_proof,
+            storage_proof,
+        })
+    }
+
     /// Handler for: `eth_getTransactionCount`
     #[rpc_method(name = "eth_getTransactionCount")]
     pub fn get_transaction_count(
         &self,
         address: reth_primitives::Address,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U64> {
         debug!("evm module: eth_getTransactionCount");
 
-        let curr_block_number = self
-            .blocks
-            .last(&mut working_set.accessory_state())
-            .expect("Head block must be set")
-            .header
-            .number;
-
-        // Specs from https://ethereum.org/en/developers/docs/apis/json-rpc
-        match block_number {
-            Some(BlockNumberOrTag::Number(num)) => {
-                if num > curr_block_number {
-                    return Err(EthApiError::UnknownBlockNumber.into());
-                }
-                set_state_to_end_of_evm_block(num, working_set);
-            }
-            // Working state here is already at the latest state, so no need to anything
-            Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) | None => {}
-            Some(BlockNumberOrTag::Earliest) => {
-                set_state_to_end_of_evm_block(0, working_set);
-            }
-            _ => {
-                return Err(EthApiError::InvalidParams(
-                    "Please provide a number or earliest/latest tag".to_string(),
-                )
-                .into())
-            }
-        }
+        self.set_state_to_block_id(block_id, working_set)?;
 
         let nonce = self
             .accounts
@@ -433,39 +441,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_code(
         &self,
         address: reth_primitives::Address,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         debug!("evm module: eth_getCode");
 
-        let curr_block_number = self
-            .blocks
-            .last(&mut working_set.accessory_state())
-            .expect("Head block must be set")
-            .header
-            .number;
-
-        match block_number {
-            Some(BlockNumberOrTag::Number(num)) => {
-                if num > curr_block_number {
-                    return Err(EthApiError::UnknownBlockNumber.into());
-                }
-                set_state_to_end_of_evm_block(num, working_set);
-            }
-            // Working state here is already at the latest state, so no need to anything
-            Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) | None => {}
-            Some(BlockNumberOrTag::Earliest) => {
-                set_state_to_end_of_evm_block(0, working_set);
-            }
-            // Is this the way?
-            // Note that reth works for all types of BlockNumberOrTag
-            _ => {
-                return Err(EthApiError::InvalidParams(
-                    "Please provide a number or earliest/latest tag".to_string(),
-                )
-                .into())
-            }
-        }
+        self.set_state_to_block_id(block_id, working_set)?;
 
         let code = self
             .accounts
@@ -608,6 +589,57 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(receipt)
     }
 
+    /// Handler for: `citrea_getFeeBreakdown`
+    /// Splits the fee paid by a transaction into its base fee, priority fee and L1 fee.
+    #[rpc_method(name = "citrea_getFeeBreakdown")]
+    pub fn get_fee_breakdown(
+        &self,
+        hash: reth_primitives::B256,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<FeeBreakdown>> {
+        debug!("evm module: citrea_getFeeBreakdown");
+        let mut accessory_state = working_set.accessory_state();
+
+        let Some(tx_number) = self.transaction_hashes.get(&hash, &mut accessory_state) else {
+            return Ok(None);
+        };
+
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Transaction with known hash must be set");
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .expect("Block number for known transaction must be set");
+        let receipt = self
+            .receipts
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Receipt for known transaction must be set");
+
+        let transaction: TransactionSignedEcRecovered = tx.into();
+        let base_fee_per_gas = block.header.base_fee_per_gas.unwrap_or_default() as u128;
+        let effective_gas_price = transaction.effective_gas_price(block.header.base_fee_per_gas);
+        let priority_fee_per_gas = effective_gas_price.saturating_sub(base_fee_per_gas);
+        let gas_used = U256::from(receipt.gas_used);
+        // All fees are credited to the sequencer's coinbase, see `reward_beneficiary` and `post_execution_output`
+        let fee_recipient = block.header.beneficiary;
+
+        Ok(Some(FeeBreakdown {
+            gas_used: U64::from(receipt.gas_used),
+            base_fee_per_gas: U256::from(base_fee_per_gas),
+            base_fee: U256::from(base_fee_per_gas) * gas_used,
+            base_fee_recipient: fee_recipient,
+            priority_fee_per_gas: U256::from(priority_fee_per_gas),
+            priority_fee: U256::from(priority_fee_per_gas) * gas_used,
+            priority_fee_recipient: fee_recipient,
+            l1_fee_rate: U256::from(block.l1_fee_rate),
+            l1_diff_size: U64::from(receipt.diff_size),
+            l1_fee: l1_fee(block.l1_fee_rate, receipt.diff_size),
+            l1_fee_recipient: fee_recipient,
+        }))
+    }
+
     /// Handler for: `eth_call`
     //https://github.com/paradigmxyz/reth/blob/f577e147807a783438a3f16aad968b4396274483/crates/rpc/rpc/src/eth/api/transactions.rs#L502
     //https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-types/src/eth/call.rs#L7
@@ -615,12 +647,16 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_call(
         &self,
         request: reth_rpc_types::TransactionRequest,
-        block_number: Option<BlockNumberOrTag>,
-        _state_overrides: Option<reth_rpc_types::state::StateOverride>,
-        _block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
+        block_id: Option<BlockId>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         debug!("evm module: eth_call");
+        let block_number = block_id
+            .map(|block_id| self.block_number_or_tag_for_id(block_id, working_set))
+            .transpose()?;
+        let overrides = EvmOverrides::new(state_overrides, block_overrides);
         let mut block_env = match block_number {
             None | Some(BlockNumberOrTag::Pending | BlockNumberOrTag::Latest) => {
                 // if no block is produced yet, should default to genesis block env, else just return the lates
@@ -642,7 +678,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     None => return Err(EthApiError::UnknownBlockNumber.into()),
                 };
 
-                set_state_to_end_of_evm_block(block.header.number, working_set);
+                set_state_to_end_of_evm_block(block.header.number, working_set)?;
 
                 BlockEnv::from(&block)
             }
@@ -660,8 +696,10 @@ impl<C: sov_modules_api::Context> Evm<C> {
         // set higher block gas limit than usual
         // but still cap it to prevent DoS
         block_env.gas_limit = 100_000_000;
+        overrides.apply_to_block_env(&mut block_env);
+        block_env.gas_limit = block_env.gas_limit.min(100_000_000);
 
-        let mut evm_db = self.get_db(working_set);
+        let mut evm_db = overrides.override_db(self.get_db(working_set))?;
         let mut tx_env = prepare_call_env(
             &block_env,
             request.clone(),
@@ -714,10 +752,16 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn create_access_list(
         &self,
         request: reth_rpc_types::TransactionRequest,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<AccessListWithGasUsed> {
         debug!("evm module: eth_createAccessList");
+        let block_number = block_id
+            .map(|block_id| self.block_number_or_tag_for_id(block_id, working_set))
+            .transpose()?;
+        let overrides = EvmOverrides::new(state_overrides, block_overrides);
 
         let mut request = request.clone();
 
@@ -746,7 +790,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     None => return Err(EthApiError::UnknownBlockNumber.into()),
                 };
 
-                set_state_to_end_of_evm_block(block.header.number, working_set);
+                set_state_to_end_of_evm_block(block.header.number, working_set)?;
                 let l1_fee_rate = block.l1_fee_rate;
                 let block_env = BlockEnv::from(&block);
                 (l1_fee_rate, block_env)
@@ -765,8 +809,10 @@ impl<C: sov_modules_api::Context> Evm<C> {
         // set higher block gas limit than usual
         // but still cap it to prevent DoS
         block_env.gas_limit = 100_000_000;
+        overrides.apply_to_block_env(&mut block_env);
+        block_env.gas_limit = block_env.gas_limit.min(100_000_000);
 
-        let mut evm_db = self.get_db(working_set);
+        let mut evm_db = overrides.override_db(self.get_db(working_set))?;
 
         let mut tx_env = prepare_call_env(
             &block_env,
@@ -827,6 +873,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             block_env,
             cfg_env,
             &mut tx_env,
+            &overrides,
             working_set,
         )?;
 
@@ -841,10 +888,14 @@ impl<C: sov_modules_api::Context> Evm<C> {
     fn estimate_tx_expenses(
         &self,
         request: reth_rpc_types::TransactionRequest,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
+        overrides: EvmOverrides,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<EstimatedTxExpenses> {
-        let (l1_fee_rate, block_env) = match block_number {
+        let block_number = block_id
+            .map(|block_id| self.block_number_or_tag_for_id(block_id, working_set))
+            .transpose()?;
+        let (l1_fee_rate, mut block_env) = match block_number {
             None | Some(BlockNumberOrTag::Pending | BlockNumberOrTag::Latest) => {
                 // so we don't unnecessarily set archival version
                 // if no block was produced yet, the l1 fee rate can unwrap to 0, we don't care, else just return the latest
@@ -869,15 +920,13 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     None => return Err(EthApiError::UnknownBlockNumber.into()),
                 };
 
-                set_state_to_end_of_evm_block(block.header.number, working_set);
+                set_state_to_end_of_evm_block(block.header.number, working_set)?;
                 let l1_fee_rate = block.l1_fee_rate;
                 let block_env = BlockEnv::from(&block);
                 (l1_fee_rate, block_env)
             }
         };
 
-        let mut tx_env = prepare_call_env(&block_env, request.clone(), None)?;
-
         let cfg = self
             .cfg
             .get(working_set)
@@ -888,12 +937,19 @@ impl<C: sov_modules_api::Context> Evm<C> {
         cfg_env.disable_eip3607 = true;
         cfg_env.disable_base_fee = true;
 
+        overrides.apply_to_block_env(&mut block_env);
+        // cap the overridden block gas limit to prevent DoS
+        block_env.gas_limit = block_env.gas_limit.min(100_000_000);
+
+        let mut tx_env = prepare_call_env(&block_env, request.clone(), None)?;
+
         self.estimate_gas_with_env(
             request,
             l1_fee_rate,
             block_env,
             cfg_env,
             &mut tx_env,
+            &overrides,
             working_set,
         )
     }
@@ -904,12 +960,15 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn eth_estimate_gas(
         &self,
         request: reth_rpc_types::TransactionRequest,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         debug!("evm module: eth_estimateGas");
 
-        let estimated = self.estimate_tx_expenses(request, block_number, working_set)?;
+        let overrides = EvmOverrides::new(state_overrides, block_overrides);
+        let estimated = self.estimate_tx_expenses(request, block_id, overrides, working_set)?;
         Ok(estimated.gas_with_l1_overhead())
     }
 
@@ -918,14 +977,17 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn eth_estimate_diff_size(
         &self,
         request: reth_rpc_types::TransactionRequest,
-        block_number: Option<BlockNumberOrTag>,
+        block_id: Option<BlockId>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<EstimatedDiffSize> {
         debug!("evm module: eth_estimateDiffSize");
         if request.gas.is_none() {
             return Err(EthApiError::InvalidParams("gas must be set".into()))?;
         }
-        let estimated = self.estimate_tx_expenses(request, block_number, working_set)?;
+        let overrides = EvmOverrides::new(state_overrides, block_overrides);
+        let estimated = self.estimate_tx_expenses(request, block_id, overrides, working_set)?;
 
         Ok(EstimatedDiffSize {
             gas: estimated.gas_used,
@@ -933,6 +995,245 @@ impl<C: sov_modules_api::Context> Evm<C> {
         })
     }
 
+    /// Handler for: `eth_simulateV1`
+    /// Executes the calls of the simulated blocks one after another, on top of the state at `block_number`.
+    /// The state changes are kept in memory, so the working set is not modified.
+    #[rpc_method(name = "eth_simulateV1")]
+    pub fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        debug!("evm module: eth_simulateV1");
+
+        let SimulatePayload {
+            block_state_calls,
+            validation,
+            trace_transfers,
+        } = payload;
+        if trace_transfers {
+            return Err(EthApiError::Unsupported("traceTransfers is not supported").into());
+        }
+        if block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "Too many blocks, the max is {}",
+                MAX_SIMULATED_BLOCKS
+            ))
+            .into());
+        }
+        let calls_count: usize = block_state_calls
+            .iter()
+            .map(|block| block.calls.len())
+            .sum();
+        if calls_count > MAX_SIMULATED_CALLS {
+            return Err(EthApiError::InvalidParams(format!(
+                "Too many calls, the max is {}",
+                MAX_SIMULATED_CALLS
+            ))
+            .into());
+        }
+
+        let block_number = match block_id {
+            None | Some(BlockId::Number(BlockNumberOrTag::Pending)) => BlockNumberOrTag::Latest,
+            Some(block_id) => self.block_number_or_tag_for_id(block_id, working_set)?,
+        };
+        let base_block = self
+            .get_sealed_block_by_number(Some(block_number), working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        // so we don't unnecessarily set archival version
+        if block_number != BlockNumberOrTag::Latest {
+            set_state_to_end_of_evm_block(base_block.header.number, working_set)?;
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let l1_fee_rate = base_block.l1_fee_rate;
+
+        let mut parent_hash = base_block.header.hash();
+        let mut parent_env = BlockEnv::from(&base_block);
+        let mut db = self.get_db(working_set);
+        let mut evm_db = OverrideDb::new(CacheDB::new(RefCellDb::new(&mut db)));
+        let mut simulated_blocks = Vec::with_capacity(block_state_calls.len());
+        // gas the remaining calls of all blocks can use
+        let mut request_gas_left = MAX_SIMULATED_GAS;
+        for SimulateBlock {
+            block_overrides,
+            state_overrides,
+            calls,
+        } in block_state_calls
+        {
+            let mut block_env = BlockEnv {
+                number: parent_env.number + 1,
+                timestamp: parent_env.timestamp + SIMULATED_BLOCK_TIME,
+                ..parent_env
+            };
+            if let Some(block_overrides) = &block_overrides {
+                apply_block_overrides(block_overrides, &mut block_env);
+                evm_db = evm_db.with_block_hash_overrides(block_overrides);
+            }
+            if block_env.number <= parent_env.number || block_env.timestamp <= parent_env.timestamp
+            {
+                return Err(EthApiError::InvalidParams(
+                    "Simulated blocks must have increasing numbers and timestamps".into(),
+                )
+                .into());
+            }
+            // cap the block gas limit to prevent DoS
+            block_env.gas_limit = block_env.gas_limit.min(100_000_000);
+            evm_db = evm_db.with_state_overrides(state_overrides)?;
+
+            let mut cfg_env = get_cfg_env(&block_env, cfg.clone());
+            if !validation {
+                cfg_env.disable_eip3607 = true;
+                cfg_env.disable_base_fee = true;
+            }
+
+            let mut gas_used = 0u64;
+            let mut log_index = 0u64;
+            let mut simulated_calls = Vec::with_capacity(calls.len());
+            for (transaction_index, request) in calls.into_iter().enumerate() {
+                let remaining_gas = (block_env.gas_limit - gas_used).min(request_gas_left);
+                let request_gas = request.gas;
+                let balance = evm_db
+                    .basic(request.from.unwrap_or_default())
+                    .map_err(EthApiError::from)?
+                    .unwrap_or_default()
+                    .balance;
+                let mut tx_env = prepare_call_env(&block_env, request, Some(balance))?;
+                if request_gas.is_some() && tx_env.gas_limit > remaining_gas {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "Call {} of block {} exceeds the gas left in the block or the request",
+                        transaction_index, block_env.number
+                    ))
+                    .into());
+                }
+                let is_gas_limited = tx_env.gas_limit > remaining_gas;
+                tx_env.gas_limit = tx_env.gas_limit.min(remaining_gas);
+                if !validation {
+                    // https://github.com/paradigmxyz/reth/issues/6574
+                    tx_env.nonce = None;
+                }
+
+                let (result_and_state, tx_info) = match inspect_no_tracing(
+                    &mut evm_db,
+                    cfg_env.clone(),
+                    block_env,
+                    tx_env,
+                    l1_fee_rate,
+                ) {
+                    Ok(result) => result,
+                    // the gas left does not even cover the intrinsic gas, only this call fails
+                    Err(EVMError::Transaction(InvalidTransaction::CallGasCostMoreThanGasLimit))
+                        if is_gas_limited =>
+                    {
+                        simulated_calls.push(SimulatedCall {
+                            status: U64::ZERO,
+                            return_data: Default::default(),
+                            gas_used: U64::ZERO,
+                            logs: vec![],
+                            l1_diff_size: U64::ZERO,
+                            l1_fee: U256::ZERO,
+                            error: Some(SimulatedCallError {
+                                code: -38015,
+                                message: "not enough gas left in the block or the request"
+                                    .to_string(),
+                            }),
+                        });
+                        continue;
+                    }
+                    Err(err) => return Err(EthApiError::from(err).into()),
+                };
+                // the next calls are executed on top of the state changes of this call
+                evm_db.commit(result_and_state.state);
+
+                let result = result_and_state.result;
+                gas_used += result.gas_used();
+                request_gas_left -= result.gas_used();
+                let logs = result
+                    .logs()
+                    .iter()
+                    .map(|log| {
+                        log_index += 1;
+                        Log {
+                            inner: log.clone(),
+                            block_hash: None,
+                            block_number: Some(block_env.number),
+                            block_timestamp: Some(block_env.timestamp),
+                            transaction_hash: None,
+                            transaction_index: Some(transaction_index as u64),
+                            log_index: Some(log_index - 1),
+                            removed: false,
+                        }
+                    })
+                    .collect();
+
+                simulated_calls.push(SimulatedCall {
+                    status: U64::from(result.is_success() as u64),
+                    gas_used: U64::from(result.gas_used()),
+                    logs,
+                    l1_diff_size: U64::from(tx_info.diff_size),
+                    l1_fee: tx_info.l1_fee,
+                    error: match &result {
+                        ExecutionResult::Success { .. } => None,
+                        ExecutionResult::Revert { output, .. } => Some(SimulatedCallError {
+                            code: 3,
+                            message: RevertError::new(output.clone()).to_string(),
+                        }),
+                        ExecutionResult::Halt { reason, .. } => Some(SimulatedCallError {
+                            code: -32015,
+                            message: RpcInvalidTransactionError::EvmHalt(*reason).to_string(),
+                        }),
+                    },
+                    return_data: match result {
+                        ExecutionResult::Success { output, .. } => output.into_data(),
+                        ExecutionResult::Revert { output, .. } => output,
+                        ExecutionResult::Halt { .. } => Default::default(),
+                    },
+                });
+            }
+
+            let header = Header {
+                parent_hash,
+                beneficiary: block_env.coinbase,
+                number: block_env.number,
+                gas_limit: block_env.gas_limit,
+                gas_used,
+                timestamp: block_env.timestamp,
+                mix_hash: block_env.prevrandao,
+                base_fee_per_gas: Some(block_env.basefee),
+                ..Default::default()
+            };
+            let hash = header.hash_slow();
+            for call in simulated_calls.iter_mut() {
+                for log in call.logs.iter_mut() {
+                    log.block_hash = Some(hash);
+                }
+            }
+
+            simulated_blocks.push(SimulatedBlock {
+                number: U64::from(block_env.number),
+                hash,
+                parent_hash,
+                timestamp: U64::from(block_env.timestamp),
+                gas_limit: U64::from(block_env.gas_limit),
+                gas_used: U64::from(gas_used),
+                base_fee_per_gas: U256::from(block_env.basefee),
+                miner: block_env.coinbase,
+                calls: simulated_calls,
+            });
+
+            // the next blocks can read the hash of this block
+            evm_db = evm_db.with_block_hash(block_env.number, hash);
+            parent_hash = hash;
+            parent_env = block_env;
+        }
+
+        Ok(simulated_blocks)
+    }
+
     /// Handler for: `eth_getBlockTransactionCountByHash`
     // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/call.rs#L172
     #[rpc_method(name = "eth_getBlockTransactionCountByHash")]
@@ -967,6 +1268,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 
     /// Inner gas estimator
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn estimate_gas_with_env(
         &self,
         request: reth_rpc_types::TransactionRequest,
@@ -974,6 +1276,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         block_env: BlockEnv,
         cfg_env: CfgEnvWithHandlerCfg,
         tx_env: &mut TxEnv,
+        overrides: &EvmOverrides,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<EstimatedTxExpenses> {
         let request_gas = request.gas;
@@ -985,19 +1288,20 @@ impl<C: sov_modules_api::Context> Evm<C> {
         // configured gas limit
         let mut highest_gas_limit = request.gas.unwrap_or(env_gas_limit);
 
-        let account = self
-            .accounts
-            .get(&tx_env.caller, working_set)
-            .map(|account| account.info)
+        // The state overrides are applied once, every execution below reads from the same database
+        let mut evm_db = overrides.override_db(self.get_db(working_set))?;
+
+        let account = evm_db
+            .basic(tx_env.caller)
+            .map_err(EthApiError::from)?
             .unwrap_or_default();
 
         // if the request is a simple transfer we can optimize
         if tx_env.data.is_empty() {
             if let TransactTo::Call(to) = tx_env.transact_to {
-                let to_account = self
-                    .accounts
-                    .get(&to, working_set)
-                    .map(|account| account.info)
+                let to_account = evm_db
+                    .basic(to)
+                    .map_err(EthApiError::from)?
                     .unwrap_or_default();
                 if KECCAK_EMPTY == to_account.code_hash {
                     // If the tx is a simple transfer (call to an account with no code) we can
@@ -1011,7 +1315,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     tx_env.gas_limit = MIN_TRANSACTION_GAS;
 
                     let res = inspect_no_tracing(
-                        self.get_db(working_set),
+                        &mut evm_db,
                         cfg_env.clone(),
                         block_env,
                         tx_env.clone(),
@@ -1047,11 +1351,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let gas_limit: u64 = std::cmp::min(tx_env.gas_limit, highest_gas_limit as u64); // highest_gas_limit is capped to u64::MAX
         tx_env.gas_limit = gas_limit;
 
-        let evm_db = self.get_db(working_set);
-
         // execute the call without writing to db
         let result = inspect_no_tracing(
-            evm_db,
+            &mut evm_db,
             cfg_env.clone(),
             block_env,
             tx_env.clone(),
@@ -1065,12 +1367,11 @@ impl<C: sov_modules_api::Context> Evm<C> {
             // if price or limit was included in the request then we can execute the request
             // again with the block's gas limit to check if revert is gas related or not
             if request_gas.is_some() || request_gas_price.is_some() {
-                let evm_db = self.get_db(working_set);
                 return Err(map_out_of_gas_err(
                     block_env,
                     tx_env.clone(),
                     cfg_env,
-                    evm_db,
+                    &mut evm_db,
                     l1_fee_rate,
                 )
                 .into());
@@ -1089,12 +1390,11 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     // if price or limit was included in the request then we can execute the request
                     // again with the block's gas limit to check if revert is gas related or not
                     return if request_gas.is_some() || request_gas_price.is_some() {
-                        let evm_db = self.get_db(working_set);
                         Err(map_out_of_gas_err(
                             block_env,
                             tx_env.clone(),
                             cfg_env,
-                            evm_db,
+                            &mut evm_db,
                             l1_fee_rate,
                         )
                         .into())
@@ -1130,7 +1430,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             tx_env.gas_limit = optimistic_gas_limit;
             // (result, env) = executor::transact(&mut db, env)?;
             let curr_result = inspect_no_tracing(
-                self.get_db(working_set),
+                &mut evm_db,
                 cfg_env.clone(),
                 block_env,
                 tx_env.clone(),
@@ -1170,9 +1470,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
             let mut tx_env = tx_env.clone();
             tx_env.gas_limit = mid_gas_limit;
 
-            let evm_db = self.get_db(working_set);
             let result = inspect_no_tracing(
-                evm_db,
+                &mut evm_db,
                 cfg_env.clone(),
                 block_env,
                 tx_env.clone(),
@@ -1295,7 +1594,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             .collect();
 
         // set state to end of the previous block
-        set_state_to_end_of_evm_block(block_number - 1, working_set);
+        set_state_to_end_of_evm_block(block_number - 1, working_set)?;
 
         let block_env = BlockEnv::from(&sealed_block);
         let cfg = self
@@ -1338,6 +1637,276 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(traces)
     }
 
+    /// Traces the transactions of the block with the parity tracer and returns the requested trace types
+    pub fn trace_replay_block_transactions(
+        &self,
+        block_number: u64,
+        trace_types: HashSet<TraceType>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<TraceResultsWithTransactionHash>> {
+        let (_, traces) =
+            self.trace_block_transactions_parity(block_number, &trace_types, working_set)?;
+        Ok(traces)
+    }
+
+    /// Traces the transactions of the block with the parity tracer and returns the traces of their calls
+    pub fn trace_block_parity(
+        &self,
+        block_number: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        let trace_types = HashSet::from([TraceType::Trace]);
+        let (sealed_block, traces) =
+            self.trace_block_transactions_parity(block_number, &trace_types, working_set)?;
+
+        let block_hash = sealed_block.header.hash();
+        let traces =
+            traces
+                .into_iter()
+                .enumerate()
+                .flat_map(|(index, tx_traces)| {
+                    let transaction_hash = tx_traces.transaction_hash;
+                    tx_traces.full_trace.trace.into_iter().map(move |trace| {
+                        LocalizedTransactionTrace {
+                            trace,
+                            block_hash: Some(block_hash),
+                            block_number: Some(block_number),
+                            transaction_hash: Some(transaction_hash),
+                            transaction_position: Some(index as u64),
+                        }
+                    })
+                })
+                .collect();
+
+        Ok(traces)
+    }
+
+    fn trace_block_transactions_parity(
+        &self,
+        block_number: u64,
+        trace_types: &HashSet<TraceType>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<(SealedBlock, Vec<TraceResultsWithTransactionHash>)> {
+        let sealed_block = self
+            .get_sealed_block_by_number(Some(BlockNumberOrTag::Number(block_number)), working_set)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        let tx_range = sealed_block.transactions.clone();
+        if tx_range.is_empty() {
+            return Ok((sealed_block, Vec::new()));
+        }
+        let block_txs: Vec<TransactionSignedEcRecovered> = tx_range
+            .map(|id| {
+                self.transactions
+                    .get(id as usize, &mut working_set.accessory_state())
+                    .expect("Transaction must be set")
+                    .into()
+            })
+            .collect();
+
+        // set state to end of the previous block
+        set_state_to_end_of_evm_block(block_number - 1, working_set)?;
+
+        let block_env = BlockEnv::from(&sealed_block);
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let cfg_env = get_cfg_env(&block_env, cfg);
+        let l1_fee_rate = sealed_block.l1_fee_rate;
+
+        let mut evm_db = self.get_db(working_set);
+
+        let mut traces = Vec::with_capacity(block_txs.len());
+        let mut transactions = block_txs.into_iter().peekable();
+        while let Some(tx) = transactions.next() {
+            let (full_trace, state_changes) = trace_transaction_parity(
+                trace_types,
+                cfg_env.clone(),
+                block_env,
+                tx_env_with_recovered(&tx),
+                tx.hash(),
+                &mut evm_db,
+                l1_fee_rate,
+            )?;
+            traces.push(TraceResultsWithTransactionHash {
+                full_trace,
+                transaction_hash: tx.hash(),
+            });
+
+            if transactions.peek().is_some() {
+                // need to apply the state changes of this transaction before executing the
+                // next transaction
+                evm_db.commit(state_changes)
+            }
+        }
+
+        Ok((sealed_block, traces))
+    }
+
+    /// Traces a call that is not mined, on top of the state at the end of the given block
+    pub fn trace_call(
+        &self,
+        request: reth_rpc_types::TransactionRequest,
+        block_id: Option<BlockId>,
+        opts: Option<GethDebugTracingCallOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GethTrace> {
+        let mut opts = opts.unwrap_or_default();
+        let bundle = Bundle {
+            transactions: vec![request],
+            block_override: opts.block_overrides.take(),
+        };
+        let state_context = StateContext {
+            block_number: block_id,
+            transaction_index: None,
+        };
+
+        let traces =
+            self.trace_call_many(vec![bundle], Some(state_context), Some(opts), working_set)?;
+
+        Ok(traces
+            .into_iter()
+            .flatten()
+            .next()
+            .expect("Every call must have a trace"))
+    }
+
+    /// Traces calls that are not mined, on top of the state at `state_context`.
+    /// The calls are executed one after another, so every call sees the state changes
+    /// of the calls before it.
+    pub fn trace_call_many(
+        &self,
+        bundles: Vec<Bundle>,
+        state_context: Option<StateContext>,
+        opts: Option<GethDebugTracingCallOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<Vec<GethTrace>>> {
+        let GethDebugTracingCallOptions {
+            tracing_options,
+            state_overrides,
+            block_overrides,
+        } = opts.unwrap_or_default();
+        let StateContext {
+            block_number,
+            transaction_index,
+        } = state_context.unwrap_or_default();
+
+        let block_number = match block_number {
+            None | Some(BlockId::Number(BlockNumberOrTag::Pending)) => BlockNumberOrTag::Latest,
+            Some(block_id) => self.block_number_or_tag_for_id(block_id, working_set)?,
+        };
+        let sealed_block = self
+            .get_sealed_block_by_number(Some(block_number), working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        // Transactions of the block that are executed before the calls
+        let tx_range = sealed_block.transactions.clone();
+        let replayed_range = match transaction_index.unwrap_or_default() {
+            TransactionIndex::All => tx_range.clone(),
+            TransactionIndex::Index(index) => {
+                tx_range.start..tx_range.end.min(tx_range.start + index as u64)
+            }
+        };
+        let replayed_txs: Vec<TransactionSignedEcRecovered> = if replayed_range == tx_range {
+            // so we don't unnecessarily set archival version
+            if block_number != BlockNumberOrTag::Latest {
+                set_state_to_end_of_evm_block(sealed_block.header.number, working_set)?;
+            }
+            vec![]
+        } else {
+            // set state to end of the previous block and replay the transactions before the index
+            set_state_to_end_of_evm_block(sealed_block.header.number - 1, working_set)?;
+            replayed_range
+                .map(|id| {
+                    self.transactions
+                        .get(id as usize, &mut working_set.accessory_state())
+                        .expect("Transaction must be set")
+                        .into()
+                })
+                .collect()
+        };
+
+        let mut block_env = BlockEnv::from(&sealed_block);
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let cfg_env = get_cfg_env(&block_env, cfg);
+        let l1_fee_rate = sealed_block.l1_fee_rate;
+
+        let mut evm_db = self.get_db(working_set);
+        for tx in replayed_txs {
+            let (result_and_state, _) = inspect_no_tracing(
+                &mut evm_db,
+                cfg_env.clone(),
+                block_env,
+                tx_env_with_recovered(&tx),
+                l1_fee_rate,
+            )
+            .map_err(EthApiError::from)?;
+            evm_db.commit(result_and_state.state);
+        }
+
+        // set endpoint specific params
+        let mut call_cfg_env = cfg_env;
+        call_cfg_env.disable_eip3607 = true;
+        call_cfg_env.disable_base_fee = true;
+        // set higher block gas limit than usual
+        // but still cap it to prevent DoS
+        block_env.gas_limit = 100_000_000;
+
+        let mut evm_db = OverrideDb::new(evm_db).with_state_overrides(state_overrides)?;
+        if let Some(block_overrides) = &block_overrides {
+            apply_block_overrides(block_overrides, &mut block_env);
+            evm_db = evm_db.with_block_hash_overrides(block_overrides);
+        }
+
+        let mut traces = Vec::with_capacity(bundles.len());
+        for Bundle {
+            transactions,
+            block_override,
+        } in bundles
+        {
+            let mut block_env = block_env;
+            if let Some(block_override) = &block_override {
+                apply_block_overrides(block_override, &mut block_env);
+                evm_db = evm_db.with_block_hash_overrides(block_override);
+            }
+            block_env.gas_limit = block_env.gas_limit.min(100_000_000);
+
+            let mut bundle_traces = Vec::with_capacity(transactions.len());
+            for request in transactions {
+                let balance = evm_db
+                    .basic(request.from.unwrap_or_default())
+                    .map_err(EthApiError::from)?
+                    .unwrap_or_default()
+                    .balance;
+                let mut tx_env = prepare_call_env(&block_env, request, Some(balance))?;
+
+                // https://github.com/paradigmxyz/reth/issues/6574
+                tx_env.nonce = None;
+
+                let (trace, state_changes) = trace_transaction(
+                    tracing_options.clone(),
+                    call_cfg_env.clone(),
+                    block_env,
+                    tx_env,
+                    EPHEMERAL_TX_HASH,
+                    &mut evm_db,
+                    l1_fee_rate,
+                )?;
+                bundle_traces.push(trace);
+
+                // the next calls are executed on top of the state changes of this call
+                evm_db.commit(state_changes);
+            }
+            traces.push(bundle_traces);
+        }
+
+        Ok(traces)
+    }
+
     // https://github.com/paradigmxyz/reth/blob/8892d04a88365ba507f28c3314d99a6b54735d3f/crates/rpc/rpc/src/eth/filter.rs#L349
     fn logs_for_filter(
         &self,
@@ -1587,6 +2156,44 @@ impl<C: sov_modules_api::Context> Evm<C> {
         }
     }
 
+    /// Resolves a block id to a block number or tag.
+    /// Every stored block is canonical, so `requireCanonical` of EIP-1898 block hashes always holds.
+    pub fn block_number_or_tag_for_id(
+        &self,
+        block_id: BlockId,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<BlockNumberOrTag, EthApiError> {
+        match block_id {
+            BlockId::Number(block_number) => Ok(block_number),
+            BlockId::Hash(block_hash) => self
+                .get_block_number_by_block_hash(block_hash.block_hash, working_set)
+                .map(BlockNumberOrTag::Number)
+                .ok_or(EthApiError::UnknownBlockNumber),
+        }
+    }
+
+    /// Sets the state of the working set to the end of the given block.
+    /// The working set is already at the latest state, so nothing is done for the latest block.
+    fn set_state_to_block_id(
+        &self,
+        block_id: Option<BlockId>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<()> {
+        let block_number = match block_id {
+            Some(block_id) => self.block_number_or_tag_for_id(block_id, working_set)?,
+            None => BlockNumberOrTag::Latest,
+        };
+
+        // Specs from https://ethereum.org/en/developers/docs/apis/json-rpc
+        match block_number {
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => Ok(()),
+            block_number => {
+                let block_number = self.block_number_for_id(&block_number, working_set)?;
+                set_state_to_end_of_evm_block(block_number, working_set)
+            }
+        }
+    }
+
     /// Helper function to get sealed block by number
     /// If returns None, block doesn't exist
     fn get_sealed_block_by_number(
@@ -1643,6 +2250,11 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 }
 
+/// L1 fee paid by a transaction for publishing its state diff, as charged in `post_execution_output`
+fn l1_fee(l1_fee_rate: u128, diff_size: u64) -> U256 {
+    U256::from(diff_size) * U256::from(l1_fee_rate)
+}
+
 // modified from: https://github.com/paradigmxyz/reth/blob/cc576bc8690a3e16e6e5bf1cbbbfdd029e85e3d4/crates/rpc/rpc/src/eth/api/transactions.rs#L849
 pub(crate) fn build_rpc_receipt(
     block: &SealedBlock,
@@ -1669,6 +2281,14 @@ pub(crate) fn build_rpc_receipt(
                 "diffSize".into(),
                 format!("{:#x}", receipt.diff_size).into(),
             ),
+            (
+                "l1DiffSize".into(),
+                format!("{:#x}", receipt.diff_size).into(),
+            ),
+            (
+                "l1Fee".into(),
+                format!("{:#x}", l1_fee(block.l1_fee_rate, receipt.diff_size)).into(),
+            ),
         ]
         .into_iter()
         .collect(),
@@ -1742,13 +2362,17 @@ fn check_tx_range(transactions_range: &Range<u64>, index: Uint<64, 1>) -> Option
     }
 }
 
-fn map_out_of_gas_err<C: sov_modules_api::Context>(
+fn map_out_of_gas_err<DB>(
     block_env: BlockEnv,
     mut tx_env: revm::primitives::TxEnv,
     cfg_env: revm::primitives::CfgEnvWithHandlerCfg,
-    db: EvmDb<'_, C>,
+    db: DB,
     l1_fee_rate: u128,
-) -> EthApiError {
+) -> EthApiError
+where
+    DB: Database,
+    DB::Error: Into<EthApiError>,
+{
     let req_gas_limit = tx_env.gas_limit;
     tx_env.gas_limit = block_env.gas_limit;
 
@@ -1824,8 +2448,23 @@ fn update_estimated_gas_range(
 fn set_state_to_end_of_evm_block<C: sov_modules_api::Context>(
     block_number: u64,
     working_set: &mut WorkingSet<C>,
-) {
+) -> RpcResult<()> {
+    ensure_state_available(block_number, working_set)?;
     // genesis is committed at db version 1
     // so every block is offset by 1
     working_set.set_archival_version(block_number + 1);
+    Ok(())
+}
+
+/// Fails with a "state pruned" error if the storage no longer keeps the state at the end of the block.
+fn ensure_state_available<C: sov_modules_api::Context>(
+    block_number: u64,
+    working_set: &WorkingSet<C>,
+) -> RpcResult<()> {
+    // genesis is committed at db version 1
+    // so every block is offset by 1
+    if block_number + 1 < working_set.earliest_available_version() {
+        return Err(state_pruned_err(block_number));
+    }
+    Ok(())
 }
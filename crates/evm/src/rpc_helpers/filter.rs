@@ -723,8 +723,15 @@ pub fn matches_address(bloom: Bloom, address_filter: &BloomFilter) -> bool {
 /// Errors that can occur in the handler implementation
 #[derive(Debug, thiserror::Error)]
 pub enum FilterError {
-    // #[error("filter not found")]
-    // FilterNotFound(FilterId),
+    /// The filter was uninstalled, expired or never installed
+    #[error("filter not found")]
+    FilterNotFound(U64),
+    /// There is a maximum number of filters that a connection can install
+    #[error("connection exceeds max installed filters {0}")]
+    TooManyFilters(usize),
+    /// There is a maximum number of filters that all connections together can install
+    #[error("node exceeds max installed filters {0}")]
+    TooManyInstalledFilters(usize),
     /// There is a maximum number of blocks that can be queried in a single eth_getLogs request.
     #[error("query exceeds max block range {0}")]
     QueryExceedsMaxBlocks(u64),
@@ -743,10 +750,18 @@ pub enum FilterError {
 impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
     fn from(err: FilterError) -> Self {
         match err {
-            // FilterError::FilterNotFound(_) => rpc_error_with_code(
-            //     jsonrpsee::types::error::INVALID_PARAMS_CODE,
-            //     "filter not found",
-            // ),
+            err @ FilterError::FilterNotFound(_) => rpc_error_with_code(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+            ),
+            err @ FilterError::TooManyFilters(_) => rpc_error_with_code(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+            ),
+            err @ FilterError::TooManyInstalledFilters(_) => rpc_error_with_code(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+            ),
             err @ FilterError::InternalError => rpc_error_with_code(
                 jsonrpsee::types::error::INTERNAL_ERROR_CODE,
                 err.to_string(),
@@ -1,9 +1,13 @@
 mod filter;
 mod log_utils;
+mod overrides;
 mod responses;
+mod simulate;
 mod tracing_utils;
 
 pub use filter::*;
 pub use log_utils::*;
+pub(crate) use overrides::*;
 pub use responses::*;
+pub use simulate::*;
 pub(crate) use tracing_utils::*;
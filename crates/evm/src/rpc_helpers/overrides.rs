@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, HashMap};
+
+use reth_primitives::{Address, B256, U256};
+use reth_rpc::eth::error::EthApiError;
+use reth_rpc_types::state::{AccountOverride, StateOverride};
+use reth_rpc_types::BlockOverrides;
+use revm::primitives::{Account, AccountInfo, Bytecode};
+use revm::{Database, DatabaseCommit};
+
+use crate::evm::primitive_types::BlockEnv;
+
+/// Storage override of an account
+enum StorageOverride {
+    /// Replaces the whole storage, slots that are not set are zero
+    State(HashMap<U256, U256>),
+    /// Replaces only the given slots
+    StateDiff(HashMap<U256, U256>),
+}
+
+struct AccountOverrides {
+    balance: Option<U256>,
+    nonce: Option<u64>,
+    code: Option<Bytecode>,
+    storage: Option<StorageOverride>,
+}
+
+impl AccountOverrides {
+    /// Applies `other` on top of these overrides
+    fn merge(&mut self, other: AccountOverrides) {
+        self.balance = other.balance.or(self.balance);
+        self.nonce = other.nonce.or(self.nonce);
+        self.code = other.code.or(self.code.take());
+        self.storage = match (self.storage.take(), other.storage) {
+            (Some(StorageOverride::State(mut slots)), Some(StorageOverride::StateDiff(diff))) => {
+                slots.extend(diff);
+                Some(StorageOverride::State(slots))
+            }
+            (
+                Some(StorageOverride::StateDiff(mut slots)),
+                Some(StorageOverride::StateDiff(diff)),
+            ) => {
+                slots.extend(diff);
+                Some(StorageOverride::StateDiff(slots))
+            }
+            (storage, None) => storage,
+            (_, storage) => storage,
+        };
+    }
+}
+
+impl From<AccountOverride> for AccountOverrides {
+    fn from(account_override: AccountOverride) -> Self {
+        let into_slots = |slots: HashMap<B256, B256>| {
+            slots
+                .into_iter()
+                .map(|(key, value)| (U256::from_be_bytes(key.0), U256::from_be_bytes(value.0)))
+                .collect()
+        };
+
+        Self {
+            balance: account_override.balance,
+            nonce: account_override.nonce.map(|nonce| nonce.to::<u64>()),
+            code: account_override.code.map(Bytecode::new_raw),
+            storage: match (account_override.state, account_override.state_diff) {
+                (Some(state), _) => Some(StorageOverride::State(into_slots(state))),
+                (None, Some(state_diff)) => {
+                    Some(StorageOverride::StateDiff(into_slots(state_diff)))
+                }
+                (None, None) => None,
+            },
+        }
+    }
+}
+
+/// Database that applies the state and block hash overrides of an RPC call
+/// on top of another database.
+///
+/// Committed changes are written to the inner database and also replace the overridden
+/// values, so that calls executed one after another see each other's changes.
+pub(crate) struct OverrideDb<DB> {
+    db: DB,
+    accounts: HashMap<Address, AccountOverrides>,
+    code: HashMap<B256, Bytecode>,
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl<DB> OverrideDb<DB> {
+    pub(crate) fn new(db: DB) -> Self {
+        Self {
+            db,
+            accounts: HashMap::new(),
+            code: HashMap::new(),
+            block_hashes: BTreeMap::new(),
+        }
+    }
+
+    /// Overrides the accounts in `state_overrides`, on top of the previous overrides.
+    /// Returns an error if an account sets both `state` and `stateDiff`.
+    pub(crate) fn with_state_overrides(
+        mut self,
+        state_overrides: Option<StateOverride>,
+    ) -> Result<Self, EthApiError> {
+        for (address, account_override) in state_overrides.unwrap_or_default() {
+            if account_override.state.is_some() && account_override.state_diff.is_some() {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Account {} has both 'state' and 'stateDiff'",
+                    address
+                )));
+            }
+            let account_override = AccountOverrides::from(account_override);
+            if let Some(code) = &account_override.code {
+                self.code.insert(code.hash_slow(), code.clone());
+            }
+            match self.accounts.get_mut(&address) {
+                Some(previous_override) => previous_override.merge(account_override),
+                None => {
+                    self.accounts.insert(address, account_override);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Overrides the hashes returned by `BLOCKHASH` with the ones in `block_overrides`
+    pub(crate) fn with_block_hash_overrides(mut self, block_overrides: &BlockOverrides) -> Self {
+        if let Some(block_hashes) = &block_overrides.block_hash {
+            self.block_hashes
+                .extend(block_hashes.iter().map(|(number, hash)| (*number, *hash)));
+        }
+        self
+    }
+
+    /// Overrides the hash returned by `BLOCKHASH` for the block `number`
+    pub(crate) fn with_block_hash(mut self, number: u64, hash: B256) -> Self {
+        self.block_hashes.insert(number, hash);
+        self
+    }
+}
+
+impl<DB: Database> Database for OverrideDb<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        let Some(account_override) = self.accounts.get(&address) else {
+            return Ok(info);
+        };
+
+        let mut info = info.unwrap_or_default();
+        if let Some(balance) = account_override.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account_override.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = &account_override.code {
+            info.code_hash = code.hash_slow();
+            info.code = Some(code.clone());
+        }
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.code.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.db.code_by_hash(code_hash),
+        }
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        match self
+            .accounts
+            .get(&address)
+            .and_then(|account_override| account_override.storage.as_ref())
+        {
+            Some(StorageOverride::State(slots)) => {
+                Ok(slots.get(&index).copied().unwrap_or_default())
+            }
+            Some(StorageOverride::StateDiff(slots)) => match slots.get(&index) {
+                Some(value) => Ok(*value),
+                None => self.db.storage(address, index),
+            },
+            None => self.db.storage(address, index),
+        }
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        match self.block_hashes.get(&number.saturating_to::<u64>()) {
+            Some(hash) => Ok(*hash),
+            None => self.db.block_hash(number),
+        }
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for OverrideDb<DB> {
+    fn commit(&mut self, changes: revm::primitives::HashMap<Address, Account>) {
+        for (address, account) in changes.iter() {
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() {
+                self.accounts.remove(address);
+                continue;
+            }
+            let Some(account_override) = self.accounts.get_mut(address) else {
+                continue;
+            };
+
+            account_override.balance = Some(account.info.balance);
+            account_override.nonce = Some(account.info.nonce);
+            if let Some(StorageOverride::State(slots) | StorageOverride::StateDiff(slots)) =
+                &mut account_override.storage
+            {
+                slots.extend(
+                    account
+                        .storage
+                        .iter()
+                        .map(|(key, slot)| (*key, slot.present_value())),
+                );
+            }
+        }
+
+        self.db.commit(changes)
+    }
+}
+
+/// State and block overrides of an RPC call
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EvmOverrides {
+    state: Option<StateOverride>,
+    block: Option<Box<BlockOverrides>>,
+}
+
+impl EvmOverrides {
+    pub(crate) fn new(state: Option<StateOverride>, block: Option<Box<BlockOverrides>>) -> Self {
+        Self { state, block }
+    }
+
+    /// Applies the header overrides to the block env
+    pub(crate) fn apply_to_block_env(&self, block_env: &mut BlockEnv) {
+        if let Some(block_overrides) = &self.block {
+            apply_block_overrides(block_overrides, block_env);
+        }
+    }
+
+    /// Wraps the database with the state and block hash overrides
+    pub(crate) fn override_db<DB>(&self, db: DB) -> Result<OverrideDb<DB>, EthApiError> {
+        let db = OverrideDb::new(db).with_state_overrides(self.state.clone())?;
+        Ok(match &self.block {
+            Some(block_overrides) => db.with_block_hash_overrides(block_overrides),
+            None => db,
+        })
+    }
+}
+
+/// Applies the header overrides of an RPC call to the block env
+pub(crate) fn apply_block_overrides(block_overrides: &BlockOverrides, block_env: &mut BlockEnv) {
+    let BlockOverrides {
+        number,
+        // Difficulty is not used after the merge
+        difficulty: _,
+        time,
+        gas_limit,
+        coinbase,
+        random,
+        base_fee,
+        // Applied by the database
+        block_hash: _,
+    } = block_overrides;
+
+    if let Some(number) = number {
+        block_env.number = number.saturating_to();
+    }
+    if let Some(time) = time {
+        block_env.timestamp = time.to();
+    }
+    if let Some(gas_limit) = gas_limit {
+        block_env.gas_limit = gas_limit.to();
+    }
+    if let Some(coinbase) = coinbase {
+        block_env.coinbase = *coinbase;
+    }
+    if let Some(random) = random {
+        block_env.prevrandao = *random;
+    }
+    if let Some(base_fee) = base_fee {
+        block_env.basefee = base_fee.saturating_to();
+    }
+}
@@ -0,0 +1,100 @@
+use reth_primitives::{Address, Bytes, B256, U256, U64};
+use reth_rpc_types::state::StateOverride;
+use reth_rpc_types::{BlockOverrides, Log, TransactionRequest};
+use serde::{Deserialize, Serialize};
+
+/// Max number of blocks that can be simulated in a single `eth_simulateV1` request
+pub const MAX_SIMULATED_BLOCKS: usize = 256;
+
+/// Max number of calls of all blocks in a single `eth_simulateV1` request
+pub const MAX_SIMULATED_CALLS: usize = 1000;
+
+/// Max gas the calls of all blocks in a single `eth_simulateV1` request can use together
+pub const MAX_SIMULATED_GAS: u64 = 100_000_000;
+
+/// Seconds between the simulated blocks, unless their timestamp is overridden
+pub const SIMULATED_BLOCK_TIME: u64 = 12;
+
+/// Request of `eth_simulateV1`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Blocks to simulate, in order
+    pub block_state_calls: Vec<SimulateBlock>,
+    /// Whether the calls are checked like transactions, for their nonce, balance and base fee
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether ETH transfers are returned as logs, not supported
+    #[serde(default)]
+    pub trace_transfers: bool,
+}
+
+/// A block of calls to simulate
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    /// Header overrides of the block
+    #[serde(default)]
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides applied before the calls of the block
+    #[serde(default)]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed one after another
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// Result of a simulated block
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// Block number
+    pub number: U64,
+    /// Hash of the simulated header
+    pub hash: B256,
+    /// Hash of the previous block
+    pub parent_hash: B256,
+    /// Block timestamp
+    pub timestamp: U64,
+    /// Block gas limit
+    pub gas_limit: U64,
+    /// Gas used by the calls of the block
+    pub gas_used: U64,
+    /// Base fee of the block
+    pub base_fee_per_gas: U256,
+    /// Coinbase of the block
+    pub miner: Address,
+    /// Results of the calls
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Result of a simulated call
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// 1 if the call succeeded, 0 otherwise
+    pub status: U64,
+    /// Output of the call, or the revert data
+    pub return_data: Bytes,
+    /// Gas used by the call
+    pub gas_used: U64,
+    /// Logs emitted by the call
+    pub logs: Vec<Log>,
+    /// Size of the state diff the call would publish to L1
+    pub l1_diff_size: U64,
+    /// L1 fee the call would pay for its state diff
+    pub l1_fee: U256,
+    /// Why the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a failed simulated call
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    /// 3 if the call reverted, -32015 if the EVM halted,
+    /// -38015 if not enough gas was left in the block or the request to run it
+    pub code: i32,
+    /// Error message
+    pub message: String,
+}
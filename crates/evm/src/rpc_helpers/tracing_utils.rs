@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
 use reth_primitives::revm_primitives::TxEnv;
 use reth_primitives::{TransactionSigned, TransactionSignedEcRecovered, TxHash, U256};
 use reth_rpc::eth::error::{EthApiError, EthResult, RpcInvalidTransactionError};
@@ -5,27 +8,37 @@ use reth_rpc_types::trace::geth::{
     FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
     GethTrace, NoopFrame,
 };
+use reth_rpc_types::trace::parity::{TraceResults, TraceType};
 use revm::precompile::{PrecompileSpecId, Precompiles};
-use revm::primitives::db::Database;
-use revm::primitives::{Address, CfgEnvWithHandlerCfg, EVMError, ResultAndState, SpecId};
+use revm::primitives::db::{Database, DatabaseRef};
+use revm::primitives::{
+    AccountInfo, Address, Bytecode, CfgEnvWithHandlerCfg, EVMError, ResultAndState, SpecId, B256,
+};
 use revm::{inspector_handle_register, Inspector};
 use revm_inspectors::tracing::{FourByteInspector, TracingInspector, TracingInspectorConfig};
 
-use crate::evm::db::EvmDb;
 use crate::evm::primitive_types::BlockEnv;
 use crate::handler::{
     citrea_handle_register, CitreaExternal, CitreaExternalExt, TracingCitreaExternal, TxInfo,
 };
 
-pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
+/// Hash that is used for the transactions that are executed but not mined,
+/// like the ones of `eth_call` and `debug_traceCall`
+pub(crate) const EPHEMERAL_TX_HASH: TxHash = TxHash::new(*b"hash_of_an_ephemeral_transaction");
+
+pub(crate) fn trace_transaction<DB>(
     opts: GethDebugTracingOptions,
     config_env: CfgEnvWithHandlerCfg,
     block_env: BlockEnv,
     tx_env: TxEnv,
     tx_hash: TxHash,
-    db: &mut EvmDb<'_, C>,
+    db: &mut DB,
     l1_fee_rate: u128,
-) -> EthResult<(GethTrace, revm::primitives::State)> {
+) -> EthResult<(GethTrace, revm::primitives::State)>
+where
+    DB: Database,
+    <DB as Database>::Error: Into<EthApiError>,
+{
     let GethDebugTracingOptions {
         config,
         tracer,
@@ -119,6 +132,72 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     Ok((frame.into(), res.state))
 }
 
+/// Traces the transaction with the parity tracer and returns the requested trace types
+pub(crate) fn trace_transaction_parity<DB>(
+    trace_types: &HashSet<TraceType>,
+    config_env: CfgEnvWithHandlerCfg,
+    block_env: BlockEnv,
+    tx_env: TxEnv,
+    tx_hash: TxHash,
+    db: &mut DB,
+    l1_fee_rate: u128,
+) -> EthResult<(TraceResults, revm::primitives::State)>
+where
+    DB: Database,
+    <DB as Database>::Error: Into<EthApiError>,
+{
+    let inspector = TracingInspector::new(TracingInspectorConfig::from_parity_config(trace_types));
+    let mut citrea_inspector = TracingCitreaExternal::new(inspector, l1_fee_rate);
+
+    let res = inspect_citrea(
+        &mut *db,
+        config_env,
+        block_env,
+        tx_env,
+        tx_hash,
+        &mut citrea_inspector,
+    )?;
+
+    // The state changes are not committed yet, so the state diff is made against the state before the transaction
+    let trace_results = citrea_inspector
+        .inspector
+        .into_parity_builder()
+        .into_trace_results_with_state(&res, trace_types, RefCellDb::new(db))
+        .map_err(Into::<EthApiError>::into)?;
+
+    Ok((trace_results, res.state))
+}
+
+/// Reads a [Database] through the [DatabaseRef] interface,
+/// which the parity tracer and in-memory caches of revm need
+pub(crate) struct RefCellDb<'a, DB>(RefCell<&'a mut DB>);
+
+impl<'a, DB> RefCellDb<'a, DB> {
+    pub(crate) fn new(db: &'a mut DB) -> Self {
+        Self(RefCell::new(db))
+    }
+}
+
+impl<'a, DB: Database> DatabaseRef for RefCellDb<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        self.0.borrow_mut().block_hash(number)
+    }
+}
+
 /// Executes the [Env] against the given [Database] without committing state changes.
 fn inspect_citrea<DB, I>(
     db: DB,
@@ -183,9 +262,8 @@ pub(crate) fn inspect_no_tracing<DB>(
 where
     DB: Database,
 {
-    let tmp_hash: TxHash = b"hash_of_an_ephemeral_transaction".into();
     let mut ext = CitreaExternal::new(l1_fee_rate);
-    ext.set_current_tx_hash(tmp_hash);
+    ext.set_current_tx_hash(EPHEMERAL_TX_HASH);
 
     let mut evm = revm::Evm::builder()
         .with_db(db)
@@ -200,7 +278,7 @@ where
     let tx_info = evm
         .context
         .external
-        .get_tx_info(tmp_hash)
+        .get_tx_info(EPHEMERAL_TX_HASH)
         .unwrap_or_default(); // default 0 in case tx was unsuccessful
     Ok((result_and_state, tx_info))
 }
@@ -57,6 +57,8 @@ fn call_multiple_test() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -165,6 +167,8 @@ fn call_test() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -244,6 +248,8 @@ fn failed_transaction_test() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         working_set,
     );
@@ -301,6 +307,8 @@ fn self_destruct_test() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -361,6 +369,8 @@ fn self_destruct_test() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -442,6 +452,8 @@ fn test_block_hash_in_evm() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -477,6 +489,8 @@ fn test_block_hash_in_evm() {
                 deposit_data: vec![],
                 l1_fee_rate,
                 timestamp: 0,
+                txs: vec![],
+                forced_txs: vec![],
             },
             &mut working_set,
         );
@@ -553,6 +567,8 @@ fn test_block_gas_limit() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -806,6 +822,8 @@ fn test_l1_fee_success() {
                 deposit_data: vec![],
                 l1_fee_rate,
                 timestamp: 0,
+                txs: vec![],
+                forced_txs: vec![],
             },
             &mut working_set,
         );
@@ -884,6 +902,8 @@ fn test_l1_fee_not_enough_funds() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -944,6 +964,8 @@ fn test_l1_fee_halt() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -73,6 +73,8 @@ impl BlockchainTestCase {
                 deposit_data: vec![],
                 l1_fee_rate: 0,
                 timestamp: 0,
+                txs: vec![],
+                forced_txs: vec![],
             },
             &mut working_set,
         );
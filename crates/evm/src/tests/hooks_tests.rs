@@ -35,6 +35,8 @@ fn begin_soft_confirmation_hook_creates_pending_block() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 54,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -70,6 +72,8 @@ fn end_soft_confirmation_hook_sets_head() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 54,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -145,6 +149,8 @@ fn end_soft_confirmation_hook_moves_transactions_and_receipts() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -250,6 +256,8 @@ fn finalize_hook_creates_final_block() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 54,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -280,6 +288,8 @@ fn finalize_hook_creates_final_block() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 54,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -366,6 +376,8 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -407,6 +419,8 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
                 deposit_data: vec![],
                 l1_fee_rate,
                 timestamp: 0,
+                txs: vec![],
+                forced_txs: vec![],
             },
             &mut working_set,
         );
@@ -429,6 +443,8 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use reth_primitives::{address, b256, BlockId, BlockNumberOrTag, TxKind, U64};
+use reth_primitives::{address, b256, Address, BlockId, BlockNumberOrTag, TxKind, U64};
 use reth_rpc::eth::error::EthApiError;
 use reth_rpc_types::request::{TransactionInput, TransactionRequest};
 use reth_rpc_types::{AnyTransactionReceipt, Block, Rich};
@@ -9,6 +9,7 @@ use serde_json::json;
 
 use crate::smart_contracts::SimpleStorageContract;
 use crate::tests::queries::init_evm;
+use crate::FeeBreakdown;
 
 #[test]
 fn get_block_by_hash_test() {
@@ -250,6 +251,95 @@ fn get_block_transaction_count_by_number_test() {
     assert_eq!(result, Ok(Some(U256::from(2))));
 }
 
+#[test]
+fn get_state_by_block_hash_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let simple_storage = address!("eeb03d20dae810f52111b853b31c8be6f30f4cd3");
+
+    let mut accessory_state = working_set.accessory_state();
+    let second_block_hash = evm
+        .blocks
+        .get(2, &mut accessory_state)
+        .unwrap()
+        .header
+        .hash();
+    let third_block_hash = evm
+        .blocks
+        .get(3, &mut accessory_state)
+        .unwrap()
+        .header
+        .hash();
+
+    let second_block = BlockId::from(second_block_hash);
+    // EIP-1898 block hash, every block is canonical
+    let third_block = serde_json::from_value::<BlockId>(json!({
+        "blockHash": third_block_hash,
+        "requireCanonical": true,
+    }))
+    .unwrap();
+
+    let nonce = evm
+        .get_transaction_count(signer.address(), Some(second_block), &mut working_set)
+        .unwrap();
+    assert_eq!(nonce, U64::from(7));
+
+    let code = evm
+        .get_code(simple_storage, Some(second_block), &mut working_set)
+        .unwrap();
+    assert!(code.is_empty());
+
+    let storage = evm
+        .get_storage_at(
+            simple_storage,
+            U256::ZERO,
+            Some(third_block),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(storage, B256::from(U256::from(478)));
+
+    let result = evm.get_balance(
+        signer.address(),
+        Some(BlockId::from(B256::from([5u8; 32]))),
+        &mut working_set,
+    );
+    assert_eq!(result, Err(EthApiError::UnknownBlockNumber.into()));
+}
+
+#[test]
+fn get_fee_breakdown_test() {
+    let (evm, mut working_set, _) = init_evm();
+
+    let result = evm.get_fee_breakdown(B256::from([5u8; 32]), &mut working_set);
+    assert_eq!(result, Ok(None));
+
+    let fee_breakdown = evm
+        .get_fee_breakdown(
+            b256!("2ff3a833e99d5a97e26f912c2e855f95e2dda542c89131fea0d189889d384d99"),
+            &mut working_set,
+        )
+        .unwrap()
+        .unwrap();
+
+    // Matches the first receipt of the third block
+    assert_eq!(
+        fee_breakdown,
+        FeeBreakdown {
+            gas_used: U64::from(0x6720),
+            base_fee_per_gas: U256::from(0x2dbf4076),
+            base_fee: U256::from(0x2dbf4076) * U256::from(0x6720),
+            base_fee_recipient: Address::ZERO,
+            priority_fee_per_gas: U256::ZERO,
+            priority_fee: U256::ZERO,
+            priority_fee_recipient: Address::ZERO,
+            l1_fee_rate: U256::from(1),
+            l1_diff_size: U64::from(0x9c),
+            l1_fee: U256::from(0x9c),
+            l1_fee_recipient: Address::ZERO,
+        }
+    );
+}
+
 #[test]
 fn call_test() {
     let (evm, mut working_set, signer) = init_evm();
@@ -274,7 +364,7 @@ fn call_test() {
             transaction_type: None,
             sidecar: None,
         },
-        Some(BlockNumberOrTag::Number(100)),
+        Some(BlockId::Number(BlockNumberOrTag::Number(100))),
         None,
         None,
         &mut working_set,
@@ -306,7 +396,7 @@ fn call_test() {
             transaction_type: None,
             sidecar: None,
         },
-        Some(BlockNumberOrTag::Number(3)),
+        Some(BlockId::Number(BlockNumberOrTag::Number(3))),
         None,
         None,
         &mut working_set,
@@ -337,7 +427,7 @@ fn call_test() {
                 sidecar: None,
             },
             // How does this work precisely? In the first block, the contract was not there?
-            Some(BlockNumberOrTag::Latest),
+            Some(BlockId::Number(BlockNumberOrTag::Latest)),
             None,
             None,
             &mut working_set,
@@ -372,7 +462,7 @@ fn call_test() {
                 sidecar: None,
             },
             // How does this work precisely? In the first block, the contract was not there?
-            Some(BlockNumberOrTag::Latest),
+            Some(BlockId::Number(BlockNumberOrTag::Latest)),
             None,
             None,
             &mut working_set,
@@ -454,6 +544,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "to": "0x819c5497b157177315e1204f52e588b393771719",
         "l1FeeRate": "0x1",
         "diffSize": "0x9c",
+        "l1DiffSize": "0x9c",
+        "l1Fee": "0x9c",
         "contractAddress": null,
         "logs": [
             {
@@ -505,6 +597,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "to": "0x819c5497b157177315e1204f52e588b393771719",
         "l1FeeRate": "0x1",
         "diffSize": "0x9c",
+        "l1DiffSize": "0x9c",
+        "l1Fee": "0x9c",
         "contractAddress": null,
         "logs": [
             {
@@ -556,6 +650,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "to": "0x819c5497b157177315e1204f52e588b393771719",
         "l1FeeRate": "0x1",
         "diffSize": "0x9c",
+        "l1DiffSize": "0x9c",
+        "l1Fee": "0x9c",
         "contractAddress": null,
         "logs": [
             {
@@ -607,6 +703,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "to": "0x819c5497b157177315e1204f52e588b393771719",
         "l1FeeRate": "0x1",
         "diffSize": "0x9c",
+        "l1DiffSize": "0x9c",
+        "l1Fee": "0x9c",
         "contractAddress": null,
         "logs": [
             {
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use jsonrpsee::core::RpcResult;
 use reth_primitives::{
-    address, b256, AccessList, AccessListItem, Address, BlockNumberOrTag, TxKind, U256,
+    address, b256, AccessList, AccessListItem, Address, BlockId, BlockNumberOrTag, TxKind, U256,
 };
 use reth_rpc::eth::error::RpcInvalidTransactionError;
 use reth_rpc_types::request::{TransactionInput, TransactionRequest};
@@ -46,7 +46,13 @@ fn payable_contract_value_test() {
         sidecar: None,
     };
 
-    let result = evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), &mut working_set);
+    let result = evm.eth_estimate_gas(
+        tx_req,
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
+        &mut working_set,
+    );
     assert_eq!(result.unwrap(), U256::from_str("0xab13").unwrap());
 }
 
@@ -79,7 +85,9 @@ fn test_tx_request_fields_gas() {
 
     let result_contract_call = evm.eth_estimate_gas(
         tx_req_contract_call.clone(),
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -88,7 +96,9 @@ fn test_tx_request_fields_gas() {
     );
     let contract_diff_size = evm.eth_estimate_diff_size(
         tx_req_contract_call.clone(),
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -105,7 +115,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_sender = evm.eth_estimate_gas(
         tx_req_no_sender,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(result_no_sender.unwrap(), U256::from_str("0x6602").unwrap());
@@ -118,7 +130,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_recipient = evm.eth_estimate_gas(
         tx_req_no_recipient,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -134,7 +148,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_gas = evm.eth_estimate_gas(
         tx_req_no_gas,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(result_no_gas.unwrap(), U256::from_str("0x6602").unwrap());
@@ -147,7 +163,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_gas_price = evm.eth_estimate_gas(
         tx_req_no_gas_price,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -163,7 +181,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_chain_id = evm.eth_estimate_gas(
         tx_req_no_chain_id,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -179,7 +199,9 @@ fn test_tx_request_fields_gas() {
 
     let result_invalid_chain_id = evm.eth_estimate_gas(
         tx_req_invalid_chain_id,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -196,7 +218,9 @@ fn test_tx_request_fields_gas() {
 
     let result_no_blob_versioned_hashes = evm.eth_estimate_gas(
         tx_req_no_blob_versioned_hashes,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -212,7 +236,9 @@ fn test_tx_request_fields_gas() {
 
     let create_no_access_list_test = evm.create_access_list(
         no_access_list_req,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
 
@@ -241,7 +267,9 @@ fn test_tx_request_fields_gas() {
 
     let access_list_gas_test = evm.eth_estimate_gas(
         access_list_req.clone(),
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
 
@@ -253,7 +281,9 @@ fn test_tx_request_fields_gas() {
 
     let already_formed_list = evm.create_access_list(
         access_list_req,
-        Some(BlockNumberOrTag::Latest),
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
         &mut working_set,
     );
 
@@ -304,11 +334,22 @@ fn test_access_list() {
         sidecar: None,
     };
 
-    let no_access_list = evm.eth_estimate_gas(tx_req_contract_call.clone(), None, &mut working_set);
+    let no_access_list = evm.eth_estimate_gas(
+        tx_req_contract_call.clone(),
+        None,
+        None,
+        None,
+        &mut working_set,
+    );
     assert_eq!(no_access_list.unwrap(), U256::from_str("0x788c").unwrap());
 
-    let form_access_list =
-        evm.create_access_list(tx_req_contract_call.clone(), None, &mut working_set);
+    let form_access_list = evm.create_access_list(
+        tx_req_contract_call.clone(),
+        None,
+        None,
+        None,
+        &mut working_set,
+    );
 
     assert_eq!(
         form_access_list.unwrap(),
@@ -333,7 +374,8 @@ fn test_access_list() {
         ..tx_req_contract_call.clone()
     };
 
-    let with_access_list = evm.eth_estimate_gas(tx_req_with_access_list, None, &mut working_set);
+    let with_access_list =
+        evm.eth_estimate_gas(tx_req_with_access_list, None, None, None, &mut working_set);
     assert_eq!(with_access_list.unwrap(), U256::from_str("0x775e").unwrap());
 }
 
@@ -380,7 +422,13 @@ fn test_estimate_gas_with_input(
         ..Default::default()
     };
 
-    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), working_set)
+    evm.eth_estimate_gas(
+        tx_req,
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
+        working_set,
+    )
 }
 
 fn test_estimate_gas_with_value(
@@ -398,5 +446,11 @@ fn test_estimate_gas_with_value(
         ..Default::default()
     };
 
-    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), working_set)
+    evm.eth_estimate_gas(
+        tx_req,
+        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+        None,
+        None,
+        working_set,
+    )
 }
@@ -2,6 +2,10 @@ mod basic_queries;
 mod estimate_gas_tests;
 mod evm_call_tests;
 mod log_tests;
+mod override_tests;
+mod proof_tests;
+mod simulate_tests;
+mod trace_call_tests;
 
 use std::str::FromStr;
 
@@ -71,6 +75,8 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 24,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -111,6 +117,8 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 24,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -152,6 +160,8 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 24,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -218,6 +228,8 @@ pub fn init_evm_single_block() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -289,6 +301,8 @@ pub fn init_evm_with_caller_contract() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -328,6 +342,8 @@ pub fn init_evm_with_caller_contract() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -0,0 +1,356 @@
+use std::collections::{BTreeMap, HashMap};
+
+use reth_primitives::{
+    address, b256, AccessList, AccessListItem, Address, BlockId, BlockNumberOrTag, Bytes, TxKind,
+    B256, U256, U64,
+};
+use reth_rpc_types::request::{TransactionInput, TransactionRequest};
+use reth_rpc_types::state::{AccountOverride, StateOverride};
+use reth_rpc_types::BlockOverrides;
+
+use crate::query::MIN_TRANSACTION_GAS;
+use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::queries::init_evm;
+
+/// Address of the simple storage contract deployed by `init_evm`, storing 478
+const SIMPLE_STORAGE: Address = address!("eeb03d20dae810f52111b853b31c8be6f30f4cd3");
+
+/// An address without balance or code
+const EMPTY_ACCOUNT: Address = address!("000000000000000000000000000000000000beef");
+
+/// Returns code that runs `opcodes` and returns the word they push to the stack
+fn code_returning(opcodes: &[u8]) -> Bytes {
+    // PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    [
+        opcodes,
+        &[0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3][..],
+    ]
+    .concat()
+    .into()
+}
+
+fn override_account(address: Address, account_override: AccountOverride) -> StateOverride {
+    HashMap::from([(address, account_override)])
+}
+
+fn call_request(from: Address, to: Address, input: Bytes) -> TransactionRequest {
+    TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(to)),
+        gas: Some(100000),
+        input: TransactionInput::new(input),
+        ..Default::default()
+    }
+}
+
+fn transfer_request(from: Address) -> TransactionRequest {
+    TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(address!(
+            "000000000000000000000000000000000000dead"
+        ))),
+        gas_price: Some(100000000),
+        value: Some(U256::from(1000)),
+        ..Default::default()
+    }
+}
+
+fn word(value: B256) -> Bytes {
+    Bytes::copy_from_slice(value.as_slice())
+}
+
+fn number(value: u64) -> Bytes {
+    word(B256::from(U256::from(value)))
+}
+
+#[test]
+fn eth_call_with_state_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let contract = SimpleStorageContract::default();
+    let get_call = || {
+        call_request(
+            signer.address(),
+            SIMPLE_STORAGE,
+            contract.get_call_data().into(),
+        )
+    };
+
+    let result = evm
+        .get_call(get_call(), None, None, None, &mut working_set)
+        .unwrap();
+    assert_eq!(result, number(478));
+
+    // Only the overridden slot changes
+    let state_diff = override_account(
+        SIMPLE_STORAGE,
+        AccountOverride {
+            state_diff: Some(HashMap::from([(B256::ZERO, B256::from(U256::from(5)))])),
+            ..Default::default()
+        },
+    );
+    let result = evm
+        .get_call(get_call(), None, Some(state_diff), None, &mut working_set)
+        .unwrap();
+    assert_eq!(result, number(5));
+
+    // The slots that are not in the overridden storage are empty
+    let state = override_account(
+        SIMPLE_STORAGE,
+        AccountOverride {
+            state: Some(HashMap::new()),
+            ..Default::default()
+        },
+    );
+    let result = evm
+        .get_call(get_call(), None, Some(state), None, &mut working_set)
+        .unwrap();
+    assert_eq!(result, number(0));
+
+    // Both can not be set for the same account
+    let state_and_state_diff = override_account(
+        SIMPLE_STORAGE,
+        AccountOverride {
+            state: Some(HashMap::new()),
+            state_diff: Some(HashMap::new()),
+            ..Default::default()
+        },
+    );
+    assert!(evm
+        .get_call(
+            get_call(),
+            None,
+            Some(state_and_state_diff),
+            None,
+            &mut working_set
+        )
+        .is_err());
+
+    // An account can be given code
+    let code = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            // SLOAD(0)
+            code: Some(code_returning(&[0x60, 0x00, 0x54])),
+            state_diff: Some(HashMap::from([(B256::ZERO, B256::from(U256::from(42)))])),
+            ..Default::default()
+        },
+    );
+    let result = evm
+        .get_call(
+            call_request(signer.address(), EMPTY_ACCOUNT, Bytes::default()),
+            None,
+            Some(code),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(result, number(42));
+
+    // An account without balance can send value when its balance is overridden
+    assert!(evm
+        .get_call(
+            transfer_request(EMPTY_ACCOUNT),
+            None,
+            None,
+            None,
+            &mut working_set
+        )
+        .is_err());
+    let balance = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            balance: Some(U256::from(10).pow(U256::from(18))),
+            ..Default::default()
+        },
+    );
+    evm.get_call(
+        transfer_request(EMPTY_ACCOUNT),
+        None,
+        Some(balance),
+        None,
+        &mut working_set,
+    )
+    .unwrap();
+}
+
+#[test]
+fn eth_call_with_block_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let mut call_code = |opcodes: &[u8], block_overrides: BlockOverrides| {
+        evm.get_call(
+            call_request(signer.address(), EMPTY_ACCOUNT, Bytes::default()),
+            Some(BlockId::Number(BlockNumberOrTag::Latest)),
+            Some(override_account(
+                EMPTY_ACCOUNT,
+                AccountOverride {
+                    code: Some(code_returning(opcodes)),
+                    ..Default::default()
+                },
+            )),
+            Some(Box::new(block_overrides)),
+            &mut working_set,
+        )
+        .unwrap()
+    };
+
+    // TIMESTAMP
+    let result = call_code(
+        &[0x42],
+        BlockOverrides {
+            time: Some(U64::from(1234)),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, number(1234));
+
+    // NUMBER
+    let result = call_code(
+        &[0x43],
+        BlockOverrides {
+            number: Some(U256::from(100)),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, number(100));
+
+    // COINBASE
+    let coinbase = address!("00000000000000000000000000000000c0ffee00");
+    let result = call_code(
+        &[0x41],
+        BlockOverrides {
+            coinbase: Some(coinbase),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, word(B256::left_padding_from(coinbase.as_slice())));
+
+    // BLOCKHASH(1)
+    let block_hash = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+    let result = call_code(
+        &[0x60, 0x01, 0x40],
+        BlockOverrides {
+            block_hash: Some(BTreeMap::from([(1, block_hash)])),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, word(block_hash));
+}
+
+#[test]
+fn estimate_gas_with_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let balance = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            balance: Some(U256::from(10).pow(U256::from(18))),
+            ..Default::default()
+        },
+    );
+    let gas = evm
+        .eth_estimate_gas(
+            transfer_request(EMPTY_ACCOUNT),
+            None,
+            Some(balance.clone()),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(gas > U256::from(MIN_TRANSACTION_GAS));
+
+    let diff_size = evm
+        .eth_estimate_diff_size(
+            TransactionRequest {
+                gas: Some(100000),
+                ..transfer_request(EMPTY_ACCOUNT)
+            },
+            None,
+            Some(balance),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(diff_size.gas, U64::from(MIN_TRANSACTION_GAS));
+
+    // A call to an account with overridden code is not estimated as a plain transfer
+    let code = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            // SLOAD(0)
+            code: Some(code_returning(&[0x60, 0x00, 0x54])),
+            ..Default::default()
+        },
+    );
+    let gas = evm
+        .eth_estimate_gas(
+            call_request(signer.address(), EMPTY_ACCOUNT, Bytes::default()),
+            None,
+            Some(code),
+            Some(Box::new(BlockOverrides {
+                time: Some(U64::from(1234)),
+                ..Default::default()
+            })),
+            &mut working_set,
+        )
+        .unwrap();
+    // The cold SLOAD alone costs more than the extra gas of the L1 fee
+    assert!(gas > U256::from(MIN_TRANSACTION_GAS + 2000));
+
+    // An overridden block gas limit is capped, an endless loop runs out of gas instead of
+    // running for u64::MAX gas
+    let endless_loop = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            // JUMPDEST PUSH1 0 JUMP
+            code: Some(Bytes::from_static(&[0x5b, 0x60, 0x00, 0x56])),
+            ..Default::default()
+        },
+    );
+    let result = evm.eth_estimate_gas(
+        TransactionRequest {
+            gas: None,
+            ..call_request(signer.address(), EMPTY_ACCOUNT, Bytes::default())
+        },
+        None,
+        Some(endless_loop),
+        Some(Box::new(BlockOverrides {
+            gas_limit: Some(U64::MAX),
+            ..Default::default()
+        })),
+        &mut working_set,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn create_access_list_with_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    // BALANCE of the simple storage contract
+    let opcodes = [&[0x73][..], SIMPLE_STORAGE.as_slice(), &[0x31]].concat();
+    let code = override_account(
+        EMPTY_ACCOUNT,
+        AccountOverride {
+            code: Some(code_returning(&opcodes)),
+            ..Default::default()
+        },
+    );
+    let access_list = evm
+        .create_access_list(
+            call_request(signer.address(), EMPTY_ACCOUNT, Bytes::default()),
+            None,
+            Some(code),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(
+        access_list.access_list,
+        AccessList(vec![AccessListItem {
+            address: SIMPLE_STORAGE,
+            storage_keys: vec![],
+        }])
+    );
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use reth_primitives::{address, Address, BlockId, BlockNumberOrTag, Bytes, KECCAK_EMPTY, U256};
+use revm::primitives::SpecId;
+use sov_modules_api::default_context::DefaultContext;
+
+use crate::tests::utils::get_evm;
+use crate::{verify_evm_proof, AccountData, EvmConfig};
+
+type C = DefaultContext;
+
+const ACCOUNT: Address = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+
+fn config() -> EvmConfig {
+    EvmConfig {
+        data: vec![AccountData {
+            address: ACCOUNT,
+            balance: U256::from(1_000_000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 3,
+            storage: HashMap::from([(U256::from(1), U256::from(42))]),
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn get_proof_of_existing_account_test() {
+    let (evm, mut working_set) = get_evm(&config());
+
+    let proof = evm
+        .get_proof(
+            ACCOUNT,
+            vec![U256::from(1), U256::from(2)],
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(proof.l2_height, 1);
+    // Block 1 is committed at version 2
+    assert_eq!(proof.state_root, working_set.get_root_hash(2).unwrap());
+    assert_eq!(proof.balance, U256::from(1_000_000));
+    assert_eq!(proof.nonce, 3);
+    assert_eq!(proof.code_hash, KECCAK_EMPTY);
+    assert!(proof.account_proof.value.is_some());
+
+    assert_eq!(proof.storage_proof[0].value, U256::from(42));
+    assert!(proof.storage_proof[0].proof.value.is_some());
+    // Slot 2 is not set, so it is proven to be absent
+    assert_eq!(proof.storage_proof[1].value, U256::ZERO);
+    assert!(proof.storage_proof[1].proof.value.is_none());
+
+    verify_evm_proof::<C>(&proof, proof.state_root).unwrap();
+
+    // Proofs do not verify against another state root
+    let genesis_root = working_set.get_root_hash(1).unwrap();
+    assert!(verify_evm_proof::<C>(&proof, genesis_root).is_err());
+
+    // Values that do not match the proofs are rejected
+    let mut tampered = proof.clone();
+    tampered.balance = U256::from(2_000_000);
+    assert!(verify_evm_proof::<C>(&tampered, proof.state_root).is_err());
+
+    let mut tampered = proof.clone();
+    tampered.storage_proof[0].value = U256::from(43);
+    assert!(verify_evm_proof::<C>(&tampered, proof.state_root).is_err());
+
+    // A proof for a slot cannot be used for another slot
+    let mut tampered = proof.clone();
+    tampered.storage_proof[1].key = U256::from(1);
+    assert!(verify_evm_proof::<C>(&tampered, proof.state_root).is_err());
+}
+
+#[test]
+fn get_proof_of_absent_account_test() {
+    let (evm, mut working_set) = get_evm(&config());
+
+    let absent = address!("0000000000000000000000000000000000000abc");
+    let proof = evm
+        .get_proof(
+            absent,
+            vec![U256::from(1)],
+            Some(BlockId::Number(BlockNumberOrTag::Number(0))),
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(proof.l2_height, 0);
+    assert_eq!(proof.state_root, working_set.get_root_hash(1).unwrap());
+    assert_eq!(proof.balance, U256::ZERO);
+    assert_eq!(proof.nonce, 0);
+    assert_eq!(proof.code_hash, KECCAK_EMPTY);
+    assert!(proof.account_proof.value.is_none());
+    assert_eq!(proof.storage_proof[0].value, U256::ZERO);
+
+    verify_evm_proof::<C>(&proof, proof.state_root).unwrap();
+
+    // An absent account cannot be claimed to have a balance
+    let mut tampered = proof.clone();
+    tampered.balance = U256::from(1);
+    assert!(verify_evm_proof::<C>(&tampered, proof.state_root).is_err());
+}
+
+#[test]
+fn get_proof_of_unknown_block_test() {
+    let (evm, mut working_set) = get_evm(&config());
+
+    assert!(evm
+        .get_proof(
+            ACCOUNT,
+            vec![],
+            Some(BlockId::Number(BlockNumberOrTag::Number(100))),
+            &mut working_set,
+        )
+        .is_err());
+}
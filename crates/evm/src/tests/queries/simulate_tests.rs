@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use reth_primitives::{address, Address, Bytes, TxKind, B256, U256, U64};
+use reth_rpc_types::request::{TransactionInput, TransactionRequest};
+use reth_rpc_types::state::AccountOverride;
+use reth_rpc_types::BlockOverrides;
+
+use crate::smart_contracts::{LogsContract, SimpleStorageContract};
+use crate::tests::queries::init_evm;
+use crate::{SimulateBlock, SimulatePayload, MAX_SIMULATED_CALLS, SIMULATED_BLOCK_TIME};
+
+const LOGS_CONTRACT: Address = address!("819c5497b157177315e1204f52e588b393771719");
+const SIMPLE_STORAGE: Address = address!("eeb03d20dae810f52111b853b31c8be6f30f4cd3");
+
+fn call(from: Address, to: Address, input: Vec<u8>) -> TransactionRequest {
+    TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(to)),
+        gas: Some(100000),
+        input: TransactionInput::new(input.into()),
+        ..Default::default()
+    }
+}
+
+fn number(value: u64) -> Bytes {
+    Bytes::copy_from_slice(B256::from(U256::from(value)).as_slice())
+}
+
+#[test]
+fn simulate_v1_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let storage = SimpleStorageContract::default();
+    let logs = LogsContract::default();
+
+    let latest_number: u64 = evm.block_number(&mut working_set).unwrap().saturating_to();
+
+    let payload = SimulatePayload {
+        block_state_calls: vec![
+            SimulateBlock {
+                calls: vec![
+                    call(signer.address(), SIMPLE_STORAGE, storage.set_call_data(5)),
+                    call(
+                        signer.address(),
+                        LOGS_CONTRACT,
+                        logs.publish_event("simulated".to_string()),
+                    ),
+                ],
+                ..Default::default()
+            },
+            SimulateBlock {
+                calls: vec![call(
+                    signer.address(),
+                    SIMPLE_STORAGE,
+                    storage.get_call_data(),
+                )],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let blocks = evm.simulate_v1(payload, None, &mut working_set).unwrap();
+    assert_eq!(blocks.len(), 2);
+
+    assert_eq!(blocks[0].number, U64::from(latest_number + 1));
+    assert_eq!(blocks[1].number, U64::from(latest_number + 2));
+    assert_eq!(
+        blocks[1].timestamp,
+        blocks[0].timestamp + U64::from(SIMULATED_BLOCK_TIME)
+    );
+    assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+
+    let set_call = &blocks[0].calls[0];
+    assert_eq!(set_call.status, U64::from(1));
+    assert_eq!(set_call.error, None);
+    // Writing a storage slot has a state diff to publish to L1
+    assert!(set_call.l1_diff_size > U64::ZERO);
+    assert!(set_call.l1_fee > U256::ZERO);
+
+    let publish_call = &blocks[0].calls[1];
+    assert_eq!(publish_call.status, U64::from(1));
+    assert!(!publish_call.logs.is_empty());
+    for (index, log) in publish_call.logs.iter().enumerate() {
+        assert_eq!(log.address(), LOGS_CONTRACT);
+        assert_eq!(log.block_hash, Some(blocks[0].hash));
+        assert_eq!(log.transaction_index, Some(1));
+        assert_eq!(log.log_index, Some(index as u64));
+    }
+    assert_eq!(
+        blocks[0].gas_used,
+        set_call.gas_used + publish_call.gas_used
+    );
+
+    // The second block sees the state changes of the first one
+    let get_call = &blocks[1].calls[0];
+    assert_eq!(get_call.return_data, number(5));
+
+    // Nothing is written to the state
+    let stored = evm
+        .get_call(
+            call(signer.address(), SIMPLE_STORAGE, storage.get_call_data()),
+            None,
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(stored, number(478));
+}
+
+#[test]
+fn simulate_v1_with_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let storage = SimpleStorageContract::default();
+
+    let payload = SimulatePayload {
+        block_state_calls: vec![
+            SimulateBlock {
+                block_overrides: Some(BlockOverrides {
+                    number: Some(U256::from(100)),
+                    time: Some(U64::from(1000)),
+                    ..Default::default()
+                }),
+                state_overrides: Some(HashMap::from([(
+                    SIMPLE_STORAGE,
+                    AccountOverride {
+                        state_diff: Some(HashMap::from([(B256::ZERO, B256::from(U256::from(7)))])),
+                        ..Default::default()
+                    },
+                )])),
+                calls: vec![],
+            },
+            SimulateBlock {
+                calls: vec![call(
+                    signer.address(),
+                    SIMPLE_STORAGE,
+                    storage.get_call_data(),
+                )],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let blocks = evm.simulate_v1(payload, None, &mut working_set).unwrap();
+
+    assert_eq!(blocks[0].number, U64::from(100));
+    assert_eq!(blocks[0].timestamp, U64::from(1000));
+    assert_eq!(blocks[0].gas_used, U64::ZERO);
+    assert_eq!(blocks[1].number, U64::from(101));
+    assert_eq!(blocks[1].timestamp, U64::from(1000 + SIMULATED_BLOCK_TIME));
+    // State overrides of a block stay in the next blocks
+    assert_eq!(blocks[1].calls[0].return_data, number(7));
+
+    // Blocks can not go back in time
+    let payload = SimulatePayload {
+        block_state_calls: vec![
+            SimulateBlock::default(),
+            SimulateBlock {
+                block_overrides: Some(BlockOverrides {
+                    number: Some(U256::from(1)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    assert!(evm.simulate_v1(payload, None, &mut working_set).is_err());
+}
+
+#[test]
+fn simulate_v1_failed_calls_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let storage = SimpleStorageContract::default();
+
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimulateBlock {
+            calls: vec![call(
+                signer.address(),
+                SIMPLE_STORAGE,
+                storage.failing_function_call_data(),
+            )],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let blocks = evm.simulate_v1(payload, None, &mut working_set).unwrap();
+
+    let failed_call = &blocks[0].calls[0];
+    assert_eq!(failed_call.status, U64::ZERO);
+    assert_eq!(failed_call.error.as_ref().unwrap().code, 3);
+
+    // With validation, calls are checked like transactions, and the nonce of the signer is not 0
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimulateBlock {
+            calls: vec![TransactionRequest {
+                nonce: Some(0),
+                ..call(signer.address(), SIMPLE_STORAGE, storage.get_call_data())
+            }],
+            ..Default::default()
+        }],
+        validation: true,
+        ..Default::default()
+    };
+    assert!(evm.simulate_v1(payload, None, &mut working_set).is_err());
+
+    let payload = SimulatePayload {
+        trace_transfers: true,
+        ..Default::default()
+    };
+    assert!(evm.simulate_v1(payload, None, &mut working_set).is_err());
+
+    // Only the call that is left without gas fails
+    let get_call = TransactionRequest {
+        gas: None,
+        ..call(signer.address(), SIMPLE_STORAGE, storage.get_call_data())
+    };
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimulateBlock {
+            block_overrides: Some(BlockOverrides {
+                gas_limit: Some(U64::from(30000)),
+                ..Default::default()
+            }),
+            calls: vec![get_call.clone(), get_call.clone()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let blocks = evm.simulate_v1(payload, None, &mut working_set).unwrap();
+    assert_eq!(blocks[0].calls[0].status, U64::from(1));
+    assert_eq!(blocks[0].calls[1].status, U64::ZERO);
+    assert_eq!(blocks[0].calls[1].error.as_ref().unwrap().code, -38015);
+
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimulateBlock {
+            calls: vec![get_call; MAX_SIMULATED_CALLS + 1],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(evm.simulate_v1(payload, None, &mut working_set).is_err());
+}
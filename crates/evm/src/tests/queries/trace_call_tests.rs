@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxKind, U256};
+use reth_rpc_types::request::{TransactionInput, TransactionRequest};
+use reth_rpc_types::state::AccountOverride;
+use reth_rpc_types::trace::geth::{
+    CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethTrace,
+};
+use reth_rpc_types::{Bundle, StateContext};
+
+use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::queries::init_evm;
+
+fn call_tracer_opts() -> GethDebugTracingCallOptions {
+    GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn into_call_frame(trace: GethTrace) -> CallFrame {
+    match trace {
+        GethTrace::CallTracer(call_frame) => call_frame,
+        trace => panic!("Expected a call trace, got {:?}", trace),
+    }
+}
+
+fn contract_address() -> Address {
+    Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap()
+}
+
+fn storage_call(from: Address, input: Bytes) -> TransactionRequest {
+    TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(contract_address())),
+        gas: Some(100000),
+        gas_price: Some(100000000),
+        input: TransactionInput::new(input),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn trace_call_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let contract = SimpleStorageContract::default();
+
+    let trace = evm
+        .trace_call(
+            storage_call(signer.address(), contract.get_call_data().into()),
+            Some(BlockId::Number(BlockNumberOrTag::Latest)),
+            Some(call_tracer_opts()),
+            &mut working_set,
+        )
+        .unwrap();
+
+    let call_frame = into_call_frame(trace);
+    assert_eq!(call_frame.from, signer.address());
+    assert_eq!(call_frame.to, Some(contract_address()));
+    assert_eq!(call_frame.error, None);
+    assert_eq!(
+        call_frame.output,
+        Some(
+            Bytes::from_str("0x00000000000000000000000000000000000000000000000000000000000001de")
+                .unwrap()
+        )
+    );
+
+    // The default struct logger is also supported
+    let trace = evm
+        .trace_call(
+            storage_call(signer.address(), contract.get_call_data().into()),
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(matches!(trace, GethTrace::Default(_)));
+}
+
+#[test]
+fn trace_call_with_state_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let poor_address = Address::from_str("0x000000000000000000000000000000000000beef").unwrap();
+    let transfer = TransactionRequest {
+        from: Some(poor_address),
+        to: Some(TxKind::Call(
+            Address::from_str("0x000000000000000000000000000000000000dead").unwrap(),
+        )),
+        gas: Some(100000),
+        gas_price: Some(100000000),
+        value: Some(U256::from(1000)),
+        ..Default::default()
+    };
+
+    // The account has no balance to transfer
+    assert!(evm
+        .trace_call(
+            transfer.clone(),
+            None,
+            Some(call_tracer_opts()),
+            &mut working_set
+        )
+        .is_err());
+
+    let opts = GethDebugTracingCallOptions {
+        state_overrides: Some(HashMap::from([(
+            poor_address,
+            AccountOverride {
+                balance: Some(U256::from(10).pow(U256::from(18))),
+                ..Default::default()
+            },
+        )])),
+        ..call_tracer_opts()
+    };
+    let call_frame = into_call_frame(
+        evm.trace_call(transfer, None, Some(opts), &mut working_set)
+            .unwrap(),
+    );
+    assert_eq!(call_frame.value, Some(U256::from(1000)));
+    assert_eq!(call_frame.error, None);
+
+    // Overriding the whole storage of the contract clears the stored value
+    let contract = SimpleStorageContract::default();
+    let opts = GethDebugTracingCallOptions {
+        state_overrides: Some(HashMap::from([(
+            contract_address(),
+            AccountOverride {
+                state: Some(HashMap::new()),
+                ..Default::default()
+            },
+        )])),
+        ..call_tracer_opts()
+    };
+    let call_frame = into_call_frame(
+        evm.trace_call(
+            storage_call(signer.address(), contract.get_call_data().into()),
+            None,
+            Some(opts),
+            &mut working_set,
+        )
+        .unwrap(),
+    );
+    assert_eq!(call_frame.output, Some(Bytes::from(vec![0u8; 32])));
+
+    // An account can not override both its whole storage and some of its slots
+    let opts = GethDebugTracingCallOptions {
+        state_overrides: Some(HashMap::from([(
+            contract_address(),
+            AccountOverride {
+                state: Some(HashMap::new()),
+                state_diff: Some(HashMap::new()),
+                ..Default::default()
+            },
+        )])),
+        ..call_tracer_opts()
+    };
+    assert!(evm
+        .trace_call(
+            storage_call(signer.address(), contract.get_call_data().into()),
+            None,
+            Some(opts),
+            &mut working_set,
+        )
+        .is_err());
+}
+
+#[test]
+fn trace_call_many_test() {
+    let (evm, mut working_set, signer) = init_evm();
+    let contract = SimpleStorageContract::default();
+
+    let bundles = vec![
+        Bundle {
+            transactions: vec![storage_call(
+                signer.address(),
+                contract.set_call_data(5).into(),
+            )],
+            block_override: None,
+        },
+        Bundle {
+            transactions: vec![storage_call(
+                signer.address(),
+                contract.get_call_data().into(),
+            )],
+            block_override: None,
+        },
+    ];
+
+    let traces = evm
+        .trace_call_many(
+            bundles,
+            Some(StateContext {
+                block_number: Some(BlockId::Number(BlockNumberOrTag::Latest)),
+                transaction_index: None,
+            }),
+            Some(call_tracer_opts()),
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(traces.len(), 2);
+    assert_eq!(traces[0].len(), 1);
+    assert_eq!(traces[1].len(), 1);
+
+    // The second call sees the value set by the first one
+    let call_frame = into_call_frame(traces[1][0].clone());
+    assert_eq!(
+        call_frame.output,
+        Some(
+            Bytes::from_str("0x0000000000000000000000000000000000000000000000000000000000000005")
+                .unwrap()
+        )
+    );
+}
@@ -147,6 +147,8 @@ fn test_sys_bitcoin_light_client() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 42,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -284,6 +286,8 @@ fn test_sys_tx_gas_usage_effect_on_block_gas_limit() {
             deposit_data: vec![],
             l1_fee_rate: 1,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -315,6 +319,8 @@ fn test_sys_tx_gas_usage_effect_on_block_gas_limit() {
             deposit_data: vec![],
             l1_fee_rate,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -452,6 +458,8 @@ fn test_bridge() {
             .to_vec()],
             l1_fee_rate: 1,
             timestamp: 0,
+            txs: vec![],
+            forced_txs: vec![],
         },
         &mut working_set,
     );
@@ -8,6 +8,7 @@ use backoff::future::retry as retry_backoff;
 use backoff::ExponentialBackoffBuilder;
 use borsh::de::BorshDeserialize;
 use borsh::BorshSerialize as _;
+use futures::StreamExt;
 use jsonrpsee::core::client::Error as JsonrpseeError;
 use jsonrpsee::RpcModule;
 use lru::LruCache;
@@ -19,14 +20,14 @@ use sov_db::schema::types::{BatchNumber, SlotNumber, StoredSoftBatch, StoredStat
 use sov_modules_api::Context;
 use sov_modules_stf_blueprint::StfBlueprintTrait;
 use sov_rollup_interface::da::{
-    BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, SequencerCommitment,
+    BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, ForcedTransaction, SequencerCommitment,
 };
 use sov_rollup_interface::rpc::SoftConfirmationStatus;
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, FinalizedHeaderEvent, SlotData};
 pub use sov_rollup_interface::stf::BatchReceipt;
 use sov_rollup_interface::stf::{SoftBatchReceipt, StateTransitionFunction};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
-use sov_rollup_interface::zk::{Proof, Zkvm, ZkvmHost};
+use sov_rollup_interface::zk::{Proof, StateTransition, Zkvm, ZkvmHost};
 use sov_stf_runner::{InitVariant, RollupPublicKeys, RpcConfig, RunnerConfig};
 use tokio::select;
 use tokio::sync::{mpsc, oneshot, Mutex};
@@ -55,6 +56,20 @@ where
             by_hash: LruCache::new(NonZeroUsize::new(10).unwrap()),
         }
     }
+
+    /// Evicts the blocks above `height` looked up by number, as they were reorged out.
+    /// Blocks looked up by hash stay valid.
+    fn evict_above(&mut self, height: u64) {
+        let reorged: Vec<u64> = self
+            .by_number
+            .iter()
+            .map(|(number, _)| *number)
+            .filter(|number| *number > height)
+            .collect();
+        for number in reorged {
+            self.by_number.pop(&number);
+        }
+    }
 }
 
 /// Citrea's own STF runner implementation.
@@ -86,6 +101,8 @@ where
     accept_public_input_as_proven: bool,
     l1_block_cache: Arc<Mutex<L1BlockCache<Da>>>,
     sync_blocks_count: u64,
+    reconstruct_from_da: bool,
+    applied_state_diffs: u64,
 }
 
 impl<Stf, Sm, Da, Vm, C> CitreaFullnode<Stf, Sm, Da, Vm, C>
@@ -146,6 +163,15 @@ where
         let start_l1_height = item_numbers.slot_number;
         let start_l2_height = item_numbers.soft_batch_number;
 
+        let reconstruct_from_da = runner_config.reconstruct_from_da.unwrap_or(false);
+        let (start_l1_height, applied_state_diffs) =
+            match ledger_db.get_da_reconstruction_progress()? {
+                Some((l1_height, applied_state_diffs)) if reconstruct_from_da => {
+                    (l1_height.0, applied_state_diffs)
+                }
+                _ => (start_l1_height, 0),
+            };
+
         Ok(Self {
             start_l1_height,
             start_l2_height,
@@ -155,7 +181,7 @@ where
             ledger_db,
             state_root: prev_state_root,
             rpc_config,
-            sequencer_client: SequencerClient::new(runner_config.sequencer_client_url),
+            sequencer_client: SequencerClient::new(runner_config.sequencer_client_urls),
             sequencer_pub_key: public_keys.sequencer_public_key,
             sequencer_da_pub_key: public_keys.sequencer_da_pub_key,
             prover_da_pub_key: public_keys.prover_da_pub_key,
@@ -167,6 +193,8 @@ where
                 .unwrap_or(false),
             sync_blocks_count,
             l1_block_cache: Arc::new(Mutex::new(L1BlockCache::new())),
+            reconstruct_from_da,
+            applied_state_diffs,
         })
     }
 
@@ -220,17 +248,13 @@ where
         });
     }
 
-    async fn process_zk_proof(
+    /// Verifies a ZK proof found on DA and extracts its state transition.
+    fn verify_zk_proof(
         &self,
-        l1_block: Da::FilteredBlock,
-        proof: Proof,
-    ) -> Result<(), SyncError> {
-        tracing::info!(
-            "Processing zk proof at height: {}",
-            l1_block.header().height()
-        );
-        tracing::debug!("ZK proof: {:?}", proof);
-        let state_transition = match proof.clone() {
+        l1_block: &Da::FilteredBlock,
+        proof: &Proof,
+    ) -> Result<StateTransition<Da::Spec, Stf::StateRoot>, SyncError> {
+        let state_transition = match proof {
             Proof::Full(proof) => {
                 let code_commitment = self.code_commitment.clone();
 
@@ -272,15 +296,22 @@ where
             }
         };
 
-        let stored_state_transition = StoredStateTransition {
-            initial_state_root: state_transition.initial_state_root.as_ref().to_vec(),
-            final_state_root: state_transition.final_state_root.as_ref().to_vec(),
-            state_diff: state_transition.state_diff,
-            da_slot_hash: state_transition.da_slot_hash.clone().into(),
-            sequencer_public_key: state_transition.sequencer_public_key,
-            sequencer_da_public_key: state_transition.sequencer_da_public_key,
-            validity_condition: state_transition.validity_condition.try_to_vec().unwrap(),
-        };
+        Ok(state_transition)
+    }
+
+    async fn process_zk_proof(
+        &self,
+        l1_block: Da::FilteredBlock,
+        proof: Proof,
+    ) -> Result<(), SyncError> {
+        tracing::info!(
+            "Processing zk proof at height: {}",
+            l1_block.header().height()
+        );
+        tracing::debug!("ZK proof: {:?}", proof);
+        let state_transition = self.verify_zk_proof(&l1_block, &proof)?;
+
+        let stored_state_transition = to_stored_state_transition(&state_transition);
 
         let l1_hash = state_transition.da_slot_hash.into();
 
@@ -384,6 +415,107 @@ where
         Ok(())
     }
 
+    /// Applies the state diff of a verified ZK proof directly to the storage,
+    /// checking the resulting root against the proof's final state root.
+    /// Applies the state diffs proven by the prover in the L1 block.
+    /// They are finalized and the progress is saved only when all of them are applied,
+    /// otherwise none is kept and the L1 block is scanned again on restart.
+    fn apply_proven_state_diffs(&mut self, l1_block: &Da::FilteredBlock) -> anyhow::Result<()> {
+        let first_height = self.applied_state_diffs + 1;
+        let state_root = self.state_root.clone();
+
+        let mut verified_proofs = vec![];
+        for mut blob in self.da_service.extract_relevant_blobs(l1_block) {
+            if blob.sender().as_ref() != self.prover_da_pub_key.as_slice() {
+                continue;
+            }
+            match DaData::try_from_slice(blob.full_data()) {
+                Ok(DaData::ZKProof(proof)) => {
+                    match self.apply_proven_state_diff(l1_block, &proof) {
+                        Ok(state_transition) => verified_proofs.push((proof, state_transition)),
+                        Err(e) => {
+                            self.storage_manager.rollback_l2(first_height)?;
+                            self.applied_state_diffs = first_height - 1;
+                            self.state_root = state_root;
+                            bail!(
+                                "Could not apply proven state diff from DA block #{}: {:?}",
+                                l1_block.header().height(),
+                                e
+                            );
+                        }
+                    }
+                }
+                data => {
+                    warn!(
+                        "Found broken DA data in block 0x{}: {:?}",
+                        hex::encode(l1_block.hash()),
+                        data
+                    );
+                }
+            }
+        }
+
+        for height in first_height..=self.applied_state_diffs {
+            self.storage_manager.finalize_l2(height)?;
+        }
+        // A crash before the progress is saved leaves the storage ahead of it, which is refused on startup
+        self.ledger_db
+            .update_verified_proofs_and_da_reconstruction_progress(
+                l1_block.header().height(),
+                verified_proofs,
+                self.applied_state_diffs,
+            )?;
+
+        Ok(())
+    }
+
+    fn apply_proven_state_diff(
+        &mut self,
+        l1_block: &Da::FilteredBlock,
+        proof: &Proof,
+    ) -> Result<StoredStateTransition, SyncError> {
+        let state_transition = self.verify_zk_proof(l1_block, proof)?;
+
+        if state_transition.initial_state_root.as_ref() != self.state_root.as_ref() {
+            return Err(anyhow!(
+                "DA reconstruction: Initial state root mismatch - expected 0x{} but got 0x{}",
+                hex::encode(self.state_root.as_ref()),
+                hex::encode(state_transition.initial_state_root.as_ref())
+            )
+            .into());
+        }
+
+        // Each applied state diff gets its own storage version, as a soft batch would
+        let height = self.applied_state_diffs + 1;
+        let pre_state = self.storage_manager.create_storage_on_l2_height(height)?;
+        let (state_root, change_set) = self
+            .stf
+            .apply_state_diff(pre_state, state_transition.state_diff.clone())?;
+
+        if state_root.as_ref() != state_transition.final_state_root.as_ref() {
+            self.storage_manager.rollback_l2(height)?;
+            return Err(anyhow!(
+                "DA reconstruction: Final state root mismatch - expected 0x{} but got 0x{}",
+                hex::encode(state_transition.final_state_root.as_ref()),
+                hex::encode(state_root.as_ref())
+            )
+            .into());
+        }
+
+        self.storage_manager.save_change_set_l2(height, change_set)?;
+
+        self.applied_state_diffs = height;
+        self.state_root = state_transition.final_state_root.clone();
+
+        info!(
+            "New State Root after applying proven state diff from DA block #{} is: {:?}",
+            l1_block.header().height(),
+            self.state_root
+        );
+
+        Ok(to_stored_state_transition(&state_transition))
+    }
+
     async fn process_sequencer_commitment(
         &self,
         l1_block: Da::FilteredBlock,
@@ -510,6 +642,12 @@ where
 
         let mut data_to_commit = SlotCommit::new(current_l1_block.clone());
 
+        let mut da_data = self.da_service.extract_relevant_blobs(&current_l1_block);
+        da_data.iter_mut().for_each(|blob| {
+            blob.full_data();
+        });
+        let forced_transactions = ForcedTransaction::from_blobs(&da_data);
+
         let pre_state = self
             .storage_manager
             .create_storage_on_l2_height(l2_height)?;
@@ -522,6 +660,7 @@ where
             Default::default(),
             current_l1_block.header(),
             &current_l1_block.validity_condition(),
+            &forced_transactions,
             &mut soft_batch.clone().into(),
         );
 
@@ -579,6 +718,10 @@ where
     /// Runs the rollup.
     #[instrument(level = "trace", skip_all, err)]
     pub async fn run(&mut self) -> Result<(), anyhow::Error> {
+        if self.reconstruct_from_da {
+            return self.run_da_reconstruction().await;
+        }
+
         let (l1_tx, mut l1_rx) = mpsc::channel(1);
         let l1_sync_worker = l1_sync(
             self.start_l1_height,
@@ -646,9 +789,12 @@ where
                                         data
                                     );
                                 }
-                            } else {
-                                warn!("Force transactions are not implemented yet");
-                                // TODO: This is where force transactions will land - try to parse DA data force transaction
+                            } else if let Ok(DaData::ForcedTransaction(_)) = data {
+                                // Applied with the soft confirmations built on this block
+                                debug!(
+                                    "Found forced transaction in block 0x{}",
+                                    hex::encode(l1_block.hash())
+                                );
                             }
                         });
 
@@ -695,7 +841,8 @@ where
                 },
                 Some(l2_blocks) = l2_rx.recv() => {
                     for (l2_height, l2_block) in l2_blocks {
-                        let l1_block = get_da_block_at_height(&self.da_service, l2_block.da_slot_height, self.l1_block_cache.clone()).await?;
+                        // Looked up by hash, soft batches of the sequencer stay on L1 blocks that were reorged out
+                        let l1_block = get_da_block_by_hash(&self.da_service, l2_block.da_slot_hash, self.l1_block_cache.clone()).await?;
                         if let Err(e) = self.process_l2_block(l2_height, l2_block, l1_block).await {
                             error!("Could not process L2 block: {}", e);
                         }
@@ -704,6 +851,33 @@ where
             }
         }
     }
+
+    /// Reconstructs the state only from verified ZK proofs found on DA, without syncing soft batches.
+    /// Only provable state is rebuilt, as proofs do not carry accessory state.
+    async fn run_da_reconstruction(&mut self) -> Result<(), anyhow::Error> {
+        info!(
+            "Reconstructing state from DA after L1 height {} with {} state diffs applied",
+            self.start_l1_height, self.applied_state_diffs
+        );
+
+        let (l1_tx, mut l1_rx) = mpsc::channel(1);
+        let l1_sync_worker = l1_sync(
+            self.start_l1_height,
+            self.da_service.clone(),
+            l1_tx,
+            self.l1_block_cache.clone(),
+        );
+        tokio::pin!(l1_sync_worker);
+
+        loop {
+            select! {
+                _ = &mut l1_sync_worker => {},
+                Some(l1_block) = l1_rx.recv() => {
+                    self.apply_proven_state_diffs(&l1_block)?;
+                },
+            }
+        }
+    }
 }
 
 async fn l1_sync<Da>(
@@ -717,6 +891,18 @@ async fn l1_sync<Da>(
     let mut l1_height = start_l1_height;
     info!("Starting to sync from L1 height {}", l1_height);
 
+    // Reorgs of finalized L1 blocks are received from the header stream
+    let header_stream = loop {
+        match da_service.subscribe_finalized_header().await {
+            Ok(header_stream) => break header_stream,
+            Err(e) => {
+                error!("Could not subscribe to finalized L1 headers: {}", e);
+                sleep(Duration::from_secs(2)).await;
+            }
+        }
+    };
+    tokio::pin!(header_stream);
+
     'block_sync: loop {
         // TODO: for a node, the da block at slot_height might not have been finalized yet
         // should wait for it to be finalized
@@ -754,7 +940,37 @@ async fn l1_sync<Da>(
             }
         }
 
-        sleep(Duration::from_secs(2)).await;
+        // Wait for the next poll, handling the reorgs reported meanwhile
+        let next_poll = sleep(Duration::from_secs(2));
+        tokio::pin!(next_poll);
+        loop {
+            select! {
+                _ = &mut next_poll => break,
+                Some(event) = header_stream.next() => {
+                    let common_ancestor = match event {
+                        Ok(FinalizedHeaderEvent::Reorg { common_ancestor, reorged }) => {
+                            warn!(
+                                "L1 reorged {} finalized blocks above height {}",
+                                reorged.len(),
+                                common_ancestor.height()
+                            );
+                            common_ancestor
+                        }
+                        Ok(FinalizedHeaderEvent::Finalized(_)) => continue,
+                        Err(e) => {
+                            error!("Finalized L1 header stream error: {}", e);
+                            continue;
+                        }
+                    };
+                    // The blocks of the new fork are fetched again and sent on the next poll
+                    l1_block_cache
+                        .lock()
+                        .await
+                        .evict_above(common_ancestor.height());
+                    l1_height = l1_height.min(common_ancestor.height());
+                }
+            }
+        }
     }
 }
 
@@ -809,13 +1025,16 @@ async fn sync_l2<Da>(
 
         if soft_batches.is_empty() {
             debug!(
-                "Soft Batch: no batch at starting height {}, retrying...",
+                "Soft Batch: no batch at starting height {}, switching to subscription",
                 l2_height
             );
 
-            // We wait for 2 seconds and then return a Permanent error so that we exit the retry.
-            // This should not backoff exponentially
-            sleep(Duration::from_secs(1)).await;
+            // We are caught up, so stream new batches as the sequencer commits them.
+            // Any gap or disconnect drops us back to range polling above.
+            if let Err(e) = follow_soft_batches(&sequencer_client, &mut l2_height, &sender).await {
+                debug!("Soft Batch: subscription unavailable: {:?}", e);
+                sleep(Duration::from_secs(1)).await;
+            }
             continue;
         }
 
@@ -832,6 +1051,55 @@ async fn sync_l2<Da>(
     }
 }
 
+/// Forwards soft batches pushed by `citrea_subscribeSoftBatches` for as long as they arrive
+/// in order. Returns once the subscription ends or skips a height, leaving `l2_height` at the
+/// next height to sync so that range polling can pick up from there.
+async fn follow_soft_batches(
+    sequencer_client: &SequencerClient,
+    l2_height: &mut u64,
+    sender: &mpsc::Sender<Vec<(u64, GetSoftBatchResponse)>>,
+) -> anyhow::Result<()> {
+    let mut subscription = sequencer_client.subscribe_soft_batches().await?;
+    info!("Subscribed to soft batches at L2 height {}", l2_height);
+
+    while let Some(notification) = subscription.next().await {
+        let (height, soft_batch) = notification?;
+        if height < *l2_height {
+            continue;
+        }
+        if height > *l2_height {
+            warn!(
+                "Soft Batch: subscription skipped from height {} to {}, falling back to polling",
+                l2_height, height
+            );
+            return Ok(());
+        }
+
+        *l2_height += 1;
+
+        if let Err(e) = sender.send(vec![(height, soft_batch)]).await {
+            error!("Could not notify about L2 block: {}", e);
+        }
+    }
+
+    debug!("Soft Batch: subscription closed, falling back to polling");
+    Ok(())
+}
+
+fn to_stored_state_transition<Da: DaSpec, Root: AsRef<[u8]>>(
+    state_transition: &StateTransition<Da, Root>,
+) -> StoredStateTransition {
+    StoredStateTransition {
+        initial_state_root: state_transition.initial_state_root.as_ref().to_vec(),
+        final_state_root: state_transition.final_state_root.as_ref().to_vec(),
+        state_diff: state_transition.state_diff.clone(),
+        da_slot_hash: state_transition.da_slot_hash.clone().into(),
+        sequencer_public_key: state_transition.sequencer_public_key.clone(),
+        sequencer_da_public_key: state_transition.sequencer_da_public_key.clone(),
+        validity_condition: state_transition.validity_condition.try_to_vec().unwrap(),
+    }
+}
+
 async fn get_da_block_at_height<Da: DaService>(
     da_service: &Da,
     height: u64,
@@ -18,7 +18,9 @@ use sov_db::schema::types::{BatchNumber, SlotNumber, StoredStateTransition};
 use sov_modules_api::storage::HierarchicalStorageManager;
 use sov_modules_api::{BlobReaderTrait, Context, SignedSoftConfirmationBatch, SlotData};
 use sov_modules_stf_blueprint::StfBlueprintTrait;
-use sov_rollup_interface::da::{BlockHeaderTrait, DaData, DaSpec, SequencerCommitment};
+use sov_rollup_interface::da::{
+    BlockHeaderTrait, DaData, DaSpec, ForcedTransaction, SequencerCommitment,
+};
 use sov_rollup_interface::rpc::SoftConfirmationStatus;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::{SoftBatchReceipt, StateTransitionFunction};
@@ -130,7 +132,7 @@ where
             state_root: prev_state_root,
             rpc_config,
             prover_service,
-            sequencer_client: SequencerClient::new(runner_config.sequencer_client_url),
+            sequencer_client: SequencerClient::new(runner_config.sequencer_client_urls),
             sequencer_pub_key: public_keys.sequencer_public_key,
             sequencer_da_pub_key: public_keys.sequencer_da_pub_key,
             prover_da_pub_key: public_keys.prover_da_pub_key,
@@ -283,9 +285,18 @@ where
                                 data
                             );
                         }
+                    } else if let Ok(DaData::ForcedTransaction(_)) = data {
+                        // Applied with the soft confirmations built on this block
+                        debug!(
+                            "Found forced transaction in block 0x{}",
+                            hex::encode(filtered_block.hash())
+                        );
                     } else {
-                        warn!("Force transactions are not implemented yet");
-                        // TODO: This is where force transactions will land - try to parse DA data force transaction
+                        warn!(
+                            "Found broken DA data in block 0x{}: {:?}",
+                            hex::encode(filtered_block.hash()),
+                            data
+                        );
                     }
                 });
 
@@ -336,6 +347,9 @@ where
             let mut da_block_headers_of_soft_confirmations: VecDeque<
                 Vec<<<Da as DaService>::Spec as DaSpec>::BlockHeader>,
             > = VecDeque::new();
+            let mut da_data_of_soft_confirmations = VecDeque::new();
+            let mut inclusion_proofs_of_soft_confirmations = VecDeque::new();
+            let mut completeness_proofs_of_soft_confirmations = VecDeque::new();
 
             let mut traversed_l1_tuples = vec![];
 
@@ -345,6 +359,10 @@ where
                 let mut da_block_headers_to_push: Vec<
                     <<Da as DaService>::Spec as DaSpec>::BlockHeader,
                 > = vec![];
+                let mut da_data_to_push = vec![];
+                let mut inclusion_proofs_to_push = vec![];
+                let mut completeness_proofs_to_push = vec![];
+                let mut forced_transactions = vec![];
 
                 let start_l1_height = retry_backoff(exponential_backoff.clone(), || async {
                     da_service
@@ -436,10 +454,11 @@ where
 
                     sof_soft_confirmations_to_push.push(signed_soft_confirmation.clone());
 
-                    // The filtered block of soft batch, which is the block at the da_slot_height of soft batch
+                    // The filtered block of soft batch, which is the block with the da_slot_hash of soft batch.
+                    // It may not be on the best fork if the sequencer built on it before an L1 reorg.
                     let filtered_block = retry_backoff(exponential_backoff.clone(), || async {
                         da_service
-                            .get_block_at(soft_batch.da_slot_height)
+                            .get_block_by_hash(soft_batch.da_slot_hash)
                             .await
                             .map_err(backoff::Error::transient)
                     })
@@ -450,6 +469,21 @@ where
                             != filtered_block.header().height()
                     {
                         da_block_headers_to_push.push(filtered_block.header().clone());
+
+                        let mut da_data_of_block =
+                            da_service.extract_relevant_blobs(&filtered_block);
+                        let (inclusion_proof, completeness_proof) = da_service
+                            .get_extraction_proof(&filtered_block, &da_data_of_block)
+                            .await;
+                        // the zk circuit reads the forced transactions from the verified data
+                        da_data_of_block.iter_mut().for_each(|blob| {
+                            blob.full_data();
+                        });
+                        forced_transactions = ForcedTransaction::from_blobs(&da_data_of_block);
+
+                        da_data_to_push.push(da_data_of_block);
+                        inclusion_proofs_to_push.push(inclusion_proof);
+                        completeness_proofs_to_push.push(completeness_proof);
                     }
 
                     let mut data_to_commit = SlotCommit::new(filtered_block.clone());
@@ -466,6 +500,7 @@ where
                         Default::default(),
                         filtered_block.header(),
                         &filtered_block.validity_condition(),
+                        &forced_transactions,
                         &mut signed_soft_confirmation,
                     );
 
@@ -524,6 +559,9 @@ where
                 soft_confirmations.push_back(sof_soft_confirmations_to_push);
                 state_transition_witnesses.push_back(state_transition_witnesses_to_push);
                 da_block_headers_of_soft_confirmations.push_back(da_block_headers_to_push);
+                da_data_of_soft_confirmations.push_back(da_data_to_push);
+                inclusion_proofs_of_soft_confirmations.push_back(inclusion_proofs_to_push);
+                completeness_proofs_of_soft_confirmations.push_back(completeness_proofs_to_push);
             }
 
             info!("Sending for proving");
@@ -541,6 +579,9 @@ where
                     soft_confirmations,
                     state_transition_witnesses,
                     da_block_headers_of_soft_confirmations,
+                    da_data_of_soft_confirmations,
+                    inclusion_proofs_of_soft_confirmations,
+                    completeness_proofs_of_soft_confirmations,
 
                     sequencer_public_key: self.sequencer_pub_key.clone(),
                     sequencer_da_public_key: self.sequencer_da_pub_key.clone(),
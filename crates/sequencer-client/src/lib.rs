@@ -1,29 +1,124 @@
 use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use jsonrpsee::core::client::{ClientT, Error};
+use jsonrpsee::core::client::{ClientT, Error, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::ArrayParams;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
-use reth_primitives::{Bytes, B256};
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use reth_primitives::{Address, Bytes, B256};
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use sov_rollup_interface::rpc::HexTx;
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
-/// Configuration for SequencerClient.
+/// Consecutive failures after which an upstream is sidelined in favour of the others.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// How long a sidelined upstream is tried only as a last resort.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Client for the upstream L2 sources: the sequencer and any trusted full nodes.
+/// Requests go to the healthiest upstream and fail over to the next one on connection errors.
+/// Soft batches are verified against the sequencer signature, so any upstream is safe to sync from.
 #[derive(Debug, Clone)]
 pub struct SequencerClient {
-    /// Host config for soft confirmation
-    pub rpc_url: String,
-    /// Client object for soft confirmation
-    pub client: HttpClient,
+    /// Upstreams in order of preference, sharing health across clones
+    upstreams: Arc<[Upstream]>,
+}
+
+#[derive(Debug)]
+struct Upstream {
+    rpc_url: String,
+    client: HttpClient,
+    health: Mutex<UpstreamHealth>,
+}
+
+#[derive(Debug, Default)]
+struct UpstreamHealth {
+    consecutive_failures: u32,
+    sidelined_until: Option<Instant>,
+}
+
+impl Upstream {
+    /// Lower is better. Sidelined upstreams rank after all others, then fewer recent failures win.
+    fn score(&self) -> (bool, u32) {
+        let health = self.health.lock().unwrap();
+        let sidelined = health
+            .sidelined_until
+            .is_some_and(|until| Instant::now() < until);
+        (sidelined, health.consecutive_failures)
+    }
+
+    fn record_success(&self) {
+        *self.health.lock().unwrap() = UpstreamHealth::default();
+    }
+
+    fn record_failure(&self) {
+        let mut health = self.health.lock().unwrap();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            health.sidelined_until = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+        }
+    }
 }
 
 impl SequencerClient {
-    /// Creates the sequencer client
+    /// Creates the sequencer client. `rpc_urls` are tried in order while equally healthy.
+    /// Without any URL every request fails, so nodes syncing from L2 are required to configure
+    /// at least one, see `RunnerConfig::validate`.
     #[instrument(level = "trace")]
-    pub fn new(rpc_url: String) -> Self {
-        let client = HttpClientBuilder::default().build(&rpc_url).unwrap();
-        Self { rpc_url, client }
+    pub fn new(rpc_urls: Vec<String>) -> Self {
+        let upstreams = rpc_urls
+            .into_iter()
+            .map(|rpc_url| {
+                let client = HttpClientBuilder::default().build(&rpc_url).unwrap();
+                Upstream {
+                    rpc_url,
+                    client,
+                    health: Mutex::new(UpstreamHealth::default()),
+                }
+            })
+            .collect();
+        Self { upstreams }
+    }
+
+    /// Upstreams ordered from healthiest to least healthy, keeping configured order on ties
+    fn ranked_upstreams(&self) -> Vec<&Upstream> {
+        let mut upstreams: Vec<&Upstream> = self.upstreams.iter().collect();
+        upstreams.sort_by_key(|upstream| upstream.score());
+        upstreams
+    }
+
+    /// Sends the request to the healthiest upstream, failing over on connection errors.
+    /// Errors returned by the upstream itself are not retried elsewhere.
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ArrayParams,
+    ) -> Result<R, Error> {
+        let mut last_err = None;
+        for upstream in self.ranked_upstreams() {
+            match upstream.client.request(method, params.clone()).await {
+                Err(
+                    e @ (Error::Transport(_) | Error::RequestTimeout | Error::RestartNeeded(_)),
+                ) => {
+                    warn!(
+                        "Upstream {} failed on {}: {}, trying next",
+                        upstream.rpc_url, method, e
+                    );
+                    upstream.record_failure();
+                    last_err = Some(e);
+                }
+                res => {
+                    upstream.record_success();
+                    return res;
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::Custom("No upstream configured".to_string())))
     }
 
     /// Gets l2 block given l2 height
@@ -33,7 +128,6 @@ impl SequencerClient {
         num: u64,
     ) -> anyhow::Result<Option<GetSoftBatchResponse>> {
         let res: Result<Option<GetSoftBatchResponse>, Error> = self
-            .client
             .request("ledger_getSoftBatchByNumber", rpc_params![num])
             .await;
 
@@ -53,7 +147,6 @@ impl SequencerClient {
         range: Range<u64>,
     ) -> anyhow::Result<Vec<Option<GetSoftBatchResponse>>> {
         let res: Result<Vec<Option<GetSoftBatchResponse>>, Error> = self
-            .client
             .request(
                 "ledger_getSoftBatchRange",
                 rpc_params![range.start, range.end],
@@ -69,19 +162,41 @@ impl SequencerClient {
         }
     }
 
+    /// Subscribes to soft batches as they are committed, using the healthiest upstream that accepts.
+    /// The subscription is served over a WebSocket connection to the same host as the upstream url.
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn subscribe_soft_batches(&self) -> anyhow::Result<SoftBatchSubscription> {
+        let mut last_err = None;
+        for upstream in self.ranked_upstreams() {
+            match subscribe_soft_batches(&upstream.rpc_url).await {
+                Ok(subscription) => {
+                    upstream.record_success();
+                    return Ok(subscription);
+                }
+                Err(e) => {
+                    warn!(
+                        "Upstream {} failed to subscribe to soft batches: {}, trying next",
+                        upstream.rpc_url, e
+                    );
+                    upstream.record_failure();
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("No upstream configured")))
+    }
+
     /// Gets l2 block height
     #[instrument(level = "trace", skip(self), err, ret)]
     pub async fn block_number(&self) -> Result<u64, Error> {
-        self.client
-            .request("ledger_getHeadSoftBatchHeight", rpc_params![])
+        self.request("ledger_getHeadSoftBatchHeight", rpc_params![])
             .await
     }
 
     /// Sends raw tx to sequencer
     #[instrument(level = "trace", skip_all, err, ret)]
     pub async fn send_raw_tx(&self, tx: Bytes) -> Result<B256, Error> {
-        self.client
-            .request("eth_sendRawTransaction", rpc_params![tx])
+        self.request("eth_sendRawTransaction", rpc_params![tx])
             .await
     }
 
@@ -91,12 +206,87 @@ impl SequencerClient {
         tx_hash: B256,
         mempool_only: Option<bool>,
     ) -> Result<Option<reth_rpc_types::Transaction>, Error> {
-        self.client
-            .request(
-                "eth_getTransactionByHash",
-                rpc_params![tx_hash, mempool_only],
-            )
-            .await
+        self.request(
+            "eth_getTransactionByHash",
+            rpc_params![tx_hash, mempool_only],
+        )
+        .await
+    }
+
+    /// Gets the pending and queued transactions of the sequencer mempool
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn txpool_content(&self) -> Result<TxpoolContent, Error> {
+        self.request("txpool_content", rpc_params![]).await
+    }
+
+    /// Gets the pending and queued transactions of the given sender in the sequencer mempool
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom, Error> {
+        self.request("txpool_contentFrom", rpc_params![from]).await
+    }
+
+    /// Gets a textual summary of the transactions in the sequencer mempool
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn txpool_inspect(&self) -> Result<TxpoolInspect, Error> {
+        self.request("txpool_inspect", rpc_params![]).await
+    }
+
+    /// Gets the number of pending and queued transactions in the sequencer mempool
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn txpool_status(&self) -> Result<TxpoolStatus, Error> {
+        self.request("txpool_status", rpc_params![]).await
+    }
+}
+
+async fn subscribe_soft_batches(rpc_url: &str) -> anyhow::Result<SoftBatchSubscription> {
+    let client = WsClientBuilder::default().build(ws_url(rpc_url)).await?;
+    let subscription = client
+        .subscribe(
+            "citrea_subscribeSoftBatches",
+            rpc_params![],
+            "citrea_unsubscribeSoftBatches",
+        )
+        .await?;
+    Ok(SoftBatchSubscription {
+        _client: client,
+        subscription,
+    })
+}
+
+/// A live `citrea_subscribeSoftBatches` subscription.
+/// Dropping it closes the underlying WebSocket connection.
+pub struct SoftBatchSubscription {
+    _client: WsClient,
+    subscription: Subscription<SoftBatchNotification>,
+}
+
+impl SoftBatchSubscription {
+    /// Waits for the next soft batch along with its L2 height.
+    /// Returns `None` once the connection to the sequencer is lost.
+    pub async fn next(&mut self) -> Option<anyhow::Result<(u64, GetSoftBatchResponse)>> {
+        let notification = self.subscription.next().await?;
+        Some(
+            notification
+                .map(|n| (n.height, n.soft_batch))
+                .map_err(Into::into),
+        )
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct SoftBatchNotification {
+    height: u64,
+    soft_batch: GetSoftBatchResponse,
+}
+
+/// Maps an HTTP(S) RPC url to the WebSocket url served on the same port.
+fn ws_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
     }
 }
 
@@ -145,3 +335,40 @@ impl From<GetSoftBatchResponse> for SignedSoftConfirmationBatch {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_upstream_is_ranked_last() {
+        let client = SequencerClient::new(vec![
+            "http://127.0.0.1:12345".to_string(),
+            "http://127.0.0.1:12346".to_string(),
+        ]);
+        let ranked_urls = |client: &SequencerClient| -> Vec<String> {
+            client
+                .ranked_upstreams()
+                .into_iter()
+                .map(|upstream| upstream.rpc_url.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            ranked_urls(&client),
+            ["http://127.0.0.1:12345", "http://127.0.0.1:12346"]
+        );
+
+        client.upstreams[0].record_failure();
+        assert_eq!(
+            ranked_urls(&client),
+            ["http://127.0.0.1:12346", "http://127.0.0.1:12345"]
+        );
+
+        client.upstreams[0].record_success();
+        assert_eq!(
+            ranked_urls(&client),
+            ["http://127.0.0.1:12345", "http://127.0.0.1:12346"]
+        );
+    }
+}
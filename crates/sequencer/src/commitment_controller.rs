@@ -47,6 +47,10 @@ pub fn get_commitment_info(
     let (l2_range_to_submit, l1_height_range) = match last_commitment_l1_height {
         Some(last_commitment_l1_height) => {
             let l1_start = last_commitment_l1_height.0 + 1;
+            // already committed up to `prev_l1_height`, e.g. at an L1 reorg
+            if l1_start > prev_l1_height {
+                return Ok(None);
+            }
             let mut l1_end = l1_start;
 
             let Some((l2_start, mut l2_end)) =
@@ -107,6 +111,49 @@ pub fn get_commitment_info(
         return Ok(None);
     }
 
+    commitment_info_for_range(ledger_db, l2_range_to_submit, l1_height_range).map(Some)
+}
+
+/// Returns the `CommitmentInfo` of all the soft confirmations that are not committed yet
+/// and are built on L1 blocks up to `last_l1_height`, regardless of `min_soft_confirmations_per_commitment`
+/// Used at an L1 reorg to end the pending commitment at the reorged L1 blocks, since the prover
+/// expects the L1 blocks of a commitment to be chained
+/// Returns none if there is nothing to commit
+#[instrument(level = "debug", skip_all, fields(last_l1_height), err)]
+pub fn get_commitment_info_up_to(
+    ledger_db: &LedgerDB,
+    last_l1_height: u64,
+) -> anyhow::Result<Option<CommitmentInfo>> {
+    let l1_start = match ledger_db.get_last_sequencer_commitment_l1_height()? {
+        Some(last_commitment_l1_height) => last_commitment_l1_height.0 + 1,
+        None => match ledger_db.get_soft_batch_by_number::<()>(1)? {
+            Some(batch) => batch.da_slot_height,
+            None => return Ok(None),
+        },
+    };
+
+    let mut l2_start = None;
+    for l1_height in l1_start..=last_l1_height {
+        if let Some((start, _)) = ledger_db.get_l2_range_by_l1_height(SlotNumber(l1_height))? {
+            l2_start = Some(start);
+            break;
+        }
+    }
+    let Some(l2_start) = l2_start else {
+        return Ok(None);
+    };
+    let Some((_, l2_end)) = ledger_db.get_l2_range_by_l1_height(SlotNumber(last_l1_height))? else {
+        bail!("Sequencer: Failed to get L1 L2 connection");
+    };
+
+    commitment_info_for_range(ledger_db, (l2_start, l2_end), (l1_start, last_l1_height)).map(Some)
+}
+
+fn commitment_info_for_range(
+    ledger_db: &LedgerDB,
+    l2_range_to_submit: (BatchNumber, BatchNumber),
+    l1_height_range: (u64, u64),
+) -> anyhow::Result<CommitmentInfo> {
     let Some(l1_start_hash) = ledger_db
         .get_soft_batch_by_number::<()>(l2_range_to_submit.0 .0)?
         .map(|s| s.da_slot_hash)
@@ -124,12 +171,12 @@ pub fn get_commitment_info(
     debug!("L1 start hash: {:?}", l1_start_hash);
     debug!("L1 end hash: {:?}", l1_end_hash);
 
-    Ok(Some(CommitmentInfo {
+    Ok(CommitmentInfo {
         l2_height_range: l2_range_to_submit.0..=l2_range_to_submit.1,
         l1_height_range: BatchNumber(l1_height_range.0)..=BatchNumber(l1_height_range.1),
         l1_start_hash,
         l1_end_hash,
-    }))
+    })
 }
 
 #[instrument(level = "debug", skip_all, err)]
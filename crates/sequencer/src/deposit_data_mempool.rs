@@ -1,24 +1,46 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use citrea_evm::system_contracts::Bridge;
 use citrea_evm::SYSTEM_SIGNER;
-use reth_primitives::TxKind;
+use reth_primitives::{TxKind, B256};
 use reth_rpc_types::{TransactionInput, TransactionRequest};
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
-#[derive(Clone, Debug)]
+/// Number of included or rejected deposits whose status is remembered
+const DEPOSIT_STATUS_CACHE_SIZE: u32 = 10_000;
+
+/// Status of a deposit submitted through `citrea_sendRawDepositTransaction`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum DepositStatus {
+    /// Waiting in the deposit mempool to be included in a block
+    Queued,
+    /// Included in an L2 block.
+    /// `l2_height` is `None` if the deposit was processed before the sequencer last restarted.
+    #[serde(rename_all = "camelCase")]
+    Included { l2_height: Option<u64> },
+    /// Rejected when submitted
+    Rejected { reason: String },
+}
+
 pub struct DepositDataMempool {
-    accepted_deposit_txs: VecDeque<Vec<u8>>,
+    accepted_deposit_txs: VecDeque<(B256, Vec<u8>)>,
+    queued_wtxids: HashSet<B256>,
+    processed_deposits: LruMap<B256, DepositStatus, ByLength>,
 }
 
 impl DepositDataMempool {
     pub fn new() -> Self {
         Self {
             accepted_deposit_txs: VecDeque::new(),
+            queued_wtxids: HashSet::new(),
+            processed_deposits: LruMap::new(ByLength::new(DEPOSIT_STATUS_CACHE_SIZE)),
         }
     }
 
-    pub fn make_deposit_tx_from_data(&mut self, deposit_tx_data: Vec<u8>) -> TransactionRequest {
+    pub fn make_deposit_tx_from_data(&self, deposit_tx_data: Vec<u8>) -> TransactionRequest {
         TransactionRequest {
             from: Some(SYSTEM_SIGNER),
             to: Some(TxKind::Call(Bridge::address())),
@@ -32,15 +54,141 @@ impl DepositDataMempool {
     // (i.e. if you have 500 dep tx, due to gas, they may not be included, so it panics - we don't want that)
 
     // Considering the deposit amounts to be allowed, and the block count, a limit per block is convenient
-    pub fn fetch_deposits(&mut self, limit_per_block: usize) -> Vec<Vec<u8>> {
-        let number_of_deposits = self.accepted_deposit_txs.len().min(limit_per_block);
+    /// Returns the oldest deposits with their wtxids, without removing them.
+    /// They stay queued until [`DepositDataMempool::mark_included`] is called for the block that includes them.
+    pub fn fetch_deposits(&self, limit_per_block: usize) -> Vec<(B256, Vec<u8>)> {
         self.accepted_deposit_txs
-            .drain(..number_of_deposits)
+            .iter()
+            .take(limit_per_block)
+            .cloned()
             .collect()
     }
 
-    #[instrument(level = "trace", skip_all, ret)]
-    pub fn add_deposit_tx(&mut self, req: Vec<u8>) {
-        self.accepted_deposit_txs.push_back(req);
+    /// Removes the deposits with the given wtxids, which were fetched for the block at `l2_height`.
+    /// Deposits may have been requeued in front of them since they were fetched,
+    /// so they are looked up by wtxid rather than by position.
+    pub fn mark_included(&mut self, wtxids: &[B256], l2_height: u64) {
+        let included: HashSet<&B256> = wtxids.iter().collect();
+        self.accepted_deposit_txs
+            .retain(|(wtxid, _)| !included.contains(wtxid));
+        for wtxid in wtxids {
+            self.queued_wtxids.remove(wtxid);
+            self.processed_deposits.insert(
+                *wtxid,
+                DepositStatus::Included {
+                    l2_height: Some(l2_height),
+                },
+            );
+        }
+    }
+
+    #[instrument(level = "trace", skip(self, req), ret)]
+    pub fn add_deposit_tx(&mut self, wtxid: B256, req: Vec<u8>) {
+        if self.queued_wtxids.insert(wtxid) {
+            self.processed_deposits.remove(&wtxid);
+            self.accepted_deposit_txs.push_back((wtxid, req));
+        }
+    }
+
+    /// Queues deposits that were included in blocks but not processed by the bridge again,
+    /// in front of the deposits that were not included yet
+    #[instrument(level = "trace", skip_all)]
+    pub fn requeue_deposits(&mut self, deposits: Vec<(B256, Vec<u8>)>) {
+        for (wtxid, req) in deposits.into_iter().rev() {
+            if self.queued_wtxids.insert(wtxid) {
+                self.processed_deposits.remove(&wtxid);
+                self.accepted_deposit_txs.push_front((wtxid, req));
+            }
+        }
+    }
+
+    #[instrument(level = "trace", skip(self), ret)]
+    pub fn add_rejected_deposit(&mut self, wtxid: B256, reason: String) {
+        self.processed_deposits
+            .insert(wtxid, DepositStatus::Rejected { reason });
+    }
+
+    pub fn is_queued(&self, wtxid: &B256) -> bool {
+        self.queued_wtxids.contains(wtxid)
+    }
+
+    /// Returns the status of the deposit if it was seen since the sequencer started
+    pub fn get_status(&mut self, wtxid: &B256) -> Option<DepositStatus> {
+        if self.is_queued(wtxid) {
+            return Some(DepositStatus::Queued);
+        }
+        self.processed_deposits.get(wtxid).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::B256;
+
+    use super::{DepositDataMempool, DepositStatus};
+
+    #[test]
+    fn test_deposit_status_lifecycle() {
+        let mut mempool = DepositDataMempool::new();
+        let first = B256::repeat_byte(1);
+        let second = B256::repeat_byte(2);
+
+        mempool.add_deposit_tx(first, vec![1]);
+        // duplicates are ignored
+        mempool.add_deposit_tx(first, vec![1]);
+        mempool.add_deposit_tx(second, vec![2]);
+
+        assert_eq!(
+            mempool.fetch_deposits(10),
+            vec![(first, vec![1]), (second, vec![2])]
+        );
+        assert_eq!(mempool.get_status(&first), Some(DepositStatus::Queued));
+
+        mempool.mark_included(&[first], 5);
+        assert_eq!(
+            mempool.get_status(&first),
+            Some(DepositStatus::Included { l2_height: Some(5) })
+        );
+        assert_eq!(mempool.fetch_deposits(10), vec![(second, vec![2])]);
+
+        let rejected = B256::repeat_byte(3);
+        mempool.add_rejected_deposit(rejected, "reverted".to_string());
+        assert_eq!(
+            mempool.get_status(&rejected),
+            Some(DepositStatus::Rejected {
+                reason: "reverted".to_string()
+            })
+        );
+        assert_eq!(mempool.get_status(&B256::repeat_byte(4)), None);
+
+        // deposits that were not processed by the bridge are included first again
+        mempool.requeue_deposits(vec![(first, vec![1])]);
+        assert_eq!(mempool.get_status(&first), Some(DepositStatus::Queued));
+        assert_eq!(
+            mempool.fetch_deposits(10),
+            vec![(first, vec![1]), (second, vec![2])]
+        );
+    }
+
+    #[test]
+    fn test_mark_included_after_requeue() {
+        let mut mempool = DepositDataMempool::new();
+        let requeued = B256::repeat_byte(1);
+        let fetched = B256::repeat_byte(2);
+
+        mempool.add_deposit_tx(fetched, vec![2]);
+        let deposits = mempool.fetch_deposits(10);
+
+        // A deposit is requeued in front while the block with the fetched deposits is built
+        mempool.requeue_deposits(vec![(requeued, vec![1])]);
+
+        let wtxids: Vec<B256> = deposits.iter().map(|(wtxid, _)| *wtxid).collect();
+        mempool.mark_included(&wtxids, 5);
+        assert_eq!(
+            mempool.get_status(&fetched),
+            Some(DepositStatus::Included { l2_height: Some(5) })
+        );
+        assert_eq!(mempool.get_status(&requeued), Some(DepositStatus::Queued));
+        assert_eq!(mempool.fetch_deposits(10), vec![(requeued, vec![1])]);
     }
 }
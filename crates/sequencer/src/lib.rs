@@ -3,6 +3,7 @@ mod config;
 mod db_provider;
 mod deposit_data_mempool;
 mod mempool;
+mod reorg;
 mod rpc;
 mod sequencer;
 mod utils;
@@ -10,10 +11,14 @@ mod utils;
 use std::net::SocketAddr;
 
 pub use config::{SequencerConfig, SequencerMempoolConfig};
+pub use deposit_data_mempool::DepositStatus;
+pub use reorg::{L1Reorg, L1ReorgOutcome, L1ReorgStatus};
+pub use rpc::{DEPOSIT_ALREADY_PROCESSED_CODE, DEPOSIT_ALREADY_QUEUED_CODE};
 pub use sequencer::CitreaSequencer;
 use sov_modules_rollup_blueprint::RollupBlueprint;
 use sov_modules_stf_blueprint::StfBlueprint;
 use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
 use tracing::{instrument, Instrument};
 
 /// Sequencer stf runner
@@ -29,6 +34,8 @@ pub struct Sequencer<S: RollupBlueprint> {
     >,
     /// Rpc methods for the rollup.
     pub rpc_methods: jsonrpsee::RpcModule<()>,
+    /// Cancelled to stop the sequencer gracefully.
+    pub shutdown: CancellationToken,
 }
 
 impl<S: RollupBlueprint> Sequencer<S> {
@@ -50,11 +57,11 @@ impl<S: RollupBlueprint> Sequencer<S> {
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) -> Result<(), anyhow::Error> {
         let mut seq = self.runner;
-        seq.start_rpc_server(channel, self.rpc_methods)
+        seq.start_rpc_server(channel, self.rpc_methods, self.shutdown.clone())
             .instrument(tracing::Span::current())
             .await
             .unwrap();
-        seq.run().await?;
+        seq.run(self.shutdown).await?;
         Ok(())
     }
 }
@@ -7,9 +7,10 @@ use reth_tasks::TokioTaskExecutor;
 use reth_transaction_pool::blobstore::NoopBlobStore;
 use reth_transaction_pool::error::PoolError;
 use reth_transaction_pool::{
-    BestTransactions, BestTransactionsAttributes, ChangedAccount, CoinbaseTipOrdering,
-    EthPooledTransaction, EthTransactionValidator, Pool, PoolConfig, PoolResult, SubPoolLimit,
-    TransactionPool, TransactionPoolExt, TransactionValidationTaskExecutor, ValidPoolTransaction,
+    AllPoolTransactions, BestTransactions, BestTransactionsAttributes, ChangedAccount,
+    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, Pool, PoolConfig,
+    PoolResult, SubPoolLimit, TransactionPool, TransactionPoolExt,
+    TransactionValidationTaskExecutor, ValidPoolTransaction,
 };
 
 use crate::config::SequencerMempoolConfig;
@@ -108,6 +109,12 @@ impl<C: sov_modules_api::Context> CitreaMempool<C> {
         self.0.get(hash)
     }
 
+    /// Returns all transactions in the pool, split into pending and queued.
+    /// Transactions waiting in the base-fee sub-pool are reported as queued.
+    pub(crate) fn all_transactions(&self) -> AllPoolTransactions<Transaction<C>> {
+        self.0.all_transactions()
+    }
+
     pub(crate) fn remove_transactions(
         &self,
         tx_hashes: Vec<TxHash>,
@@ -115,6 +122,11 @@ impl<C: sov_modules_api::Context> CitreaMempool<C> {
         self.0.remove_transactions(tx_hashes)
     }
 
+    /// Returns a receiver for the hashes of the transactions that become ready for inclusion
+    pub(crate) fn pending_transactions_listener(&self) -> tokio::sync::mpsc::Receiver<TxHash> {
+        self.0.pending_transactions_listener()
+    }
+
     pub(crate) fn update_accounts(&self, account_updates: Vec<ChangedAccount>) {
         self.0.update_accounts(account_updates);
     }
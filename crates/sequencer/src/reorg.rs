@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Number of handled L1 reorgs that are reported over RPC
+const REORG_HISTORY_SIZE: usize = 100;
+
+/// What the sequencer did about an L1 reorg
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "camelCase")]
+pub enum L1ReorgOutcome {
+    /// No soft confirmation was built on the reorged L1 blocks
+    NothingToReanchor,
+    /// Soft confirmations were built on the reorged L1 blocks. They are kept, and the next
+    /// soft confirmation is built on the L1 block of the new fork at `next_l1_height`.
+    /// Deposits of the soft confirmations that the bridge did not process are queued again.
+    #[serde(rename_all = "camelCase")]
+    Reanchored {
+        first_l2_height: u64,
+        last_l2_height: u64,
+        next_l1_height: u64,
+        requeued_deposits: usize,
+    },
+    /// The reorg could not be handled, block production is halted
+    Halted { reason: String },
+}
+
+/// L1 reorg handled by the sequencer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1Reorg {
+    pub common_ancestor_height: u64,
+    /// Hex encoded hash of the last L1 block that was not reorged
+    pub common_ancestor_hash: String,
+    /// Hex encoded hashes of the reorged L1 blocks, in ascending order of height
+    pub reorged_hashes: Vec<String>,
+    #[serde(flatten)]
+    pub outcome: L1ReorgOutcome,
+}
+
+/// Reported by `citrea_getL1ReorgStatus`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1ReorgStatus {
+    /// Whether block production is halted because of a reorg that could not be handled
+    pub halted: bool,
+    pub halt_reason: Option<String>,
+    /// Latest handled reorgs, oldest first
+    pub reorgs: VecDeque<L1Reorg>,
+}
+
+impl L1ReorgStatus {
+    pub fn record(&mut self, reorg: L1Reorg) {
+        if let L1ReorgOutcome::Halted { reason } = &reorg.outcome {
+            self.halted = true;
+            self.halt_reason = Some(reason.clone());
+        }
+        if self.reorgs.len() == REORG_HISTORY_SIZE {
+            self.reorgs.pop_front();
+        }
+        self.reorgs.push_back(reorg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{L1Reorg, L1ReorgOutcome, L1ReorgStatus};
+
+    #[test]
+    fn test_halting_reorg_is_reported() {
+        let mut status = L1ReorgStatus::default();
+        status.record(L1Reorg {
+            common_ancestor_height: 5,
+            common_ancestor_hash: "00".to_string(),
+            reorged_hashes: vec!["01".to_string()],
+            outcome: L1ReorgOutcome::NothingToReanchor,
+        });
+        assert!(!status.halted);
+
+        status.record(L1Reorg {
+            common_ancestor_height: 6,
+            common_ancestor_hash: "02".to_string(),
+            reorged_hashes: vec!["03".to_string()],
+            outcome: L1ReorgOutcome::Halted {
+                reason: "finalized".to_string(),
+            },
+        });
+        assert!(status.halted);
+        assert_eq!(status.halt_reason.as_deref(), Some("finalized"));
+        assert_eq!(status.reorgs.len(), 2);
+    }
+}
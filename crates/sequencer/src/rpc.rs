@@ -1,23 +1,37 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use citrea_evm::system_contracts::Bridge;
 use citrea_evm::Evm;
 use futures::channel::mpsc::UnboundedSender;
-use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INTERNAL_ERROR_MSG};
+use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INTERNAL_ERROR_MSG, INVALID_PARAMS_CODE};
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
-use reth_primitives::{Bytes, FromRecoveredPooledTransaction, IntoRecoveredTransaction, B256};
+use reth_primitives::{
+    Address, Bytes, FromRecoveredPooledTransaction, IntoRecoveredTransaction, TxKind, B256,
+};
 use reth_rpc::eth::error::EthApiError;
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
+};
+use reth_rpc_types::{TransactionInput, TransactionRequest};
 use reth_rpc_types_compat::transaction::from_recovered;
-use reth_transaction_pool::EthPooledTransaction;
+use reth_transaction_pool::{AllPoolTransactions, EthPooledTransaction, PoolTransaction};
 use shared_backup_db::PostgresConnector;
 use sov_modules_api::WorkingSet;
 use tokio::sync::Mutex;
 use tracing::{debug, error};
 
-use crate::deposit_data_mempool::DepositDataMempool;
+use crate::deposit_data_mempool::{DepositDataMempool, DepositStatus};
 use crate::mempool::CitreaMempool;
+use crate::reorg::L1ReorgStatus;
 use crate::utils::recover_raw_transaction;
 
+/// Error code returned when a deposit with the same wtxid is already in the deposit mempool
+pub const DEPOSIT_ALREADY_QUEUED_CODE: i32 = -32051;
+/// Error code returned when a deposit with the same wtxid was already processed by the bridge
+pub const DEPOSIT_ALREADY_PROCESSED_CODE: i32 = -32052;
+
 pub(crate) struct RpcContext<C: sov_modules_api::Context> {
     pub mempool: Arc<CitreaMempool<C>>,
     pub deposit_mempool: Arc<Mutex<DepositDataMempool>>,
@@ -25,6 +39,7 @@ pub(crate) struct RpcContext<C: sov_modules_api::Context> {
     pub storage: C::Storage,
     pub test_mode: bool,
     pub pg_pool: Option<Arc<PostgresConnector>>,
+    pub l1_reorg_status: Arc<Mutex<L1ReorgStatus>>,
 }
 
 pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
@@ -109,6 +124,48 @@ pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
         }
     })?;
 
+    rpc.register_async_method("txpool_content", |_, ctx| async move {
+        debug!("Sequencer: txpool_content");
+
+        Ok::<TxpoolContent, ErrorObjectOwned>(txpool_content(ctx.mempool.all_transactions()))
+    })?;
+
+    rpc.register_async_method("txpool_contentFrom", |parameters, ctx| async move {
+        let from: Address = parameters.one()?;
+        debug!("Sequencer: txpool_contentFrom({})", from);
+
+        let mut content = txpool_content(ctx.mempool.all_transactions());
+
+        Ok::<TxpoolContentFrom, ErrorObjectOwned>(content.remove_from(&from))
+    })?;
+
+    rpc.register_async_method("txpool_inspect", |_, ctx| async move {
+        debug!("Sequencer: txpool_inspect");
+
+        let AllPoolTransactions { pending, queued } = ctx.mempool.all_transactions();
+
+        let mut inspect = TxpoolInspect::default();
+        for tx in pending {
+            insert_inspect_summary(&tx.transaction, &mut inspect.pending);
+        }
+        for tx in queued {
+            insert_inspect_summary(&tx.transaction, &mut inspect.queued);
+        }
+
+        Ok::<TxpoolInspect, ErrorObjectOwned>(inspect)
+    })?;
+
+    rpc.register_async_method("txpool_status", |_, ctx| async move {
+        debug!("Sequencer: txpool_status");
+
+        let all = ctx.mempool.all_transactions();
+
+        Ok::<TxpoolStatus, ErrorObjectOwned>(TxpoolStatus {
+            pending: all.pending.len() as u64,
+            queued: all.queued.len() as u64,
+        })
+    })?;
+
     rpc.register_async_method(
         "citrea_sendRawDepositTransaction",
         |parameters, ctx| async move {
@@ -117,33 +174,153 @@ pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
 
             debug!("Sequencer: citrea_sendRawDepositTransaction");
 
-            let evm = Evm::<C>::default();
-            let mut working_set = WorkingSet::<C>::new(ctx.storage.clone());
+            let wtxid = Bridge::deposit_wtxid(&deposit).ok_or_else(|| {
+                ErrorObjectOwned::owned(INVALID_PARAMS_CODE, "Invalid deposit data", None::<String>)
+            })?;
 
-            let dep_tx = ctx
-                .deposit_mempool
-                .lock()
-                .await
-                .make_deposit_tx_from_data(deposit.clone().into());
+            // The checks are made under the lock, which is released for the database write
+            // so that block production and status queries do not wait on it.
+            {
+                let mut deposit_mempool = ctx.deposit_mempool.lock().await;
 
-            let tx_res = evm.get_call(dep_tx, None, None, None, &mut working_set);
+                if deposit_mempool.is_queued(&wtxid) {
+                    return Err(ErrorObjectOwned::owned(
+                        DEPOSIT_ALREADY_QUEUED_CODE,
+                        "Deposit already queued",
+                        Some(wtxid.to_string()),
+                    ));
+                }
 
-            match tx_res {
-                Ok(hex_res) => {
-                    tracing::debug!("Deposit tx processed successfully {}", hex_res);
-                    ctx.deposit_mempool
-                        .lock()
-                        .await
-                        .add_deposit_tx(deposit.to_vec());
+                if is_deposit_processed::<C>(wtxid, ctx.storage.clone())? {
+                    return Err(ErrorObjectOwned::owned(
+                        DEPOSIT_ALREADY_PROCESSED_CODE,
+                        "Deposit already processed",
+                        Some(wtxid.to_string()),
+                    ));
                 }
-                Err(e) => {
-                    error!("Error processing deposit tx: {:?}", e);
-                    return Err(e);
+
+                let evm = Evm::<C>::default();
+                let mut working_set = WorkingSet::<C>::new(ctx.storage.clone());
+
+                let dep_tx = deposit_mempool.make_deposit_tx_from_data(deposit.clone().into());
+
+                match evm.get_call(dep_tx, None, None, None, &mut working_set) {
+                    Ok(hex_res) => {
+                        tracing::debug!("Deposit tx processed successfully {}", hex_res);
+                    }
+                    Err(e) => {
+                        error!("Error processing deposit tx: {:?}", e);
+                        deposit_mempool.add_rejected_deposit(wtxid, e.message().to_string());
+                        return Err(e);
+                    }
+                }
+            }
+
+            if let Some(pool) = &ctx.pg_pool {
+                // Do not return error here just log
+                if let Err(e) = pool
+                    .insert_deposit_tx(wtxid.to_vec(), deposit.to_vec())
+                    .await
+                {
+                    tracing::warn!("Failed to insert deposit tx into db: {:?}", e);
                 }
             }
 
+            // Queuing is idempotent, a deposit sent twice concurrently is only queued once
+            ctx.deposit_mempool
+                .lock()
+                .await
+                .add_deposit_tx(wtxid, deposit.to_vec());
+
             Ok(())
         },
     )?;
+
+    rpc.register_async_method("citrea_getDepositStatus", |parameters, ctx| async move {
+        let wtxid: B256 = parameters.one()?;
+        debug!("Sequencer: citrea_getDepositStatus({})", wtxid);
+
+        if let Some(status) = ctx.deposit_mempool.lock().await.get_status(&wtxid) {
+            return Ok::<Option<DepositStatus>, ErrorObjectOwned>(Some(status));
+        }
+
+        // Deposits included before the last restart are only known to the bridge contract
+        if is_deposit_processed::<C>(wtxid, ctx.storage.clone())? {
+            return Ok(Some(DepositStatus::Included { l2_height: None }));
+        }
+
+        Ok(None)
+    })?;
+
+    rpc.register_async_method("citrea_getL1ReorgStatus", |_, ctx| async move {
+        debug!("Sequencer: citrea_getL1ReorgStatus");
+
+        Ok::<L1ReorgStatus, ErrorObjectOwned>(ctx.l1_reorg_status.lock().await.clone())
+    })?;
+
     Ok(rpc)
 }
+
+/// Checks the bridge contract for whether the deposit with the given wtxid was already processed
+pub(crate) fn is_deposit_processed<C: sov_modules_api::Context>(
+    wtxid: B256,
+    storage: C::Storage,
+) -> Result<bool, ErrorObjectOwned> {
+    let evm = Evm::<C>::default();
+    let mut working_set = WorkingSet::<C>::new(storage);
+
+    let spent = evm.get_call(
+        TransactionRequest {
+            to: Some(TxKind::Call(Bridge::address())),
+            input: TransactionInput::new(Bridge::spent_wtx_ids(wtxid)),
+            ..Default::default()
+        },
+        None,
+        None,
+        None,
+        &mut working_set,
+    )?;
+
+    Ok(spent.last() == Some(&1))
+}
+
+/// Groups the pool transactions by sender and nonce, the way geth's `txpool_content` does.
+fn txpool_content(all: AllPoolTransactions<EthPooledTransaction>) -> TxpoolContent {
+    let AllPoolTransactions { pending, queued } = all;
+
+    let mut content = TxpoolContent::default();
+    for tx in pending {
+        insert_transaction(&tx.transaction, &mut content.pending);
+    }
+    for tx in queued {
+        insert_transaction(&tx.transaction, &mut content.queued);
+    }
+
+    content
+}
+
+fn insert_transaction(
+    tx: &EthPooledTransaction,
+    content: &mut BTreeMap<Address, BTreeMap<String, reth_rpc_types::Transaction>>,
+) {
+    content.entry(tx.sender()).or_default().insert(
+        tx.nonce().to_string(),
+        from_recovered(tx.to_recovered_transaction()),
+    );
+}
+
+fn insert_inspect_summary(
+    tx: &EthPooledTransaction,
+    inspect: &mut BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
+) {
+    let recovered = tx.to_recovered_transaction();
+    inspect.entry(tx.sender()).or_default().insert(
+        tx.nonce().to_string(),
+        TxpoolInspectSummary {
+            to: recovered.to(),
+            value: recovered.value(),
+            gas: recovered.gas_limit() as u128,
+            gas_price: recovered.max_fee_per_gas(),
+        },
+    );
+}
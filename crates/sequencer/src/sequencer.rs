@@ -9,15 +9,22 @@ use std::vec;
 
 use anyhow::anyhow;
 use borsh::ser::BorshSerialize;
+use citrea_evm::system_contracts::Bridge;
 use citrea_evm::{CallMessage, Evm, RlpEvmTransaction, MIN_TRANSACTION_GAS};
 use citrea_stf::runtime::Runtime;
 use digest::Digest;
+use ethereum_rpc::{
+    create_filter_rpc_module, create_subscription_rpc_module, FilterConfig, FilterManager,
+    SubscriptionManager,
+};
 use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use futures::StreamExt;
 use hyper::Method;
 use jsonrpsee::server::{BatchRequestConfig, ServerBuilder};
 use jsonrpsee::RpcModule;
-use reth_primitives::{Address, FromRecoveredPooledTransaction, IntoRecoveredTransaction, TxHash};
+use reth_primitives::{
+    Address, FromRecoveredPooledTransaction, IntoRecoveredTransaction, TxHash, B256,
+};
 use reth_provider::{AccountReader, BlockReaderIdExt};
 use reth_transaction_pool::{
     BestTransactions, BestTransactionsAttributes, ChangedAccount, EthPooledTransaction,
@@ -36,8 +43,10 @@ use sov_modules_api::{
     UnsignedSoftConfirmationBatch, WorkingSet,
 };
 use sov_modules_stf_blueprint::StfBlueprintTrait;
-use sov_rollup_interface::da::{BlockHeaderTrait, DaData, DaSpec};
-use sov_rollup_interface::services::da::{BlobWithNotifier, DaService};
+use sov_rollup_interface::da::{
+    BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, ForcedTransaction,
+};
+use sov_rollup_interface::services::da::{BlobWithNotifier, DaService, FinalizedHeaderEvent};
 use sov_rollup_interface::stf::{SoftBatchReceipt, StateTransitionFunction};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_rollup_interface::zk::ZkvmHost;
@@ -45,6 +54,8 @@ use sov_stf_runner::{InitVariant, RollupPublicKeys, RpcConfig};
 use tokio::sync::oneshot::channel as oneshot_channel;
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -53,7 +64,8 @@ use crate::config::SequencerConfig;
 use crate::db_provider::DbProvider;
 use crate::deposit_data_mempool::DepositDataMempool;
 use crate::mempool::CitreaMempool;
-use crate::rpc::{create_rpc_module, RpcContext};
+use crate::reorg::{L1Reorg, L1ReorgOutcome, L1ReorgStatus};
+use crate::rpc::{create_rpc_module, is_deposit_processed, RpcContext};
 use crate::utils::recover_raw_transaction;
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
@@ -62,6 +74,9 @@ type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 /// Contains previous height, latest finalized block and fee rate.
 type L1Data<Da> = (<Da as DaService>::FilteredBlock, u128);
 
+/// How long the sequencer waits on shutdown for the blobs queued to the DA service to be sent
+const DA_QUEUE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub struct CitreaSequencer<C, Da, Sm, Vm, Stf>
 where
     C: Context,
@@ -87,6 +102,10 @@ where
     sequencer_pub_key: Vec<u8>,
     rpc_config: RpcConfig,
     soft_confirmation_rule_enforcer: SoftConfirmationRuleEnforcer<C, Da::Spec>,
+    pg_tasks: TaskTracker,
+    /// First L1 block of the new fork that is skipped after an L1 reorg, see [`Self::handle_l1_reorg`]
+    first_skipped_l1_height: Option<u64>,
+    l1_reorg_status: Arc<Mutex<L1ReorgStatus>>,
 }
 
 enum L2BlockMode {
@@ -170,13 +189,18 @@ where
             sequencer_pub_key: public_keys.sequencer_public_key,
             rpc_config,
             soft_confirmation_rule_enforcer,
+            pg_tasks: TaskTracker::new(),
+            first_skipped_l1_height: None,
+            l1_reorg_status: Arc::new(Mutex::new(L1ReorgStatus::default())),
         })
     }
 
+    /// Starts the RPC server in the background. The server is stopped once `shutdown` is cancelled.
     pub async fn start_rpc_server(
         &self,
         channel: Option<tokio::sync::oneshot::Sender<SocketAddr>>,
         methods: RpcModule<()>,
+        shutdown: CancellationToken,
     ) -> anyhow::Result<()> {
         let methods = self.register_rpc_methods(methods).await?;
 
@@ -226,8 +250,13 @@ where
                     }
                     info!("Starting RPC server at {} ", &bound_address);
 
-                    let _server_handle = server.start(methods);
-                    futures::future::pending::<()>().await;
+                    let server_handle = server.start(methods);
+                    shutdown.cancelled().await;
+
+                    info!("Stopping RPC server at {}", &bound_address);
+                    // Only fails if the server is already stopped
+                    let _ = server_handle.stop();
+                    server_handle.stopped().await;
                 }
                 Err(e) => {
                     error!("Could not start RPC server: {}", e);
@@ -247,6 +276,7 @@ where
         state_root: <Stf as StateTransitionFunction<Vm, <Da as DaService>::Spec>>::StateRoot,
         prestate: <Sm as HierarchicalStorageManager<<Da as DaService>::Spec>>::NativeStorage,
         da_block_header: <<Da as DaService>::Spec as DaSpec>::BlockHeader,
+        forced_transactions: &[ForcedTransaction],
         mut signed_batch: SignedSoftConfirmationBatch,
         l2_block_mode: L2BlockMode,
     ) -> anyhow::Result<(Vec<RlpEvmTransaction>, Vec<TxHash>)> {
@@ -256,9 +286,15 @@ where
             prestate.clone(),
             Default::default(),
             &da_block_header,
+            forced_transactions,
             &mut signed_batch,
         ) {
-            (Ok(()), mut working_set_to_discard) => {
+            (Ok(()), working_set_to_discard) => {
+                // forced transactions are applied before the transactions of the sequencer
+                let (mut working_set_to_discard, _) = self
+                    .stf
+                    .apply_soft_batch_txs(signed_batch.txs(), working_set_to_discard);
+
                 let block_gas_limit = self.db_provider.cfg().block_gas_limit;
 
                 let evm = Evm::<C>::default();
@@ -342,19 +378,58 @@ where
         last_used_l1_height: u64,
     ) -> anyhow::Result<u64> {
         let da_height = da_block.header().height();
-        let (l2_height, l1_height) = match self
+        let (l2_height, l1_height, l1_hash) = match self
             .ledger_db
             .get_head_soft_batch()
             .map_err(|e| anyhow!("Failed to get head soft batch: {}", e))?
         {
-            Some((l2_height, sb)) => (l2_height.0 + 1, sb.da_slot_height),
-            None => (0, da_height),
+            Some((l2_height, sb)) => (l2_height.0 + 1, sb.da_slot_height, Some(sb.da_slot_hash)),
+            None => (0, da_height, None),
         };
+        // After an L1 reorg the L1 block of the last soft confirmation may not be on the best fork anymore,
+        // the next soft confirmation is then built on the first L1 block of the new fork above it
+        let da_hash: [u8; 32] = da_block.header().hash().into();
+        let is_reorged_out = l1_hash.is_some_and(|hash| hash != da_hash);
+        if da_height < l1_height || (da_height == l1_height && is_reorged_out) {
+            anyhow::bail!(
+                "Sequencer: L1 block {l1_height} of the last soft confirmation was reorged out, waiting for L1 block {} of the new fork",
+                l1_height + 1
+            );
+        }
         anyhow::ensure!(
             l1_height == da_height || l1_height + 1 == da_height,
             "Sequencer: L1 height mismatch, expected {da_height} (or {da_height}-1), got {l1_height}",
         );
 
+        let mut da_data = self.da_service.extract_relevant_blobs(&da_block);
+        da_data.iter_mut().for_each(|blob| {
+            blob.full_data();
+        });
+        let forced_transactions = ForcedTransaction::from_blobs(&da_data);
+        // Forced transactions are included right away in the first L2 block on their L1 block,
+        // after the ones of the L1 blocks that were skipped after an L1 reorg
+        let is_first_on_l1_block = l2_height == 0 || l1_height != da_height;
+        let forced_txs = if is_first_on_l1_block {
+            let mut forced_txs = match self.first_skipped_l1_height {
+                Some(first_skipped_l1_height) => {
+                    self.skipped_forced_txs(first_skipped_l1_height..=l1_height)
+                        .await?
+                }
+                None => vec![],
+            };
+            forced_txs.extend(self.stf.valid_forced_txs(&forced_transactions));
+            forced_txs
+        } else {
+            vec![]
+        };
+        if !forced_txs.is_empty() {
+            info!(
+                "Including {} forced transactions from L1 block {}",
+                forced_txs.len(),
+                da_height
+            );
+        }
+
         let timestamp = chrono::Local::now().timestamp() as u64;
         let pub_key = self
             .sov_tx_signer_priv_key
@@ -362,11 +437,13 @@ where
             .try_to_vec()
             .map_err(Into::<anyhow::Error>::into)?;
 
-        let deposit_data = self
+        let (deposit_wtxids, deposit_data): (Vec<B256>, Vec<Vec<u8>>) = self
             .deposit_mempool
             .lock()
             .await
-            .fetch_deposits(self.config.deposit_mempool_fetch_limit);
+            .fetch_deposits(self.config.deposit_mempool_fetch_limit)
+            .into_iter()
+            .unzip();
 
         let batch_info = HookSoftConfirmationInfo {
             da_slot_height: da_block.header().height(),
@@ -377,6 +454,8 @@ where
             pub_key,
             l1_fee_rate,
             timestamp,
+            txs: forced_txs.clone(),
+            forced_txs: vec![],
         };
         // initially create sc info and call begin soft confirmation hook with it
         let mut signed_batch: SignedSoftConfirmationBatch = batch_info.clone().into();
@@ -404,6 +483,7 @@ where
                 self.state_root.clone(),
                 prestate.clone(),
                 da_block.header().clone(),
+                &forced_transactions,
                 signed_batch.clone(),
                 l2_block_mode,
             )
@@ -421,6 +501,7 @@ where
             prestate.clone(),
             Default::default(),
             da_block.header(),
+            &forced_transactions,
             &mut signed_batch,
         ) {
             (Ok(()), mut batch_workspace) => {
@@ -429,7 +510,8 @@ where
                 let raw_message =
                     <Runtime<C, Da::Spec> as EncodeCall<citrea_evm::Evm<C>>>::encode_call(call_txs);
                 let signed_blob = self.make_blob(raw_message, &mut batch_workspace)?;
-                let txs = vec![signed_blob.clone()];
+                let mut txs = forced_txs;
+                txs.push(signed_blob);
 
                 let (batch_workspace, tx_receipts) =
                     self.stf.apply_soft_batch_txs(txs.clone(), batch_workspace);
@@ -500,7 +582,7 @@ where
                     tx_receipts: batch_receipt.tx_receipts,
                     soft_confirmation_signature: signed_soft_batch.signature().to_vec(),
                     pub_key: signed_soft_batch.pub_key().to_vec(),
-                    deposit_data,
+                    deposit_data: deposit_data.clone(),
                     l1_fee_rate: signed_soft_batch.l1_fee_rate(),
                     timestamp: signed_soft_batch.timestamp(),
                 };
@@ -519,6 +601,15 @@ where
 
                 self.ledger_db.commit_soft_batch(soft_batch_receipt, true)?;
 
+                if is_first_on_l1_block {
+                    self.first_skipped_l1_height = None;
+                }
+
+                self.deposit_mempool
+                    .lock()
+                    .await
+                    .mark_included(&deposit_wtxids, l2_height);
+
                 let mut txs_to_remove = self.db_provider.last_block_tx_hashes()?;
                 txs_to_remove.extend(l1_fee_failed_txs);
 
@@ -529,8 +620,13 @@ where
                 self.mempool.update_accounts(account_updates);
 
                 if let Some(pg_pool) = pg_pool.clone() {
+                    let deposits_to_remove = deposit_wtxids
+                        .iter()
+                        .map(|wtxid| wtxid.to_vec())
+                        .collect::<Vec<Vec<u8>>>();
                     // TODO: Is this okay? I'm not sure because we have a loop in this and I can't do async in spawn_blocking
-                    tokio::spawn(async move {
+                    // Tracked so that the deletions are not lost on shutdown
+                    self.pg_tasks.spawn(async move {
                         let txs = txs_to_remove
                             .iter()
                             .map(|tx_hash| tx_hash.to_vec())
@@ -538,6 +634,14 @@ where
                         if let Err(e) = pg_pool.delete_txs_by_tx_hashes(txs).await {
                             warn!("Failed to remove txs from mempool: {:?}", e);
                         }
+                        if !deposits_to_remove.is_empty() {
+                            if let Err(e) = pg_pool
+                                .delete_deposit_txs_by_tx_hashes(deposits_to_remove)
+                                .await
+                            {
+                                warn!("Failed to remove deposit txs from db: {:?}", e);
+                            }
+                        }
                     });
                 }
 
@@ -570,9 +674,139 @@ where
         }
     }
 
+    /// Re-anchors block production on the new fork after the finalized L1 blocks above
+    /// `common_ancestor_height` were reorged out.
+    /// Published soft confirmations are never rolled back, full nodes may have applied them already.
+    /// The L1 light client of the rollup counts L1 blocks, so the next soft confirmation is built on
+    /// the first L1 block of the new fork above the L1 block of the last soft confirmation.
+    /// The forced transactions of the L1 blocks of the new fork below it are included in that
+    /// soft confirmation, and the deposits of the soft confirmations on the reorged L1 blocks
+    /// that were not processed by the bridge are queued again.
+    /// The soft confirmations on the reorged L1 blocks that are not committed yet are committed
+    /// right away, so no commitment spans both forks.
+    async fn handle_l1_reorg(&mut self, common_ancestor_height: u64) -> L1ReorgOutcome {
+        match self.reanchor_on_new_fork(common_ancestor_height).await {
+            Ok(outcome) => outcome,
+            Err(e) => L1ReorgOutcome::Halted {
+                reason: format!("Failed to re-anchor on the new L1 fork: {e}"),
+            },
+        }
+    }
+
+    async fn reanchor_on_new_fork(
+        &mut self,
+        common_ancestor_height: u64,
+    ) -> anyhow::Result<L1ReorgOutcome> {
+        let Some((head, head_soft_batch)) = self.ledger_db.get_head_soft_batch()? else {
+            return Ok(L1ReorgOutcome::NothingToReanchor);
+        };
+        if head_soft_batch.da_slot_height <= common_ancestor_height {
+            return Ok(L1ReorgOutcome::NothingToReanchor);
+        }
+
+        let mut first_l2_height = None;
+        for l1_height in common_ancestor_height + 1..=head_soft_batch.da_slot_height {
+            if let Some((start, _)) = self
+                .ledger_db
+                .get_l2_range_by_l1_height(SlotNumber(l1_height))?
+            {
+                first_l2_height = Some(start.0);
+                break;
+            }
+        }
+        let first_l2_height = first_l2_height.ok_or_else(|| {
+            anyhow!(
+                "No soft confirmation is recorded for the L1 blocks above {common_ancestor_height}"
+            )
+        })?;
+        let last_l2_height = head.0;
+        let next_l1_height = head_soft_batch.da_slot_height + 1;
+
+        warn!(
+            "Sequencer: soft confirmations {}-{} are built on reorged L1 blocks, the next one is built on L1 block {} of the new fork",
+            first_l2_height, last_l2_height, next_l1_height
+        );
+        // The prover expects the L1 blocks of a commitment to be chained, so the pending
+        // commitment ends at the reorged L1 blocks and the next one starts on the new fork
+        if let Some(commitment_info) = commitment_controller::get_commitment_info_up_to(
+            &self.ledger_db,
+            head_soft_batch.da_slot_height,
+        )? {
+            info!("Sequencer: committing the soft confirmations built on reorged L1 blocks");
+            self.submit_commitment_info(commitment_info).await?;
+        }
+        self.first_skipped_l1_height = Some(
+            self.first_skipped_l1_height
+                .map_or(common_ancestor_height + 1, |height| {
+                    height.min(common_ancestor_height + 1)
+                }),
+        );
+
+        let soft_batches = self.ledger_db.get_soft_batch_range(
+            &(BatchNumber(first_l2_height)..BatchNumber(last_l2_height + 1)),
+        )?;
+        let mut deposits = vec![];
+        for deposit in soft_batches
+            .iter()
+            .flat_map(|soft_batch| soft_batch.deposit_data.iter())
+        {
+            let Some(wtxid) = Bridge::deposit_wtxid(deposit) else {
+                continue;
+            };
+            let is_processed = is_deposit_processed::<C>(wtxid, self.storage.clone())
+                .map_err(|e| anyhow!("Failed to check deposit {}: {}", wtxid, e.message()))?;
+            if !is_processed {
+                deposits.push((wtxid, deposit.clone()));
+            }
+        }
+        let requeued_deposits = deposits.len();
+        self.deposit_mempool.lock().await.requeue_deposits(deposits);
+        info!(
+            "Sequencer: {} deposits of the soft confirmations on reorged L1 blocks are queued again",
+            requeued_deposits
+        );
+
+        Ok(L1ReorgOutcome::Reanchored {
+            first_l2_height,
+            last_l2_height,
+            next_l1_height,
+            requeued_deposits,
+        })
+    }
+
+    /// Valid forced transactions of the L1 blocks of the new fork that no soft confirmation is built on
+    async fn skipped_forced_txs(
+        &self,
+        l1_heights: RangeInclusive<u64>,
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut forced_txs = vec![];
+        for l1_height in l1_heights {
+            let da_block = self
+                .da_service
+                .get_block_at(l1_height)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch L1 block {}: {}", l1_height, e))?;
+            let mut da_data = self.da_service.extract_relevant_blobs(&da_block);
+            da_data.iter_mut().for_each(|blob| {
+                blob.full_data();
+            });
+            let skipped = self
+                .stf
+                .valid_forced_txs(&ForcedTransaction::from_blobs(&da_data));
+            if !skipped.is_empty() {
+                info!(
+                    "Including {} forced transactions from L1 block {} skipped after an L1 reorg",
+                    skipped.len(),
+                    l1_height
+                );
+            }
+            forced_txs.extend(skipped);
+        }
+        Ok(forced_txs)
+    }
+
     async fn submit_commitment(&self, prev_l1_height: u64) -> anyhow::Result<()> {
         debug!("Sequencer: new L1 block, checking if commitment should be submitted");
-        let inscription_queue = self.da_service.get_send_transaction_queue();
         let min_soft_confirmations_per_commitment =
             self.config.min_soft_confirmations_per_commitment;
         let commitment_info = commitment_controller::get_commitment_info(
@@ -583,87 +817,95 @@ where
 
         if let Some(commitment_info) = commitment_info {
             debug!("Sequencer: enough soft confirmations to submit commitment");
-            let l2_range_to_submit = commitment_info.l2_height_range.clone();
-
-            // calculate exclusive range end
-            let range_end = BatchNumber(l2_range_to_submit.end().0 + 1); // cannnot add u64 to BatchNumber directly
+            self.submit_commitment_info(commitment_info).await?;
+        }
+        Ok(())
+    }
 
-            let soft_confirmation_hashes = self
-                .ledger_db
-                .get_soft_batch_range(&(*l2_range_to_submit.start()..range_end))?
-                .iter()
-                .map(|sb| sb.hash)
-                .collect::<Vec<[u8; 32]>>();
+    async fn submit_commitment_info(
+        &self,
+        commitment_info: commitment_controller::CommitmentInfo,
+    ) -> anyhow::Result<()> {
+        let inscription_queue = self.da_service.get_send_transaction_queue();
+        let l2_range_to_submit = commitment_info.l2_height_range.clone();
 
-            let commitment = commitment_controller::get_commitment(
-                commitment_info.clone(),
-                soft_confirmation_hashes,
-            )?;
+        // calculate exclusive range end
+        let range_end = BatchNumber(l2_range_to_submit.end().0 + 1); // cannnot add u64 to BatchNumber directly
 
-            debug!("Sequencer: submitting commitment: {:?}", commitment);
+        let soft_confirmation_hashes = self
+            .ledger_db
+            .get_soft_batch_range(&(*l2_range_to_submit.start()..range_end))?
+            .iter()
+            .map(|sb| sb.hash)
+            .collect::<Vec<[u8; 32]>>();
+
+        let commitment = commitment_controller::get_commitment(
+            commitment_info.clone(),
+            soft_confirmation_hashes,
+        )?;
 
-            let blob = DaData::SequencerCommitment(commitment.clone())
-                .try_to_vec()
-                .map_err(|e| anyhow!(e))?;
-            let (notify, rx) = oneshot_channel();
-            let request = BlobWithNotifier { blob, notify };
-            inscription_queue
-                .send(request)
-                .map_err(|_| anyhow!("Bitcoin service already stopped!"))?;
-            let tx_id = rx
-                .await
-                .map_err(|_| anyhow!("DA service is dead!"))?
-                .map_err(|_| anyhow!("Send transaction cannot fail"))?;
+        debug!("Sequencer: submitting commitment: {:?}", commitment);
 
-            self.ledger_db
-                .set_last_sequencer_commitment_l1_height(SlotNumber(
-                    commitment_info.l1_height_range.end().0,
-                ))
-                .map_err(|_| {
-                    anyhow!("Sequencer: Failed to set last sequencer commitment L1 height")
-                })?;
-
-            debug!("Commitment info: {:?}", commitment_info);
-            let l1_start_height = commitment_info.l1_height_range.start().0;
-            let l1_end_height = commitment_info.l1_height_range.end().0;
-            let l2_start = l2_range_to_submit.start().0 as u32;
-            let l2_end = l2_range_to_submit.end().0 as u32;
-            if let Some(db_config) = self.config.db_config.clone() {
-                match PostgresConnector::new(db_config).await {
-                    Ok(pg_connector) => {
-                        pg_connector
-                            .insert_sequencer_commitment(
-                                l1_start_height as u32,
-                                l1_end_height as u32,
-                                Into::<[u8; 32]>::into(tx_id).to_vec(),
-                                commitment.l1_start_block_hash.to_vec(),
-                                commitment.l1_end_block_hash.to_vec(),
-                                l2_start,
-                                l2_end,
-                                commitment.merkle_root.to_vec(),
-                                CommitmentStatus::Mempool,
-                            )
-                            .await
-                            .map_err(|_| {
-                                anyhow!("Sequencer: Failed to insert sequencer commitment")
-                            })?;
-                    }
-                    Err(e) => {
-                        warn!("Failed to connect to postgres: {:?}", e);
-                    }
+        let blob = DaData::SequencerCommitment(commitment.clone())
+            .try_to_vec()
+            .map_err(|e| anyhow!(e))?;
+        let (notify, rx) = oneshot_channel();
+        let request = BlobWithNotifier { blob, notify };
+        inscription_queue
+            .send(request)
+            .map_err(|_| anyhow!("Bitcoin service already stopped!"))?;
+        let tx_id = rx
+            .await
+            .map_err(|_| anyhow!("DA service is dead!"))?
+            .map_err(|_| anyhow!("Send transaction cannot fail"))?;
+
+        self.ledger_db
+            .set_last_sequencer_commitment_l1_height(SlotNumber(
+                commitment_info.l1_height_range.end().0,
+            ))
+            .map_err(|_| anyhow!("Sequencer: Failed to set last sequencer commitment L1 height"))?;
+
+        debug!("Commitment info: {:?}", commitment_info);
+        let l1_start_height = commitment_info.l1_height_range.start().0;
+        let l1_end_height = commitment_info.l1_height_range.end().0;
+        let l2_start = l2_range_to_submit.start().0 as u32;
+        let l2_end = l2_range_to_submit.end().0 as u32;
+        if let Some(db_config) = self.config.db_config.clone() {
+            match PostgresConnector::new(db_config).await {
+                Ok(pg_connector) => {
+                    pg_connector
+                        .insert_sequencer_commitment(
+                            l1_start_height as u32,
+                            l1_end_height as u32,
+                            Into::<[u8; 32]>::into(tx_id).to_vec(),
+                            commitment.l1_start_block_hash.to_vec(),
+                            commitment.l1_end_block_hash.to_vec(),
+                            l2_start,
+                            l2_end,
+                            commitment.merkle_root.to_vec(),
+                            CommitmentStatus::Mempool,
+                        )
+                        .await
+                        .map_err(|_| anyhow!("Sequencer: Failed to insert sequencer commitment"))?;
+                }
+                Err(e) => {
+                    warn!("Failed to connect to postgres: {:?}", e);
                 }
             }
-
-            info!(
-                "New commitment. L2 range: #{}-{}, L1 Range #{}-{}",
-                l2_start, l2_end, l1_start_height, l1_end_height,
-            );
         }
+
+        info!(
+            "New commitment. L2 range: #{}-{}, L1 Range #{}-{}",
+            l2_start, l2_end, l1_start_height, l1_end_height,
+        );
         Ok(())
     }
 
-    #[instrument(level = "trace", skip(self), err, ret)]
-    pub async fn run(&mut self) -> Result<(), anyhow::Error> {
+    /// Produces L2 blocks until `shutdown` is cancelled.
+    /// On shutdown the block in progress and the due commitments are finished, and the DA queue
+    /// and pending offchain db writes are awaited before returning.
+    #[instrument(level = "trace", skip(self, shutdown), err, ret)]
+    pub async fn run(&mut self, shutdown: CancellationToken) -> Result<(), anyhow::Error> {
         // TODO: hotfix for mock da
         self.da_service
             .get_block_at(1)
@@ -687,6 +929,12 @@ where
                             warn!("Sequencer: Mempool restore error: {:?}", e);
                         }
                     }
+                    match self.restore_deposit_mempool(pg_connector.clone()).await {
+                        Ok(()) => debug!("Sequencer: Deposit mempool restored"),
+                        Err(e) => {
+                            warn!("Sequencer: Deposit mempool restore error: {:?}", e);
+                        }
+                    }
                     Some(pg_connector)
                 }
                 Err(e) => {
@@ -722,6 +970,16 @@ where
 
         debug!("Sequencer: Last used L1 height: {:?}", last_used_l1_height);
 
+        // Reorgs of finalized L1 blocks are received from the header stream,
+        // new finalized blocks are picked up by the DA monitor
+        let header_stream = self
+            .da_service
+            .subscribe_finalized_header()
+            .await
+            .map_err(|e| anyhow!(e))?;
+        tokio::pin!(header_stream);
+        let mut production_halted = false;
+
         // Setup required workers to update our knowledge of the DA layer every X seconds (configurable).
         let (da_height_update_tx, mut da_height_update_rx) = mpsc::channel(1);
         let (da_commitment_tx, mut da_commitment_rx) = unbounded::<u64>();
@@ -729,6 +987,7 @@ where
             self.da_service.clone(),
             da_height_update_tx,
             self.config.da_update_interval_ms,
+            shutdown.clone(),
         );
         tokio::pin!(da_monitor);
 
@@ -747,8 +1006,12 @@ where
             interval.tick().await;
 
             tokio::select! {
-                // Run the DA monitor worker
-                _ = &mut da_monitor => {},
+                _ = shutdown.cancelled() => {
+                    info!("Sequencer: shutdown requested, stopping block production");
+                    break;
+                },
+                // Run the DA monitor worker, it only returns on shutdown
+                _ = &mut da_monitor => break,
                 // Receive updates from DA layer worker.
                 l1_data = da_height_update_rx.recv() => {
                     // Stop receiving updates from DA layer until we have caught up.
@@ -778,6 +1041,45 @@ where
                         }
                     }
                 },
+                Some(event) = header_stream.next() => {
+                    let (common_ancestor, reorged) = match event {
+                        Ok(FinalizedHeaderEvent::Reorg { common_ancestor, reorged }) => (common_ancestor, reorged),
+                        Ok(FinalizedHeaderEvent::Finalized(_)) => continue,
+                        Err(e) => {
+                            error!("Sequencer: finalized L1 header stream error: {}", e);
+                            continue;
+                        }
+                    };
+                    let common_ancestor_height = common_ancestor.height();
+                    warn!(
+                        "Sequencer: L1 reorged {} finalized blocks above height {}",
+                        reorged.len(),
+                        common_ancestor_height
+                    );
+
+                    let outcome = self.handle_l1_reorg(common_ancestor_height).await;
+                    match &outcome {
+                        L1ReorgOutcome::Halted { reason } => {
+                            error!("Sequencer: halting block production after L1 reorg: {}", reason);
+                            production_halted = true;
+                        }
+                        outcome => info!("Sequencer: handled L1 reorg: {:?}", outcome),
+                    }
+                    self.l1_reorg_status.lock().await.record(L1Reorg {
+                        common_ancestor_height,
+                        common_ancestor_hash: hex::encode(common_ancestor.hash().into()),
+                        reorged_hashes: reorged
+                            .iter()
+                            .map(|header| hex::encode(header.hash().into()))
+                            .collect(),
+                        outcome,
+                    });
+
+                    // An update of the DA monitor sent before the reorg may be a reorged block,
+                    // blocks are built on the new fork once the DA monitor reports it above the last used L1 block
+                    while da_height_update_rx.try_recv().is_ok() {}
+                    missed_da_blocks_count = 0;
+                },
                 prev_l1_height = da_commitment_rx.select_next_some() => {
                     if let Err(e) = self.submit_commitment(prev_l1_height).await {
                         error!("Failed to submit commitment: {}", e);
@@ -786,7 +1088,7 @@ where
                 // If sequencer is in test mode, it will build a block every time it receives a message
                 // The RPC from which the sender can be called is only registered for test mode. This means
                 // that evey though we check the receiver here, it'll never be "ready" to be consumed unless in test mode.
-                _ = self.l2_force_block_rx.next(), if self.config.test_mode => {
+                _ = self.l2_force_block_rx.next(), if self.config.test_mode && !production_halted => {
                     if missed_da_blocks_count > 0 {
                         debug!("We have {} missed DA blocks", missed_da_blocks_count);
                         for _ in 1..=missed_da_blocks_count {
@@ -824,7 +1126,7 @@ where
                     }
                 },
                 // If sequencer is in production mode, it will build a block every 2 seconds
-                _ = interval.tick(), if !self.config.test_mode => {
+                _ = interval.tick(), if !self.config.test_mode && !production_halted => {
                     // By default, we produce a non-empty block IFF we were caught up all the way to
                     // last_finalized_block. If there are missed DA blocks, we start producing
                     // empty blocks at ~2 second rate, 1 L2 block per respective missed DA block
@@ -877,6 +1179,40 @@ where
                 }
             }
         }
+
+        // Commitments that are due are submitted, and the blobs queued to the DA service are
+        // sent before stopping. If that takes too long, nothing is lost: the next commitment is
+        // computed from the last committed L1 height, which is only updated after sending.
+        da_commitment_rx.close();
+        let drain_da_queue = async {
+            while let Ok(Some(prev_l1_height)) = da_commitment_rx.try_next() {
+                if let Err(e) = self.submit_commitment(prev_l1_height).await {
+                    error!("Failed to submit commitment: {}", e);
+                }
+            }
+            self.da_service.close_send_transaction_queue().await;
+        };
+        if tokio::time::timeout(DA_QUEUE_SHUTDOWN_TIMEOUT, drain_da_queue)
+            .await
+            .is_err()
+        {
+            warn!(
+                "Sequencer: DA queue did not finish in {:?}, remaining commitments will be submitted after restart",
+                DA_QUEUE_SHUTDOWN_TIMEOUT
+            );
+        }
+
+        self.pg_tasks.close();
+        if !self.pg_tasks.is_empty() {
+            info!(
+                "Sequencer: waiting for {} offchain db writes to finish",
+                self.pg_tasks.len()
+            );
+        }
+        self.pg_tasks.wait().await;
+
+        info!("Sequencer: stopped");
+        Ok(())
     }
 
     fn get_best_transactions(
@@ -983,6 +1319,7 @@ where
             storage: self.storage.clone(),
             test_mode: self.config.test_mode,
             pg_pool,
+            l1_reorg_status: self.l1_reorg_status.clone(),
         }
     }
 
@@ -994,6 +1331,33 @@ where
         let rpc_context = self.create_rpc_context().await;
         let rpc = create_rpc_module(rpc_context)?;
         rpc_methods.merge(rpc)?;
+
+        let subscription_manager = SubscriptionManager::new::<C>(
+            self.storage.clone(),
+            self.ledger_db.subscribe_soft_batches(),
+        );
+        let mut pending_transactions_rx = self.mempool.pending_transactions_listener();
+        let publisher = subscription_manager.clone();
+        tokio::spawn(async move {
+            while let Some(hash) = pending_transactions_rx.recv().await {
+                publisher.publish_pending_transaction(hash);
+            }
+        });
+        let filter_manager = FilterManager::<C>::new(
+            self.storage.clone(),
+            subscription_manager.clone(),
+            FilterConfig {
+                max_filters_per_connection: self.rpc_config.max_filters_per_connection,
+                max_filters: self.rpc_config.max_filters,
+                idle_timeout: Duration::from_secs(self.rpc_config.filter_idle_timeout_secs),
+            },
+        );
+        rpc_methods.merge(create_filter_rpc_module(
+            filter_manager,
+            self.ledger_db.clone(),
+        )?)?;
+        rpc_methods.merge(create_subscription_rpc_module(subscription_manager)?)?;
+
         Ok(rpc_methods)
     }
 
@@ -1012,6 +1376,23 @@ where
         Ok(())
     }
 
+    /// Restores deposits that were accepted but not yet included in a block,
+    /// keeping the order they were accepted in.
+    pub async fn restore_deposit_mempool(
+        &self,
+        pg_connector: PostgresConnector,
+    ) -> Result<(), anyhow::Error> {
+        let deposit_txs = pg_connector.get_all_deposit_txs().await?;
+        let mut deposit_mempool = self.deposit_mempool.lock().await;
+        for deposit_tx in deposit_txs {
+            match Bridge::deposit_wtxid(&deposit_tx.tx) {
+                Some(wtxid) => deposit_mempool.add_deposit_tx(wtxid, deposit_tx.tx),
+                None => warn!("Skipping undecodable deposit tx from db"),
+            }
+        }
+        Ok(())
+    }
+
     pub async fn compare_commitments_from_db(
         &self,
         pg_connector: PostgresConnector,
@@ -1042,10 +1423,8 @@ where
         last_used_l1_height: u64,
     ) -> anyhow::Result<()> {
         let commit_up_to = match last_finalized_height.cmp(&last_used_l1_height) {
-            Ordering::Less => {
-                panic!("DA L1 height is less than Ledger finalized height. DA L1 height: {}, Finalized height: {}", last_finalized_height, last_used_l1_height);
-            }
-            Ordering::Equal => None,
+            // Only happens after an L1 reorg, until the new fork is above the last used L1 block
+            Ordering::Less | Ordering::Equal => None,
             Ordering::Greater => {
                 let commit_up_to = last_finalized_height - 1;
                 Some(commit_up_to)
@@ -1106,12 +1485,20 @@ where
         .map_err(|e| anyhow::anyhow!("Error reading min max l1 fee rate: {}", e))
 }
 
-async fn da_block_monitor<Da>(da_service: Da, sender: mpsc::Sender<L1Data<Da>>, loop_interval: u64)
-where
+async fn da_block_monitor<Da>(
+    da_service: Da,
+    sender: mpsc::Sender<L1Data<Da>>,
+    loop_interval: u64,
+    shutdown: CancellationToken,
+) where
     Da: DaService + Clone,
 {
     loop {
-        let l1_data = match get_da_block_data(da_service.clone()).await {
+        let l1_data = tokio::select! {
+            _ = shutdown.cancelled() => return,
+            l1_data = get_da_block_data(da_service.clone()) => l1_data,
+        };
+        let l1_data = match l1_data {
             Ok(l1_data) => l1_data,
             Err(e) => {
                 error!("Could not fetch L1 data, {}", e);
@@ -1121,7 +1508,10 @@ where
 
         let _ = sender.send(l1_data).await;
 
-        sleep(Duration::from_millis(loop_interval)).await;
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = sleep(Duration::from_millis(loop_interval)) => {},
+        }
     }
 }
 
@@ -4,4 +4,6 @@ pub mod tables;
 
 pub use config::SharedBackupDbConfig;
 pub use postgres_connector::PostgresConnector;
-pub use tables::{CommitmentStatus, DbProof, DbSequencerCommitment, ProofType, Tables};
+pub use tables::{
+    CommitmentStatus, DbDepositTx, DbProof, DbSequencerCommitment, ProofType, Tables,
+};
@@ -8,9 +8,10 @@ use tracing::{debug, instrument};
 
 use crate::config::SharedBackupDbConfig;
 use crate::tables::{
-    CommitmentStatus, DbMempoolTx, DbProof, DbSequencerCommitment, ProofType, Tables,
-    INDEX_L1_END_HASH, INDEX_L1_END_HEIGHT, INDEX_L2_END_HEIGHT, MEMPOOL_TXS_TABLE_CREATE_QUERY,
-    PROOF_TABLE_CREATE_QUERY, SEQUENCER_COMMITMENT_TABLE_CREATE_QUERY,
+    CommitmentStatus, DbDepositTx, DbMempoolTx, DbProof, DbSequencerCommitment, ProofType, Tables,
+    DEPOSIT_TXS_TABLE_CREATE_QUERY, INDEX_L1_END_HASH, INDEX_L1_END_HEIGHT, INDEX_L2_END_HEIGHT,
+    MEMPOOL_TXS_TABLE_CREATE_QUERY, PROOF_TABLE_CREATE_QUERY,
+    SEQUENCER_COMMITMENT_TABLE_CREATE_QUERY,
 };
 
 #[derive(Clone)]
@@ -58,6 +59,7 @@ impl PostgresConnector {
             .batch_execute(SEQUENCER_COMMITMENT_TABLE_CREATE_QUERY)
             .await?;
         client.batch_execute(MEMPOOL_TXS_TABLE_CREATE_QUERY).await?;
+        client.batch_execute(DEPOSIT_TXS_TABLE_CREATE_QUERY).await?;
         client.batch_execute(PROOF_TABLE_CREATE_QUERY).await?;
         let db_client = Self { client: pool };
 
@@ -119,6 +121,10 @@ impl PostgresConnector {
             .batch_execute(MEMPOOL_TXS_TABLE_CREATE_QUERY)
             .await
             .unwrap();
+        test_client
+            .batch_execute(DEPOSIT_TXS_TABLE_CREATE_QUERY)
+            .await
+            .unwrap();
         test_client
             .batch_execute(PROOF_TABLE_CREATE_QUERY)
             .await
@@ -223,6 +229,43 @@ impl PostgresConnector {
             .await?)
     }
 
+    #[instrument(level = "trace", skip(self, tx), err, ret)]
+    pub async fn insert_deposit_tx(&self, tx_hash: Vec<u8>, tx: Vec<u8>) -> Result<u64, PoolError> {
+        let client = self.client().await?;
+        Ok(client
+            .execute(
+                "INSERT INTO deposit_txs (tx_hash, tx) VALUES ($1, $2);",
+                &[&tx_hash, &tx],
+            )
+            .await?)
+    }
+
+    /// Returns the deposit txs in the order they were accepted
+    #[instrument(level = "trace", skip(self), err)]
+    pub async fn get_all_deposit_txs(&self) -> Result<Vec<DbDepositTx>, PoolError> {
+        let client = self.client().await?;
+        Ok(client
+            .query("SELECT * FROM deposit_txs ORDER BY id ASC", &[])
+            .await?
+            .iter()
+            .map(PostgresConnector::row_to_deposit_tx)
+            .collect())
+    }
+
+    #[instrument(level = "trace", skip_all, err, ret)]
+    pub async fn delete_deposit_txs_by_tx_hashes(
+        &self,
+        tx_hashes: Vec<Vec<u8>>,
+    ) -> Result<u64, PoolError> {
+        let client = self.client().await?;
+        Ok(client
+            .execute(
+                "DELETE FROM deposit_txs WHERE tx_hash = ANY($1);",
+                &[&tx_hashes],
+            )
+            .await?)
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[instrument(level = "trace", skip_all, fields(l1_tx_id), err, ret)]
     pub async fn insert_proof_data(
@@ -269,6 +312,7 @@ impl PostgresConnector {
         let query = match table {
             Tables::SequencerCommitment => SEQUENCER_COMMITMENT_TABLE_CREATE_QUERY,
             Tables::MempoolTxs => MEMPOOL_TXS_TABLE_CREATE_QUERY,
+            Tables::DepositTxs => DEPOSIT_TXS_TABLE_CREATE_QUERY,
             Tables::Proof => PROOF_TABLE_CREATE_QUERY,
         };
         client.execute(query, &[]).await.unwrap();
@@ -297,6 +341,13 @@ impl PostgresConnector {
         }
     }
 
+    fn row_to_deposit_tx(row: &Row) -> DbDepositTx {
+        DbDepositTx {
+            tx_hash: row.get("tx_hash"),
+            tx: row.get("tx"),
+        }
+    }
+
     fn row_to_proof(row: &Row) -> DbProof {
         DbProof {
             l1_tx_id: row.get("l1_tx_id"),
@@ -398,6 +449,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_insert_deposit_tx() {
+        let client = PostgresConnector::new_test_client("insert_deposit_tx".to_owned())
+            .await
+            .unwrap();
+        client.create_table(Tables::DepositTxs).await;
+
+        client
+            .insert_deposit_tx(vec![1, 2, 3], vec![1, 2, 4])
+            .await
+            .unwrap();
+        client
+            .insert_deposit_tx(vec![3, 4, 5], vec![10, 20, 42])
+            .await
+            .unwrap();
+        client
+            .insert_deposit_tx(vec![5, 6, 7], vec![12, 22, 42])
+            .await
+            .unwrap();
+
+        // the same deposit cannot be stored twice
+        assert!(client
+            .insert_deposit_tx(vec![1, 2, 3], vec![1, 2, 4])
+            .await
+            .is_err());
+
+        let txs = client.get_all_deposit_txs().await.unwrap();
+        assert_eq!(
+            txs.iter().map(|tx| tx.tx_hash.clone()).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3], vec![3, 4, 5], vec![5, 6, 7]]
+        );
+
+        client
+            .delete_deposit_txs_by_tx_hashes(vec![vec![1, 2, 3], vec![5, 6, 7]])
+            .await
+            .unwrap();
+
+        let txs = client.get_all_deposit_txs().await.unwrap();
+
+        assert_eq!(
+            txs,
+            vec![DbDepositTx {
+                tx_hash: vec![3, 4, 5],
+                tx: vec![10, 20, 42]
+            }]
+        );
+
+        client.drop_table(Tables::DepositTxs).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_insert_proof_data() {
         let client = PostgresConnector::new_test_client("test_insert_proof_data".to_string())
@@ -8,6 +8,7 @@ pub enum Tables {
     #[allow(dead_code)]
     SequencerCommitment,
     MempoolTxs,
+    DepositTxs,
     Proof,
 }
 
@@ -17,6 +18,7 @@ impl fmt::Display for Tables {
         match self {
             Tables::SequencerCommitment => write!(f, "sequencer_commitments"),
             Tables::MempoolTxs => write!(f, "mempool_txs"),
+            Tables::DepositTxs => write!(f, "deposit_txs"),
             Tables::Proof => write!(f, "proof"),
         }
     }
@@ -110,6 +112,22 @@ pub struct DbMempoolTx {
     pub tx: Vec<u8>,
 }
 
+// tx is the raw deposit data accepted by citrea_sendRawDepositTransaction
+pub const DEPOSIT_TXS_TABLE_CREATE_QUERY: &str = "
+CREATE TABLE IF NOT EXISTS deposit_txs (
+    id          SERIAL PRIMARY KEY,
+    tx_hash     BYTEA NOT NULL UNIQUE,
+    tx          BYTEA NOT NULL
+);";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbDepositTx {
+    /// Hash of the deposit data
+    pub tx_hash: Vec<u8>,
+    /// Raw deposit data
+    pub tx: Vec<u8>,
+}
+
 pub const PROOF_TABLE_CREATE_QUERY: &str = "
 CREATE TABLE IF NOT EXISTS proof (
     id                          SERIAL PRIMARY KEY,
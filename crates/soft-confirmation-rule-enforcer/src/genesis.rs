@@ -15,6 +15,8 @@ pub struct SoftConfirmationRuleEnforcerConfig<C: Context> {
     /// L1 fee rate change percentage
     /// Out of 100.
     pub(crate) l1_fee_rate_change_percentage: u128,
+    /// Number of L2 blocks the sequencer has to include a forced transaction in.
+    pub(crate) max_l2_blocks_to_include_forced_tx: u64,
 }
 
 impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
@@ -28,6 +30,12 @@ impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
             .set(&config.max_l2_blocks_per_l1, working_set);
         self.l1_fee_rate_change_percentage
             .set(&config.l1_fee_rate_change_percentage, working_set);
+        anyhow::ensure!(
+            config.max_l2_blocks_to_include_forced_tx > 0,
+            "Max L2 blocks to include forced tx must be greater than 0"
+        );
+        self.max_l2_blocks_to_include_forced_tx
+            .set(&config.max_l2_blocks_to_include_forced_tx, working_set);
         Ok(())
     }
 }
@@ -1,10 +1,11 @@
 use sov_modules_api::hooks::{ApplySoftConfirmationError, HookSoftConfirmationInfo};
-use sov_modules_api::{Context, DaSpec, StateMapAccessor, StateValueAccessor, WorkingSet};
+use sov_modules_api::{Context, DaSpec, Spec, StateMapAccessor, StateValueAccessor, WorkingSet};
+use sov_rollup_interface::digest::Digest;
 use sov_state::Storage;
 #[cfg(feature = "native")]
 use tracing::instrument;
 
-use crate::SoftConfirmationRuleEnforcer;
+use crate::{PendingForcedTx, SoftConfirmationRuleEnforcer};
 
 impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da>
 where
@@ -122,8 +123,69 @@ where
         Ok(())
     }
 
+    /// Checks the forced transaction rule.
+    /// Forced transactions of an L1 block are registered by the first L2 block built on it,
+    /// and each of them has to be included in one of the next max L2 blocks to include forced tx L2 blocks.
+    /// If a forced transaction is not included by then, the soft confirmation should fail and not be accepted by full nodes.
+    /// Together with the block count rule, this ensures the sequencer cannot censor transactions published on L1.
+    /// Must run after the block count rule, which counts the L2 blocks on the L1 block.
+    #[cfg_attr(feature = "native", instrument(level = "trace", skip_all, err, ret))]
+    fn apply_forced_tx_rule(
+        &self,
+        soft_batch: &mut HookSoftConfirmationInfo,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ApplySoftConfirmationError> {
+        let l2_block_number = self.l2_block_count.get(working_set).unwrap_or(0);
+        let mut pending_forced_txs = self.pending_forced_txs.get(working_set).unwrap_or_default();
+
+        let is_first_l2_block_on_l1 = self
+            .da_root_hash_to_number
+            .get(&soft_batch.da_slot_hash(), working_set)
+            == Some(1);
+        if is_first_l2_block_on_l1 && !soft_batch.forced_txs().is_empty() {
+            let max_l2_blocks_to_include_forced_tx = self
+                .max_l2_blocks_to_include_forced_tx
+                .get(working_set)
+                .expect("Max L2 blocks to include forced tx must be set");
+            let deadline = l2_block_number + max_l2_blocks_to_include_forced_tx - 1;
+
+            for forced_tx in soft_batch.forced_txs() {
+                pending_forced_txs.push(PendingForcedTx {
+                    tx_hash: <C as Spec>::Hasher::digest(forced_tx).into(),
+                    deadline,
+                });
+            }
+        }
+
+        if !pending_forced_txs.is_empty() {
+            let included_tx_hashes: Vec<[u8; 32]> = soft_batch
+                .txs()
+                .iter()
+                .map(|tx| <C as Spec>::Hasher::digest(tx).into())
+                .collect();
+            pending_forced_txs.retain(|forced_tx| !included_tx_hashes.contains(&forced_tx.tx_hash));
+
+            if let Some(overdue) = pending_forced_txs
+                .iter()
+                .find(|forced_tx| forced_tx.deadline <= l2_block_number)
+            {
+                return Err(ApplySoftConfirmationError::ForcedTransactionNotIncluded {
+                    tx_hash: overdue.tx_hash,
+                    deadline: overdue.deadline,
+                });
+            }
+
+            self.pending_forced_txs
+                .set(&pending_forced_txs, working_set);
+        }
+
+        self.l2_block_count.set(&(l2_block_number + 1), working_set);
+
+        Ok(())
+    }
+
     /// Logic executed at the beginning of the soft confirmation.
-    /// Checks two rules: block count rule and fee rate rule.
+    /// Checks four rules: block count rule, forced transaction rule, fee rate rule and timestamp rule.
     #[cfg_attr(
         feature = "native",
         instrument(level = "trace", skip(self, working_set), err, ret)
@@ -135,6 +197,8 @@ where
     ) -> Result<(), ApplySoftConfirmationError> {
         self.apply_block_count_rule(soft_batch, working_set)?;
 
+        self.apply_forced_tx_rule(soft_batch, working_set)?;
+
         self.apply_fee_rate_rule(soft_batch, working_set)?;
 
         self.apply_timestamp_rule(soft_batch, working_set)?;
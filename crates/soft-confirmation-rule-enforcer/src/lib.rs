@@ -13,9 +13,19 @@ pub use query::*;
 mod tests;
 
 // "Given DA slot hasn't been used for more than N soft confirmation blocks."
+use serde::{Deserialize, Serialize};
 use sov_modules_api::{Context, DaSpec, ModuleInfo, StateMap, StateValue, WorkingSet};
 use sov_state::codec::BcsCodec;
 
+/// A forced transaction the sequencer has not included yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingForcedTx {
+    /// Hash of the raw transaction.
+    pub tx_hash: [u8; 32],
+    /// Last L2 block the transaction can be included in.
+    pub deadline: u64,
+}
+
 #[derive(ModuleInfo, Clone)]
 pub struct SoftConfirmationRuleEnforcer<C: Context, Da: DaSpec> {
     /// Address of the SoftConfirmationRuleEnforcer module.
@@ -43,6 +53,16 @@ pub struct SoftConfirmationRuleEnforcer<C: Context, Da: DaSpec> {
     /// Sequencer's block timestamp
     #[state]
     pub(crate) last_timestamp: StateValue<u64, BcsCodec>,
+    /// Number of L2 blocks the sequencer has to include a forced transaction in,
+    /// counting from the first L2 block on the L1 block the forced transaction was found in.
+    #[state]
+    pub(crate) max_l2_blocks_to_include_forced_tx: StateValue<u64, BcsCodec>,
+    /// Number of soft confirmations applied so far.
+    #[state]
+    pub(crate) l2_block_count: StateValue<u64, BcsCodec>,
+    /// Forced transactions that are not included yet, in the order they were found on L1.
+    #[state]
+    pub(crate) pending_forced_txs: StateValue<Vec<PendingForcedTx>, BcsCodec>,
     /// Phantom state using the da type.
     /// This is used to make sure that the state is generic over the DA type.
     #[allow(dead_code)]
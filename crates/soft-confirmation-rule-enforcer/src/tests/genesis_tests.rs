@@ -19,6 +19,7 @@ lazy_static! {
             )
             .unwrap(),
             l1_fee_rate_change_percentage: 10,
+            max_l2_blocks_to_include_forced_tx: 3,
         };
 }
 
@@ -29,6 +30,7 @@ fn genesis_data() {
     let max_l2_blocks_per_l1 = &TEST_CONFIG.max_l2_blocks_per_l1;
     let authority = &TEST_CONFIG.authority;
     let l1_fee_rate_change_percentage = TEST_CONFIG.l1_fee_rate_change_percentage;
+    let max_l2_blocks_to_include_forced_tx = TEST_CONFIG.max_l2_blocks_to_include_forced_tx;
 
     assert_eq!(
         soft_confirmation_rule_enforcer
@@ -52,6 +54,14 @@ fn genesis_data() {
             .unwrap(),
         l1_fee_rate_change_percentage
     );
+
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .max_l2_blocks_to_include_forced_tx
+            .get(&mut working_set)
+            .unwrap(),
+        max_l2_blocks_to_include_forced_tx
+    );
 }
 
 pub(crate) fn get_soft_confirmation_rule_enforcer<Da: DaSpec>(
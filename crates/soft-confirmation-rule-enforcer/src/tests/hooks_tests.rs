@@ -3,8 +3,10 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::hooks::{ApplySoftConfirmationError, HookSoftConfirmationInfo};
 use sov_modules_api::utils::generate_address;
 use sov_modules_api::{Context, Module, Spec, StateValueAccessor};
+use sov_rollup_interface::digest::Digest;
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 
 use crate::call::CallMessage;
@@ -298,3 +300,83 @@ fn begin_soft_confirmation_hook_checks_timestamp() {
 
     assert!(res.is_ok());
 }
+
+#[test]
+fn begin_soft_confirmation_hook_checks_forced_txs() {
+    let (soft_confirmation_rule_enforcer, mut working_set) =
+        get_soft_confirmation_rule_enforcer::<MockDaSpec>(&TEST_CONFIG);
+
+    let forced_tx_1 = vec![1u8; 32];
+    let forced_tx_2 = vec![2u8; 32];
+
+    let soft_confirmation_info = |txs: Vec<Vec<u8>>, forced_txs: Vec<Vec<u8>>| {
+        let mut info: HookSoftConfirmationInfo = SignedSoftConfirmationBatch::new(
+            [0; 32],
+            0,
+            [1; 32],
+            [0; 32],
+            vec![],
+            100,
+            txs,
+            vec![],
+            vec![],
+            vec![],
+            10,
+        )
+        .into();
+        info.forced_txs = forced_txs;
+        info
+    };
+
+    // first L2 block on the L1 block registers the forced txs and includes one of them
+    let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
+        &mut soft_confirmation_info(
+            vec![forced_tx_1.clone()],
+            vec![forced_tx_1.clone(), forced_tx_2.clone()],
+        ),
+        &mut working_set,
+    );
+    assert!(res.is_ok());
+
+    // forced txs are only registered by the first L2 block on the L1 block
+    let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
+        &mut soft_confirmation_info(vec![], vec![vec![3u8; 32]]),
+        &mut working_set,
+    );
+    assert!(res.is_ok());
+
+    // the third L2 block is the last one the remaining forced tx can be included in
+    let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
+        &mut soft_confirmation_info(vec![], vec![]),
+        &mut working_set,
+    );
+    let forced_tx_2_hash: [u8; 32] = <C as Spec>::Hasher::digest(&forced_tx_2).into();
+    assert!(matches!(
+        res,
+        Err(ApplySoftConfirmationError::ForcedTransactionNotIncluded {
+            tx_hash,
+            deadline: 2,
+        }) if tx_hash == forced_tx_2_hash
+    ));
+
+    let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
+        &mut soft_confirmation_info(vec![forced_tx_2], vec![]),
+        &mut working_set,
+    );
+    assert!(res.is_ok());
+
+    assert!(soft_confirmation_rule_enforcer
+        .pending_forced_txs
+        .get(&mut working_set)
+        .unwrap()
+        .is_empty());
+
+    // no forced txs are pending anymore
+    for _ in 0..3 {
+        let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
+            &mut soft_confirmation_info(vec![], vec![]),
+            &mut working_set,
+        );
+        assert!(res.is_ok());
+    }
+}
@@ -35,6 +35,21 @@ impl DbConnector {
         )
         .expect("DbConnector: failed to create table");
 
+        // Blocks that were replaced by a fork, they can still be fetched by hash
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS orphaned_blocks (
+                    prev_hash BLOB,
+                    hash BLOB,
+                    txs_commitment BLOB,
+                    height INTEGER,
+                    time TEXT,
+                    is_valid INTEGER,
+                    blobs TEXT
+                );",
+            (),
+        )
+        .expect("DbConnector: failed to create orphaned blocks table");
+
         Self { conn }
     }
 
@@ -76,7 +91,10 @@ impl DbConnector {
     pub fn get_by_hash(&self, hash: [u8; 32]) -> Option<MockBlock> {
         let mut stmt = self
             .conn
-            .prepare("SELECT * FROM blocks WHERE hash = ?")
+            .prepare(
+                "SELECT * FROM blocks WHERE hash = ?1
+                UNION ALL SELECT * FROM orphaned_blocks WHERE hash = ?1",
+            )
             .unwrap();
         let mut rows = stmt
             .query(params![hash])
@@ -99,7 +117,14 @@ impl DbConnector {
         count as usize
     }
 
-    pub fn prune_above(&self, height: u64) {
+    /// Removes the blocks above the given height, they stay available by hash.
+    pub fn orphan_above(&self, height: u64) {
+        self.conn
+            .execute(
+                "INSERT INTO orphaned_blocks SELECT * FROM blocks WHERE height > ?",
+                params![height],
+            )
+            .expect("DbConnector: failed to keep orphaned blocks");
         self.conn
             .execute("DELETE FROM blocks WHERE height > ?", params![height])
             .expect("DbConnector: failed to prune");
@@ -215,7 +240,7 @@ mod tests {
     }
 
     #[test]
-    fn test_prune_above() {
+    fn test_orphan_above() {
         let db_path = tempfile::tempdir().unwrap();
         let db = DbConnector::new(db_path.path());
 
@@ -223,15 +248,17 @@ mod tests {
         let block2 = get_test_block(2);
 
         db.push_back(block1);
-        db.push_back(block2);
+        db.push_back(block2.clone());
 
-        db.prune_above(2);
+        db.orphan_above(2);
 
         assert_eq!(db.len(), 2);
 
-        db.prune_above(1);
+        db.orphan_above(1);
 
         assert_eq!(db.len(), 1);
+        assert!(db.get(1).is_none());
+        assert_eq!(db.get_by_hash(block2.header.hash.into()), Some(block2));
     }
 
     #[test]
@@ -9,9 +9,11 @@ use pin_project::pin_project;
 use sha2::Digest;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, Time};
 use sov_rollup_interface::maybestd::sync::Arc;
-use sov_rollup_interface::services::da::{BlobWithNotifier, DaService, SlotData};
-use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
-use tokio::sync::{broadcast, Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+use sov_rollup_interface::services::da::{
+    BlobWithNotifier, DaService, FinalizedHeaderEvent, SlotData,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 use tokio::time;
 use tracing::{instrument, Instrument};
 
@@ -29,6 +31,12 @@ const GENESIS_HEADER: MockBlockHeader = MockBlockHeader {
     time: Time::from_secs(1672531200),
 };
 
+/// How often subscriptions to finalized headers check the blocks for changes.
+const FINALIZED_HEADER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How many reported headers a subscription remembers to find the common ancestor of a reorg.
+const REPORTED_HEADERS_HISTORY_SIZE: usize = 1000;
+
 /// Definition of a fork that will be executed in `MockDaService` at specified height
 pub struct PlannedFork {
     trigger_at_height: u64,
@@ -71,8 +79,6 @@ pub struct MockDaService {
     pub(crate) blocks: Arc<AsyncMutex<DbConnector>>,
     /// How many blocks should be submitted, before block is finalized. 0 means instant finality.
     blocks_to_finality: u32,
-    /// Used for calculating correct finality from state of `blocks`
-    finalized_header_sender: broadcast::Sender<MockBlockHeader>,
     wait_attempts: usize,
     planned_fork: Arc<Mutex<Option<PlannedFork>>>,
 }
@@ -90,19 +96,10 @@ impl MockDaService {
         blocks_to_finality: u32,
         db_path: &Path,
     ) -> Self {
-        let (tx, rx1) = broadcast::channel(16);
-        // Spawn a task, so channel is never closed
-        tokio::spawn(async move {
-            let mut rx = rx1;
-            while let Ok(header) = rx.recv().instrument(tracing::Span::current()).await {
-                tracing::debug!("Finalized MockHeader: {}", header);
-            }
-        });
         Self {
             sequencer_da_address,
             blocks: Arc::new(AsyncMutex::new(DbConnector::new(db_path))),
             blocks_to_finality,
-            finalized_header_sender: tx,
             wait_attempts: 100_0000,
             planned_fork: Arc::new(Mutex::new(None)),
         }
@@ -146,8 +143,18 @@ impl MockDaService {
                 last_finalized_height
             );
         }
+        self.reorg_at(height, blobs).await
+    }
+
+    /// Rewrites existing blocks above given height with given blocks, finalized blocks included.
+    /// Simulates a reorg deeper than the finality of the DA layer,
+    /// which is reported to subscribers of finalized headers.
+    /// New blobs will be added **after** specified height,
+    /// meaning that first blob will be in the block of height + 1.
+    /// Rewritten blocks can still be fetched by hash.
+    pub async fn reorg_at(&self, height: u64, blobs: Vec<Vec<u8>>) -> anyhow::Result<()> {
         let blocks = self.blocks.lock().await;
-        blocks.prune_above(height);
+        blocks.orphan_above(height);
 
         for blob in blobs {
             let _ = self.add_blob(&blocks, &blob, Default::default())?;
@@ -232,20 +239,80 @@ impl MockDaService {
 
         blocks.push_back(block.clone());
 
-        // Enough blocks to finalize block
-        if blocks.len() > self.blocks_to_finality as usize {
-            let next_index_to_finalize = blocks.len() - self.blocks_to_finality as usize - 1;
-            let next_finalized_header = blocks
-                .get(next_index_to_finalize as u64)
-                .unwrap()
-                .header()
-                .clone();
-            self.finalized_header_sender
-                .send(next_finalized_header)
-                .unwrap();
+        Ok(height)
+    }
+
+    /// Returns the header of the block at given height on the current fork.
+    async fn get_header_at(&self, height: u64) -> Option<MockBlockHeader> {
+        if height == 0 {
+            return Some(GENESIS_HEADER);
         }
+        self.blocks
+            .lock()
+            .await
+            .get(height - 1)
+            .map(|block| block.header)
+    }
 
-        Ok(height)
+    /// Sends finalized headers and reorgs of finalized blocks until the receiver is dropped.
+    /// Blocks are polled from the database,
+    /// so blocks and forks submitted by other services on the same database are reported too.
+    async fn report_finalized_headers(
+        self,
+        last_finalized_header: MockBlockHeader,
+        sender: UnboundedSender<FinalizedHeaderEvent<MockBlockHeader>>,
+    ) {
+        // The last element is the last reported header, or the last finalized one when subscribed
+        let mut reported = vec![last_finalized_header];
+
+        while !sender.is_closed() {
+            let mut reorged = Vec::new();
+            while let Some(header) = reported.last() {
+                if self.get_header_at(header.height).await.as_ref() == Some(header) {
+                    break;
+                }
+                reorged.extend(reported.pop());
+            }
+            if !reorged.is_empty() {
+                reorged.reverse();
+                let common_ancestor = match reported.last() {
+                    Some(header) => header.clone(),
+                    None => {
+                        let header = self
+                            .get_header_at(reorged[0].height - 1)
+                            .await
+                            .unwrap_or(GENESIS_HEADER);
+                        reported.push(header.clone());
+                        header
+                    }
+                };
+                tracing::debug!(
+                    "Reorged MockHeaders above {}: {}",
+                    common_ancestor,
+                    reorged.len()
+                );
+                let _ = sender.send(FinalizedHeaderEvent::Reorg {
+                    common_ancestor,
+                    reorged,
+                });
+            }
+
+            let last_finalized_height = self.get_last_finalized_height().await;
+            let last_reported_height = reported.last().map_or(0, |header| header.height);
+            for height in last_reported_height + 1..=last_finalized_height {
+                let Some(header) = self.get_header_at(height).await else {
+                    break;
+                };
+                tracing::debug!("Finalized MockHeader: {}", header);
+                let _ = sender.send(FinalizedHeaderEvent::Finalized(header.clone()));
+                reported.push(header);
+            }
+            if reported.len() > REPORTED_HEADERS_HISTORY_SIZE {
+                reported.drain(..reported.len() - REPORTED_HEADERS_HISTORY_SIZE);
+            }
+
+            time::sleep(FINALIZED_HEADER_POLL_INTERVAL).await;
+        }
     }
 
     /// Executes planned fork if it is planned at given height
@@ -273,26 +340,25 @@ impl MockDaService {
 /// Stream of finalized headers
 pub struct MockDaBlockHeaderStream {
     #[pin]
-    inner: tokio_stream::wrappers::BroadcastStream<MockBlockHeader>,
+    inner: tokio_stream::wrappers::UnboundedReceiverStream<FinalizedHeaderEvent<MockBlockHeader>>,
 }
 
 impl MockDaBlockHeaderStream {
     /// Create new stream of finalized headers
-    pub fn new(receiver: broadcast::Receiver<MockBlockHeader>) -> Self {
+    pub fn new(receiver: UnboundedReceiver<FinalizedHeaderEvent<MockBlockHeader>>) -> Self {
         Self {
-            inner: tokio_stream::wrappers::BroadcastStream::new(receiver),
+            inner: tokio_stream::wrappers::UnboundedReceiverStream::new(receiver),
         }
     }
 }
 
 impl futures::Stream for MockDaBlockHeaderStream {
-    type Item = Result<MockBlockHeader, anyhow::Error>;
+    type Item = Result<FinalizedHeaderEvent<MockBlockHeader>, anyhow::Error>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.project(); // Requires the pin-project crate or similar functionality
-        this.inner
-            .poll_next(cx)
-            .map(|opt| opt.map(|res| res.map_err(Into::into)))
+
+        this.inner.poll_next(cx).map(|opt| opt.map(Ok))
     }
 }
 
@@ -360,7 +426,13 @@ impl DaService for MockDaService {
     }
 
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
-        let receiver = self.finalized_header_sender.subscribe();
+        let last_finalized_header = self.get_last_finalized_block_header().await?;
+        let (sender, receiver) = unbounded_channel();
+        tokio::spawn(
+            self.clone()
+                .report_finalized_headers(last_finalized_header, sender)
+                .instrument(tracing::Span::current()),
+        );
         Ok(MockDaBlockHeaderStream::new(receiver))
     }
 
@@ -506,7 +578,7 @@ mod tests {
             let mut received = Vec::with_capacity(expected_num_headers);
             for _ in 0..expected_num_headers {
                 match time::timeout(timeout_duration, receiver.next()).await {
-                    Ok(Some(Ok(header))) => received.push(header),
+                    Ok(Some(Ok(FinalizedHeaderEvent::Finalized(header)))) => received.push(header),
                     _ => break,
                 }
             }
@@ -798,6 +870,75 @@ mod tests {
             assert_ne!(block_3_after, block_3_after_reorg);
         }
 
+        #[tokio::test]
+        async fn test_reorg_of_finalized_blocks() {
+            let db_path = tempfile::tempdir().unwrap();
+            let da = MockDaService::new(MockAddress::new([1; 32]), db_path.path());
+            let mut receiver = da.subscribe_finalized_header().await.unwrap();
+            let timeout_duration = Duration::from_millis(1000);
+
+            // 1 -> 2 -> 3.1
+            //      \ -> 3.2 -> 4.2
+
+            da.send_transaction(&[1, 2, 3, 4]).await.unwrap();
+            da.send_transaction(&[4, 5, 6, 7]).await.unwrap();
+            da.send_transaction(&[8, 9, 0, 1]).await.unwrap();
+
+            let block_2 = da.get_block_at(2).await.unwrap();
+            let block_3_before = da.get_block_at(3).await.unwrap();
+
+            for height in 1..=3 {
+                let event = time::timeout(timeout_duration, receiver.next())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .unwrap();
+                let FinalizedHeaderEvent::Finalized(header) = event else {
+                    panic!("Expected finalized header, got {:?}", event);
+                };
+                assert_eq!(height, header.height());
+            }
+
+            da.reorg_at(2, vec![vec![3, 3, 3, 3], vec![4, 4, 4, 4]])
+                .await
+                .unwrap();
+
+            let event = time::timeout(timeout_duration, receiver.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                FinalizedHeaderEvent::Reorg {
+                    common_ancestor: block_2.header().clone(),
+                    reorged: vec![block_3_before.header().clone()],
+                },
+                event
+            );
+            for height in 3..=4 {
+                let event = time::timeout(timeout_duration, receiver.next())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .unwrap();
+                let FinalizedHeaderEvent::Finalized(header) = event else {
+                    panic!("Expected finalized header, got {:?}", event);
+                };
+                assert_eq!(height, header.height());
+            }
+
+            let block_3_after = da.get_block_at(3).await.unwrap();
+            assert_ne!(block_3_before, block_3_after);
+            assert_eq!(block_2.header().hash(), block_3_after.header().prev_hash());
+
+            // Reorged blocks can still be fetched by hash
+            let orphaned_block_3 = da
+                .get_block_by_hash(block_3_before.header().hash().into())
+                .await
+                .unwrap();
+            assert_eq!(block_3_before, orphaned_block_3);
+        }
+
         #[tokio::test]
         async fn test_planned_reorg() {
             let db_path = tempfile::tempdir().unwrap();
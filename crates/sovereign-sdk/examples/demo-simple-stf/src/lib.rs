@@ -3,7 +3,7 @@
 use std::marker::PhantomData;
 
 use sha2::Digest;
-use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+use sov_rollup_interface::da::{BlobReaderTrait, DaSpec, ForcedTransaction};
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{CumulativeStateDiff, ValidityCondition, Zkvm};
@@ -116,6 +116,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _witness: Self::Witness,
         _slot_header: &<Da as DaSpec>::BlockHeader,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        _forced_transactions: &[ForcedTransaction],
         _soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> SlotResult<
         Self::StateRoot,
@@ -136,6 +137,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _da_data: Vec<<Da as DaSpec>::BlobTransaction>,
         _witnesses: std::collections::VecDeque<Vec<Self::Witness>>,
         _slot_headers: std::collections::VecDeque<Vec<<Da as DaSpec>::BlockHeader>>,
+        _forced_transactions: std::collections::VecDeque<Vec<Vec<ForcedTransaction>>>,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
         _soft_batch: std::collections::VecDeque<Vec<SignedSoftConfirmationBatch>>,
     ) -> (Self::StateRoot, CumulativeStateDiff) {
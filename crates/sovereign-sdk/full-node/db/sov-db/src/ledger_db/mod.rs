@@ -11,7 +11,8 @@ use tracing::instrument;
 
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, CommitmentsByNumber, EventByKey, EventByNumber, L2RangeByL1Height,
+    BatchByHash, BatchByNumber, CommitmentsByNumber, DaReconstructionProgress, EventByKey,
+    EventByNumber, L2RangeByL1Height, LastCommittedL2Height, LastProvenL2Height,
     LastSequencerCommitmentSent, ProofBySlotNumber, ProverLastScannedSlot, SlotByHash,
     SlotByNumber, SoftBatchByHash, SoftBatchByNumber, SoftConfirmationStatus, TxByHash, TxByNumber,
     VerifiedProofsBySlotNumber, LEDGER_TABLES,
@@ -36,6 +37,7 @@ pub struct LedgerDB {
     db: Arc<DB>,
     next_item_numbers: Arc<Mutex<ItemNumbers>>,
     slot_subscriptions: tokio::sync::broadcast::Sender<u64>,
+    soft_batch_subscriptions: tokio::sync::broadcast::Sender<u64>,
 }
 
 /// A SlotNumber, BatchNumber, TxNumber, and EventNumber which are grouped together, typically representing
@@ -122,9 +124,15 @@ impl LedgerDB {
             db: Arc::new(inner),
             next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
             slot_subscriptions: tokio::sync::broadcast::channel(10).0,
+            soft_batch_subscriptions: tokio::sync::broadcast::channel(10).0,
         })
     }
 
+    /// Returns a receiver that is notified with the number of every committed soft batch
+    pub fn subscribe_soft_batches(&self) -> tokio::sync::broadcast::Receiver<u64> {
+        self.soft_batch_subscriptions.subscribe()
+    }
+
     /// Get the next slot, block, transaction, and event numbers
     #[instrument(level = "trace", skip(self), ret)]
     pub fn get_next_items_numbers(&self) -> ItemNumbers {
@@ -337,10 +345,14 @@ impl LedgerDB {
             &BatchNumber(current_item_numbers.soft_batch_number),
             &mut schema_batch,
         )?;
-        current_item_numbers.soft_batch_number += 1;
 
         self.db.write_schemas(schema_batch)?;
 
+        // Notify subscribers. This call returns an error IFF there are no subscribers, so we don't need to check the result
+        let _ = self
+            .soft_batch_subscriptions
+            .send(current_item_numbers.soft_batch_number);
+
         Ok(())
     }
 
@@ -480,6 +492,25 @@ impl LedgerDB {
         schema_batch
             .put::<SoftConfirmationStatus>(&height, &status)
             .unwrap();
+
+        // The status is set per L1 block, the last L2 block on it is committed or proven.
+        // Proven soft confirmations have been committed before
+        if let Some((_, l2_height)) = self.db.get::<L2RangeByL1Height>(&height)? {
+            if status != sov_rollup_interface::rpc::SoftConfirmationStatus::Trusted
+                && self.get_last_committed_l2_height()? < Some(l2_height)
+            {
+                schema_batch
+                    .put::<LastCommittedL2Height>(&(), &l2_height)
+                    .unwrap();
+            }
+            if status == sov_rollup_interface::rpc::SoftConfirmationStatus::Proven
+                && self.get_last_proven_l2_height()? < Some(l2_height)
+            {
+                schema_batch
+                    .put::<LastProvenL2Height>(&(), &l2_height)
+                    .unwrap();
+            }
+        }
         self.db.write_schemas(schema_batch)?;
 
         Ok(())
@@ -534,6 +565,18 @@ impl LedgerDB {
         self.db.get::<LastSequencerCommitmentSent>(&())
     }
 
+    /// Get the highest L2 height covered by a sequencer commitment
+    #[instrument(level = "trace", skip(self), err, ret)]
+    pub fn get_last_committed_l2_height(&self) -> anyhow::Result<Option<BatchNumber>> {
+        self.db.get::<LastCommittedL2Height>(&())
+    }
+
+    /// Get the highest L2 height covered by a verified proof
+    #[instrument(level = "trace", skip(self), err, ret)]
+    pub fn get_last_proven_l2_height(&self) -> anyhow::Result<Option<BatchNumber>> {
+        self.db.get::<LastProvenL2Height>(&())
+    }
+
     /// Get L2 height range for a given L1 height.
     /// This means L2 heights in that range were soft confirmations for L1 height.
     #[instrument(level = "trace", skip(self), err, ret)]
@@ -564,6 +607,31 @@ impl LedgerDB {
         Ok(())
     }
 
+    /// Get the last scanned slot and the number of applied state diffs
+    /// of a full node reconstructing state from DA
+    #[instrument(level = "trace", skip(self), err, ret)]
+    pub fn get_da_reconstruction_progress(&self) -> anyhow::Result<Option<(SlotNumber, u64)>> {
+        self.db.get::<DaReconstructionProgress>(&())
+    }
+
+    /// Set the last scanned slot and the number of applied state diffs
+    /// Called by a full node reconstructing state from DA.
+    #[instrument(level = "trace", skip(self), err, ret)]
+    pub fn set_da_reconstruction_progress(
+        &self,
+        l1_height: SlotNumber,
+        applied_state_diffs: u64,
+    ) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+
+        schema_batch
+            .put::<DaReconstructionProgress>(&(), &(l1_height, applied_state_diffs))
+            .unwrap();
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
     /// Gets the commitments in the da slot with given height if any
     /// Adds the new coming commitment info
     #[instrument(level = "trace", skip(self, commitment), err, ret)]
@@ -624,28 +692,51 @@ impl LedgerDB {
         proof: Proof,
         state_transition: StoredStateTransition,
     ) -> anyhow::Result<()> {
-        let verified_proofs = self
+        let mut schema_batch = SchemaBatch::new();
+        self.put_verified_proofs(
+            &mut schema_batch,
+            l1_height,
+            vec![(proof, state_transition)],
+        )?;
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Stores the proofs of the state diffs a full node reconstructing state from DA applied from
+    /// an L1 block, together with the number of applied state diffs and the L1 block as the last
+    /// scanned one, so that all of them are saved or none is.
+    #[instrument(level = "trace", skip(self, verified_proofs), err, ret)]
+    pub fn update_verified_proofs_and_da_reconstruction_progress(
+        &self,
+        l1_height: u64,
+        verified_proofs: Vec<(Proof, StoredStateTransition)>,
+        applied_state_diffs: u64,
+    ) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+        if !verified_proofs.is_empty() {
+            self.put_verified_proofs(&mut schema_batch, l1_height, verified_proofs)?;
+        }
+        schema_batch
+            .put::<DaReconstructionProgress>(&(), &(SlotNumber(l1_height), applied_state_diffs))?;
+        self.db.write_schemas(schema_batch)
+    }
+
+    fn put_verified_proofs(
+        &self,
+        schema_batch: &mut SchemaBatch,
+        l1_height: u64,
+        proofs: Vec<(Proof, StoredStateTransition)>,
+    ) -> anyhow::Result<()> {
+        let mut verified_proofs = self
             .db
-            .get::<VerifiedProofsBySlotNumber>(&SlotNumber(l1_height))?;
-
-        match verified_proofs {
-            Some(mut verified_proofs) => {
-                let stored_verified_proof = StoredVerifiedProof {
-                    proof,
-                    state_transition,
-                };
-                verified_proofs.push(stored_verified_proof);
-                self.db
-                    .put::<VerifiedProofsBySlotNumber>(&SlotNumber(l1_height), &verified_proofs)
+            .get::<VerifiedProofsBySlotNumber>(&SlotNumber(l1_height))?
+            .unwrap_or_default();
+        verified_proofs.extend(proofs.into_iter().map(|(proof, state_transition)| {
+            StoredVerifiedProof {
+                proof,
+                state_transition,
             }
-            None => self.db.put(
-                &SlotNumber(l1_height),
-                &vec![StoredVerifiedProof {
-                    proof,
-                    state_transition,
-                }],
-            ),
-        }
+        }));
+        schema_batch.put::<VerifiedProofsBySlotNumber>(&SlotNumber(l1_height), &verified_proofs)
     }
 
     /// Sets l1 height of l1 hash
@@ -660,3 +751,142 @@ impl LedgerDB {
         self.db.get::<SlotByHash>(&hash).map(|v| v.map(|a| a.0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use sov_mock_da::{MockDaSpec, MockHash};
+    use sov_rollup_interface::rpc::SoftConfirmationStatus;
+    use sov_rollup_interface::stf::{Event, SoftBatchReceipt, TransactionReceipt};
+    use sov_rollup_interface::zk::Proof;
+
+    use super::LedgerDB;
+    use crate::schema::tables::VerifiedProofsBySlotNumber;
+    use crate::schema::types::{BatchNumber, SlotNumber, StoredStateTransition};
+
+    fn soft_batch_receipt(number: u8, da_slot_height: u64) -> SoftBatchReceipt<(), (), MockDaSpec> {
+        SoftBatchReceipt {
+            da_slot_height,
+            da_slot_hash: MockHash([da_slot_height as u8; 32]),
+            da_slot_txs_commitment: MockHash([0; 32]),
+            batch_hash: [number; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [number; 32],
+                body_to_save: Some(vec![number]),
+                events: vec![Event::new("key", &number.to_string())],
+                receipt: (),
+            }],
+            phantom_data: PhantomData,
+            pre_state_root: vec![number - 1],
+            post_state_root: vec![number],
+            soft_confirmation_signature: vec![],
+            pub_key: vec![],
+            deposit_data: vec![],
+            l1_fee_rate: 0,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_soft_batch_subscription() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        let mut rx = db.subscribe_soft_batches();
+        db.commit_soft_batch(soft_batch_receipt(1, 1), true)
+            .unwrap();
+        db.commit_soft_batch(soft_batch_receipt(2, 1), true)
+            .unwrap();
+
+        assert_eq!(rx.blocking_recv().unwrap(), 1);
+        assert_eq!(rx.blocking_recv().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_da_reconstruction_progress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        assert_eq!(db.get_da_reconstruction_progress().unwrap(), None);
+
+        db.set_da_reconstruction_progress(SlotNumber(5), 2).unwrap();
+        assert_eq!(
+            db.get_da_reconstruction_progress().unwrap(),
+            Some((SlotNumber(5), 2))
+        );
+
+        let state_transition = StoredStateTransition {
+            initial_state_root: vec![1; 32],
+            final_state_root: vec![2; 32],
+            state_diff: Default::default(),
+            da_slot_hash: [7; 32],
+            sequencer_public_key: vec![],
+            sequencer_da_public_key: vec![],
+            validity_condition: vec![],
+        };
+        db.update_verified_proofs_and_da_reconstruction_progress(
+            7,
+            vec![
+                (Proof::PublicInput(vec![]), state_transition.clone()),
+                (Proof::PublicInput(vec![1]), state_transition),
+            ],
+            4,
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_da_reconstruction_progress().unwrap(),
+            Some((SlotNumber(7), 4))
+        );
+        let verified_proofs = db
+            .db
+            .get::<VerifiedProofsBySlotNumber>(&SlotNumber(7))
+            .unwrap()
+            .unwrap();
+        assert_eq!(verified_proofs.len(), 2);
+
+        // An L1 block without proofs only moves the progress
+        db.update_verified_proofs_and_da_reconstruction_progress(8, vec![], 4)
+            .unwrap();
+        assert_eq!(
+            db.get_da_reconstruction_progress().unwrap(),
+            Some((SlotNumber(8), 4))
+        );
+    }
+
+    #[test]
+    fn test_last_committed_and_proven_l2_heights() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        assert_eq!(db.get_last_committed_l2_height().unwrap(), None);
+        assert_eq!(db.get_last_proven_l2_height().unwrap(), None);
+
+        // L2 blocks 1-2 are on L1 block 1, 3-5 on L1 block 2, 6 on L1 block 3 and 7-8 on L1 block 4
+        for (l1_height, l2_heights) in [(1, 1..=2), (2, 3..=5), (3, 6..=6), (4, 7..=8)] {
+            for l2_height in l2_heights {
+                db.extend_l2_range_of_l1_slot(SlotNumber(l1_height), BatchNumber(l2_height))
+                    .unwrap();
+            }
+        }
+
+        // No L2 block is on L1 block 5
+        for height in 1..=5 {
+            db.put_soft_confirmation_status(SlotNumber(height), SoftConfirmationStatus::Finalized)
+                .unwrap();
+        }
+        db.put_soft_confirmation_status(SlotNumber(2), SoftConfirmationStatus::Proven)
+            .unwrap();
+        db.put_soft_confirmation_status(SlotNumber(1), SoftConfirmationStatus::Proven)
+            .unwrap();
+
+        assert_eq!(
+            db.get_last_committed_l2_height().unwrap(),
+            Some(BatchNumber(8))
+        );
+        assert_eq!(
+            db.get_last_proven_l2_height().unwrap(),
+            Some(BatchNumber(5))
+        );
+    }
+}
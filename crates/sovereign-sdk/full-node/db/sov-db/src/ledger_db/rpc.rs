@@ -10,9 +10,9 @@ use sov_rollup_interface::stf::Event;
 use tokio::sync::broadcast::Receiver;
 
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, CommitmentsByNumber, EventByNumber, ProofBySlotNumber, SlotByHash,
-    SlotByNumber, SoftBatchByHash, SoftBatchByNumber, SoftConfirmationStatus, TxByHash, TxByNumber,
-    VerifiedProofsBySlotNumber,
+    BatchByHash, BatchByNumber, CommitmentsByNumber, EventByNumber, LastCommittedL2Height,
+    LastProvenL2Height, ProofBySlotNumber, SlotByHash, SlotByNumber, SoftBatchByHash,
+    SoftBatchByNumber, SoftConfirmationStatus, TxByHash, TxByNumber, VerifiedProofsBySlotNumber,
 };
 use crate::schema::types::{
     BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
@@ -390,6 +390,10 @@ impl LedgerRpcProvider for LedgerDB {
         Ok(self.slot_subscriptions.subscribe())
     }
 
+    fn subscribe_soft_batches(&self) -> Result<Receiver<u64>, anyhow::Error> {
+        Ok(self.soft_batch_subscriptions.subscribe())
+    }
+
     fn get_prover_last_scanned_l1_height(&self) -> Result<u64, anyhow::Error> {
         match self.get_prover_last_scanned_l1_height()? {
             Some(height) => Ok(height.0),
@@ -441,6 +445,14 @@ impl LedgerRpcProvider for LedgerDB {
         let next_ids = self.get_next_items_numbers();
         Ok(next_ids.soft_batch_number.saturating_sub(1))
     }
+
+    fn get_last_committed_l2_height(&self) -> Result<Option<u64>, anyhow::Error> {
+        Ok(self.db.get::<LastCommittedL2Height>(&())?.map(Into::into))
+    }
+
+    fn get_last_proven_l2_height(&self) -> Result<Option<u64>, anyhow::Error> {
+        Ok(self.db.get::<LastProvenL2Height>(&())?.map(Into::into))
+    }
 }
 
 impl LedgerDB {
@@ -5,7 +5,7 @@ use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{ModuleAccessoryState, NATIVE_TABLES};
+use crate::schema::tables::{ModuleAccessoryState, StaleAccessoryValues, NATIVE_TABLES};
 use crate::schema::types::AccessoryKey;
 
 /// Specifies a particular version of the Accessory state.
@@ -17,12 +17,15 @@ pub type Version = u64;
 pub struct NativeDB<Q> {
     /// Pointer to [`DbSnapshot`] for up to date state
     db: Arc<DbSnapshot<Q>>,
+    /// Whether overwritten values are indexed, so they can be pruned later
+    record_stale: bool,
 }
 
 impl<Q> Clone for NativeDB<Q> {
     fn clone(&self) -> Self {
         NativeDB {
             db: self.db.clone(),
+            record_stale: self.record_stale,
         }
     }
 }
@@ -49,6 +52,32 @@ impl<Q> NativeDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Index values that are overwritten with each write,
+    /// so that [`NativeDB::prune`] can remove them later.
+    pub fn record_stale_versions(mut self) -> Self {
+        self.record_stale = true;
+        self
+    }
+
+    /// Removes every accessory value that is not needed to read at `earliest_version` or later.
+    /// Must only be given versions that non-finalized snapshots no longer read.
+    pub fn prune(db: &sov_schema_db::DB, earliest_version: Version) -> anyhow::Result<()> {
+        let mut batch = SchemaBatch::new();
+
+        let mut stale_values = db.iter::<StaleAccessoryValues>()?;
+        stale_values.seek_to_first();
+        for item in stale_values {
+            let ((stale_since_version, key, version), _) = item?.into_tuple();
+            if stale_since_version > earliest_version {
+                break;
+            }
+            batch.delete::<ModuleAccessoryState>(&(key.clone(), version))?;
+            batch.delete::<StaleAccessoryValues>(&(stale_since_version, key, version))?;
+        }
+
+        db.write_schemas(batch)
+    }
 }
 
 impl<Q: QueryManager> NativeDB<Q> {
@@ -57,6 +86,7 @@ impl<Q: QueryManager> NativeDB<Q> {
         // We keep Result type, just for future archival state integration
         Ok(Self {
             db: Arc::new(db_snapshot),
+            record_stale: false,
         })
     }
 
@@ -90,6 +120,19 @@ impl<Q: QueryManager> NativeDB<Q> {
     ) -> anyhow::Result<()> {
         let mut batch = SchemaBatch::default();
         for (key, value) in key_value_pairs {
+            if self.record_stale {
+                if let Some(((found_key, found_version), _)) = self
+                    .db
+                    .get_prev::<ModuleAccessoryState>(&(key.clone(), version))?
+                {
+                    if found_key == key && found_version < version {
+                        batch.put::<StaleAccessoryValues>(
+                            &(version, key.clone(), found_version),
+                            &(),
+                        )?;
+                    }
+                }
+            }
             batch.put::<ModuleAccessoryState>(&(key, version), &value)?;
         }
         self.db.write_many(batch)?;
@@ -142,4 +185,36 @@ mod tests {
         let key = b"spam".to_vec();
         assert_eq!(db.get_value_option(&key, 0).unwrap(), None);
     }
+
+    #[test]
+    fn prune_overwritten_values() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let schema_db = NativeDB::<NoopQueryManager>::setup_schema_db(tmpdir.path()).unwrap();
+        let db = setup_db().record_stale_versions();
+
+        let key = b"foo".to_vec();
+        db.set_values(vec![(key.clone(), Some(b"bar".to_vec()))], 0)
+            .unwrap();
+        db.set_values(vec![(key.clone(), Some(b"bar2".to_vec()))], 1)
+            .unwrap();
+        schema_db
+            .write_schemas(db.freeze().unwrap().into())
+            .unwrap();
+
+        NativeDB::<NoopQueryManager>::prune(&schema_db, 0).unwrap();
+        assert!(schema_db
+            .get::<ModuleAccessoryState>(&(key.clone(), 0))
+            .unwrap()
+            .is_some());
+
+        NativeDB::<NoopQueryManager>::prune(&schema_db, 1).unwrap();
+        assert!(schema_db
+            .get::<ModuleAccessoryState>(&(key.clone(), 0))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            schema_db.get::<ModuleAccessoryState>(&(key, 1)).unwrap(),
+            Some(Some(b"bar2".to_vec()))
+        );
+    }
 }
@@ -22,12 +22,18 @@
 //! - `(Key, Version) -> JmtValue`
 //! - `NodeKey -> Node`
 //!
-//! Module Accessory State Table:
+//! JMT Pruning Tables:
+//! - `(StaleSinceVersion, NodeKey) -> ()`
+//! - `(StaleSinceVersion, Key, Version) -> ()`
+//! - `() -> Version`
+//!
+//! Module Accessory State Tables:
 //! - `(ModuleAddress, Key) -> Value`
+//! - `(StaleSinceVersion, Key, Version) -> ()`
 
 use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
-use jmt::storage::{NibblePath, Node, NodeKey};
+use jmt::storage::{NibblePath, Node, NodeKey, StaleNodeIndex};
 use jmt::Version;
 use sov_rollup_interface::da::SequencerCommitment;
 use sov_rollup_interface::stf::{Event, EventKey};
@@ -46,6 +52,9 @@ pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
     JmtNodes::table_name(),
+    StaleJmtNodes::table_name(),
+    StaleJmtValues::table_name(),
+    EarliestStateVersion::table_name(),
 ];
 
 /// A list of all tables used by the LedgerDB. These tables store rollup "history" - meaning
@@ -58,9 +67,12 @@ pub const LEDGER_TABLES: &[&str] = &[
     L2RangeByL1Height::table_name(),
     LastSequencerCommitmentSent::table_name(),
     ProverLastScannedSlot::table_name(),
+    DaReconstructionProgress::table_name(),
     BatchByHash::table_name(),
     BatchByNumber::table_name(),
     SoftConfirmationStatus::table_name(),
+    LastCommittedL2Height::table_name(),
+    LastProvenL2Height::table_name(),
     TxByHash::table_name(),
     TxByNumber::table_name(),
     EventByKey::table_name(),
@@ -73,7 +85,10 @@ pub const LEDGER_TABLES: &[&str] = &[
 /// A list of all tables used by the NativeDB. These tables store
 /// "accessory" state only accessible from a native execution context, to be
 /// used for JSON-RPC and other tooling.
-pub const NATIVE_TABLES: &[&str] = &[ModuleAccessoryState::table_name()];
+pub const NATIVE_TABLES: &[&str] = &[
+    ModuleAccessoryState::table_name(),
+    StaleAccessoryValues::table_name(),
+];
 
 /// Macro to define a table that implements [`sov_schema_db::Schema`].
 /// KeyCodec<Schema> and ValueCodec<Schema> must be implemented separately.
@@ -259,6 +274,12 @@ define_table_with_seek_key_codec!(
     (ProverLastScannedSlot) () => SlotNumber
 );
 
+define_table_with_seek_key_codec!(
+    /// A full node reconstructing state from DA stores the last slot it scanned
+    /// and the number of proven state diffs it has applied so far
+    (DaReconstructionProgress) () => (SlotNumber, u64)
+);
+
 define_table_with_seek_key_codec!(
     /// The primary source for batch data
     (BatchByNumber) BatchNumber => StoredBatch
@@ -269,6 +290,16 @@ define_table_with_default_codec!(
     (SoftConfirmationStatus) SlotNumber => sov_rollup_interface::rpc::SoftConfirmationStatus
 );
 
+define_table_with_seek_key_codec!(
+    /// The highest L2 height covered by a sequencer commitment on the DA layer
+    (LastCommittedL2Height) () => BatchNumber
+);
+
+define_table_with_seek_key_codec!(
+    /// The highest L2 height covered by a verified ZK proof
+    (LastProvenL2Height) () => BatchNumber
+);
+
 define_table_with_default_codec!(
     /// A "secondary index" for batch data by hash
     (BatchByHash) DbHash => BatchNumber
@@ -346,6 +377,53 @@ impl ValueCodec<JmtNodes> for Node {
     }
 }
 
+define_table_without_codec!(
+    /// Index of JMT nodes that are no longer part of the tree since `stale_since_version`.
+    /// Only populated when the storage runs in pruned mode.
+    (StaleJmtNodes) StaleNodeIndex => ()
+);
+
+impl KeyEncoder<StaleJmtNodes> for StaleNodeIndex {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        // Stale since version goes first, so that the pruner can iterate in version order
+        let mut output = self.stale_since_version.to_be_bytes().to_vec();
+        output.extend(<NodeKey as KeyEncoder<JmtNodes>>::encode_key(
+            &self.node_key,
+        )?);
+        Ok(output)
+    }
+}
+
+impl KeyDecoder<StaleJmtNodes> for StaleNodeIndex {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        if data.len() < 8 {
+            return Err(CodecError::InvalidKeyLength {
+                expected: 16,
+                got: data.len(),
+            });
+        }
+        let mut stale_since_version = [0u8; 8];
+        stale_since_version.copy_from_slice(&data[..8]);
+        Ok(Self {
+            stale_since_version: u64::from_be_bytes(stale_since_version),
+            node_key: <NodeKey as KeyDecoder<JmtNodes>>::decode_key(&data[8..])?,
+        })
+    }
+}
+
+impl_borsh_value_codec!(StaleJmtNodes, ());
+
+define_table_with_seek_key_codec!(
+    /// Index of JMT values that were overwritten at `stale_since_version`.
+    /// Only populated when the storage runs in pruned mode.
+    (StaleJmtValues) (Version, StateKey, Version) => ()
+);
+
+define_table_with_seek_key_codec!(
+    /// The oldest JMT version whose state is still fully available after pruning
+    (EarliestStateVersion) () => Version
+);
+
 define_table_without_codec!(
     /// The source of truth for JMT values by version
     (JmtValues) (StateKey, Version) => JmtValue
@@ -404,6 +482,12 @@ define_table_without_codec!(
     (ModuleAccessoryState) (AccessoryKey, Version) => AccessoryStateValue
 );
 
+define_table_with_seek_key_codec!(
+    /// Index of accessory values that were overwritten at `stale_since_version`.
+    /// Only populated when the storage runs in pruned mode.
+    (StaleAccessoryValues) (Version, AccessoryKey, Version) => ()
+);
+
 impl KeyEncoder<ModuleAccessoryState> for (AccessoryKey, Version) {
     fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
         let mut out = Vec::with_capacity(self.0.len() + std::mem::size_of::<Version>() + 8);
@@ -1,13 +1,16 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use jmt::storage::{HasPreimage, TreeReader, TreeWriter};
+use jmt::storage::{HasPreimage, StaleNodeIndexBatch, TreeReader, TreeWriter};
 use jmt::{KeyHash, Version};
 use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
+use crate::schema::tables::{
+    EarliestStateVersion, JmtNodes, JmtValues, KeyHashToKey, StaleJmtNodes, StaleJmtValues,
+    STATE_TABLES,
+};
 use crate::schema::types::StateKey;
 
 /// A typed wrapper around the db for storing rollup state. Internally,
@@ -23,6 +26,8 @@ pub struct StateDB<Q> {
     /// This [`Version`] is also used for querying data,
     /// so if this instance of StateDB is used as read only, it won't see newer data.
     next_version: Arc<Mutex<Version>>,
+    /// Whether overwritten nodes and values are indexed, so they can be pruned later
+    record_stale: bool,
 }
 
 // Manual implementation of [`Clone`] to satisfy compiler
@@ -31,6 +36,7 @@ impl<Q> Clone for StateDB<Q> {
         StateDB {
             db: self.db.clone(),
             next_version: self.next_version.clone(),
+            record_stale: self.record_stale,
         }
     }
 }
@@ -57,6 +63,56 @@ impl<Q> StateDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Index JMT nodes and values that become stale with each write,
+    /// so that [`StateDB::prune`] can remove them later.
+    pub fn record_stale_versions(mut self) -> Self {
+        self.record_stale = true;
+        self
+    }
+
+    /// Removes every JMT node and value that is not needed to read state at
+    /// `earliest_version` or later. Only entries indexed by a [`StateDB`] that
+    /// was created with [`StateDB::record_stale_versions`] are removed.
+    ///
+    /// Operates on the finalized [`sov_schema_db::DB`], so it must not be given a version
+    /// that non-finalized snapshots can still read.
+    pub fn prune(db: &sov_schema_db::DB, earliest_version: Version) -> anyhow::Result<()> {
+        let pruned_until = db.get::<EarliestStateVersion>(&())?.unwrap_or_default();
+        if earliest_version <= pruned_until {
+            return Ok(());
+        }
+
+        // The marker is written first, so readers stop trusting pruned versions
+        // before their data is gone.
+        db.put::<EarliestStateVersion>(&(), &earliest_version)?;
+
+        let mut batch = SchemaBatch::new();
+
+        let mut stale_nodes = db.iter::<StaleJmtNodes>()?;
+        stale_nodes.seek_to_first();
+        for item in stale_nodes {
+            let (index, _) = item?.into_tuple();
+            if index.stale_since_version > earliest_version {
+                break;
+            }
+            batch.delete::<JmtNodes>(&index.node_key)?;
+            batch.delete::<StaleJmtNodes>(&index)?;
+        }
+
+        let mut stale_values = db.iter::<StaleJmtValues>()?;
+        stale_values.seek_to_first();
+        for item in stale_values {
+            let ((stale_since_version, key, version), _) = item?.into_tuple();
+            if stale_since_version > earliest_version {
+                break;
+            }
+            batch.delete::<JmtValues>(&(key.clone(), version))?;
+            batch.delete::<StaleJmtValues>(&(stale_since_version, key, version))?;
+        }
+
+        db.write_schemas(batch)
+    }
 }
 
 impl<Q: QueryManager> StateDB<Q> {
@@ -66,9 +122,32 @@ impl<Q: QueryManager> StateDB<Q> {
         Ok(Self {
             db: Arc::new(db_snapshot),
             next_version: Arc::new(Mutex::new(next_version)),
+            record_stale: false,
         })
     }
 
+    /// Index JMT nodes that were replaced by the latest tree update.
+    /// Does nothing unless [`StateDB::record_stale_versions`] was called.
+    pub fn put_stale_nodes(&self, stale_nodes: &StaleNodeIndexBatch) -> anyhow::Result<()> {
+        if !self.record_stale {
+            return Ok(());
+        }
+        let mut batch = SchemaBatch::new();
+        for index in stale_nodes {
+            batch.put::<StaleJmtNodes>(index, &())?;
+        }
+        self.db.write_many(batch)?;
+        Ok(())
+    }
+
+    /// The oldest version that can be read, versions below it might have been pruned.
+    pub fn get_earliest_available_version(&self) -> anyhow::Result<Version> {
+        Ok(self
+            .db
+            .read::<EarliestStateVersion>(&())?
+            .unwrap_or_default())
+    }
+
     /// Put the preimage of a hashed key into the database. Note that the preimage is not checked for correctness,
     /// since the DB is unaware of the hash function used by the JMT.
     pub fn put_preimages<'a>(
@@ -173,6 +252,18 @@ impl<Q: QueryManager> TreeWriter for StateDB<Q> {
                     .ok_or(anyhow::format_err!(
                         "Could not find preimage for key hash {key_hash:?}. Has `StateDB::put_preimage` been called for this key?"
                     ))?;
+            if self.record_stale {
+                if let Some(((found_key, found_version), _)) =
+                    self.db.get_prev::<JmtValues>(&(&key_preimage, *version))?
+                {
+                    if found_key == key_preimage && found_version < *version {
+                        batch.put::<StaleJmtValues>(
+                            &(*version, key_preimage.clone(), found_version),
+                            &(),
+                        )?;
+                    }
+                }
+            }
             batch.put::<JmtValues>(&(key_preimage, *version), value)?;
         }
         self.db.write_many(batch)?;
@@ -195,6 +286,7 @@ mod state_db_tests {
     use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, ReadOnlyLock};
 
     use super::StateDB;
+    use crate::schema::tables::{EarliestStateVersion, JmtValues};
 
     #[test]
     fn test_simple() {
@@ -216,4 +308,49 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+
+    #[test]
+    fn test_prune_overwritten_values() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let schema_db = StateDB::<NoopQueryManager>::setup_schema_db(tmpdir.path()).unwrap();
+
+        let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let db_snapshot = DbSnapshot::<NoopQueryManager>::new(0, manager);
+        let db = StateDB::with_db_snapshot(db_snapshot)
+            .unwrap()
+            .record_stale_versions();
+        let key_hash = KeyHash([1u8; 32]);
+        let key = vec![2u8; 100];
+
+        db.put_preimages(vec![(key_hash, &key)]).unwrap();
+        for version in 1..=2 {
+            let mut batch = NodeBatch::default();
+            batch.extend(
+                vec![],
+                vec![((version, key_hash), Some(vec![version as u8]))],
+            );
+            db.write_node_batch(&batch).unwrap();
+        }
+        schema_db
+            .write_schemas(db.freeze().unwrap().into())
+            .unwrap();
+
+        // Value written at version 1 is still needed to read version 1
+        StateDB::<NoopQueryManager>::prune(&schema_db, 1).unwrap();
+        assert!(schema_db
+            .get::<JmtValues>(&(key.clone(), 1))
+            .unwrap()
+            .is_some());
+
+        StateDB::<NoopQueryManager>::prune(&schema_db, 2).unwrap();
+        assert!(schema_db
+            .get::<JmtValues>(&(key.clone(), 1))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            schema_db.get::<JmtValues>(&(key, 2)).unwrap(),
+            Some(Some(vec![2]))
+        );
+        assert_eq!(schema_db.get::<EarliestStateVersion>(&()).unwrap(), Some(2));
+    }
 }
@@ -6,8 +6,10 @@ use jsonrpsee::{RpcModule, SubscriptionMessage};
 use serde::de::DeserializeOwned;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier,
+    SoftBatchNotification, TxIdentifier,
 };
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::HexHash;
 
@@ -276,6 +278,64 @@ where
         },
     )?;
 
+    rpc.register_subscription(
+        "citrea_subscribeSoftBatches",
+        "citrea_softBatch",
+        "citrea_unsubscribeSoftBatches",
+        |_, pending_subscription, db| async move {
+            let mut rx = db
+                .subscribe_soft_batches()
+                .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))?;
+
+            let subscription = pending_subscription.accept().await?;
+            let closed = subscription.closed();
+            futures::pin_mut!(closed);
+
+            // The next soft batch number the subscriber expects. Unknown until the first
+            // notification arrives, since only batches committed after subscribing are streamed.
+            let mut next_height: Option<u64> = None;
+
+            loop {
+                let next_msg = rx.recv();
+                futures::pin_mut!(next_msg);
+                match futures::future::select(closed, next_msg).await {
+                    Either::Left(_) => break Ok(()),
+                    Either::Right((outcome, channel_closing_future)) => {
+                        let latest_height = match outcome {
+                            Ok(height) => height,
+                            // If we fell behind the channel, catch up from the database so that
+                            // the subscriber does not observe a gap.
+                            Err(RecvError::Lagged(_)) => db
+                                .get_head_soft_batch_height()
+                                .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))?,
+                            Err(RecvError::Closed) => break Ok(()),
+                        };
+
+                        let start_height = next_height.unwrap_or(latest_height);
+                        for height in start_height..=latest_height {
+                            let Some(soft_batch) = db
+                                .get_soft_batch_by_number::<Tx>(height)
+                                .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))?
+                            else {
+                                break;
+                            };
+                            let msg = SubscriptionMessage::from_json(&SoftBatchNotification {
+                                height,
+                                soft_batch,
+                            })?;
+                            // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                            if subscription.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        next_height = Some(start_height.max(latest_height + 1));
+                        closed = channel_closing_future;
+                    }
+                }
+            }
+        },
+    )?;
+
     rpc.register_async_method("ledger_getHeadSoftBatch", |_, ledger| async move {
         ledger
             .get_head_soft_batch()
@@ -288,6 +348,18 @@ where
             .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))
     })?;
 
+    rpc.register_async_method("citrea_getLastCommittedL2Height", |_, ledger| async move {
+        ledger
+            .get_last_committed_l2_height()
+            .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))
+    })?;
+
+    rpc.register_async_method("citrea_getLastProvenL2Height", |_, ledger| async move {
+        ledger
+            .get_last_proven_l2_height()
+            .map_err(|e| to_jsonrpsee_error_object(LEDGER_RPC_ERROR, e))
+    })?;
+
     Ok(rpc)
 }
 
@@ -1,7 +1,7 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
 use jsonrpsee::core::params::ArrayParams;
 use sov_db::ledger_db::LedgerDB;
 use sov_ledger_rpc::client::RpcClient;
@@ -110,6 +110,21 @@ async fn subscribe_slots_succeeds() {
     rpc_client.subscribe_slots().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn subscribe_soft_batches_succeeds() {
+    let (_server_handle, addr) = rpc_server().await;
+    let rpc_client = rpc_client(addr).await;
+
+    rpc_client
+        .subscribe::<serde_json::Value, _>(
+            "citrea_subscribeSoftBatches",
+            ArrayParams::new(),
+            "citrea_unsubscribeSoftBatches",
+        )
+        .await
+        .unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_head_with_optional_query_mode() {
     let (_server_handle, addr) = rpc_server().await;
@@ -8,11 +8,14 @@ use sov_db::state_db::StateDB;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock, SnapshotId};
+use sov_state::config::PruningMode;
 use sov_state::{MerkleProofSpec, ProverStorage};
 use tracing::{debug, trace};
 
+use crate::pruner::Pruner;
 pub use crate::snapshot_manager::SnapshotManager;
 
+mod pruner;
 mod snapshot_manager;
 
 /// Implementation of [`HierarchicalStorageManager`] that handles relation between snapshots
@@ -40,6 +43,9 @@ pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     state_snapshot_manager: Arc<RwLock<SnapshotManager>>,
     accessory_snapshot_manager: Arc<RwLock<SnapshotManager>>,
 
+    // Set only in pruned mode, receives heights as they are finalized
+    pruner: Option<Pruner>,
+
     phantom_mp_spec: PhantomData<S>,
 }
 
@@ -64,6 +70,7 @@ where
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
+            pruner: None,
             phantom_mp_spec: Default::default(),
         }
     }
@@ -74,7 +81,40 @@ where
         let state_db = StateDB::<SnapshotManager>::setup_schema_db(&path)?;
         let native_db = NativeDB::<SnapshotManager>::setup_schema_db(&path)?;
 
-        Ok(Self::with_db_handles(state_db, native_db))
+        let mut storage_manager = Self::with_db_handles(state_db, native_db);
+        if let PruningMode::Pruned { retain_l2_heights } = config.pruning {
+            storage_manager.start_pruner(retain_l2_heights)?;
+        }
+        Ok(storage_manager)
+    }
+
+    fn start_pruner(&mut self, retain_l2_heights: u64) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            retain_l2_heights > 0,
+            "Pruned storage must retain at least 1 L2 height"
+        );
+        let state_db = self.state_snapshot_manager.read().unwrap().db();
+        let native_db = self.accessory_snapshot_manager.read().unwrap().db();
+        self.pruner = Some(Pruner::spawn(state_db, native_db, retain_l2_heights)?);
+        Ok(())
+    }
+
+    /// Storage handles over the given snapshots. In pruned mode they index
+    /// everything they overwrite, so the pruner can find it later.
+    fn db_handles(
+        &self,
+        state_db_snapshot: DbSnapshot<SnapshotManager>,
+        native_db_snapshot: DbSnapshot<SnapshotManager>,
+    ) -> anyhow::Result<(StateDB<SnapshotManager>, NativeDB<SnapshotManager>)> {
+        let state_db = StateDB::with_db_snapshot(state_db_snapshot)?;
+        let native_db = NativeDB::with_db_snapshot(native_db_snapshot)?;
+        if self.pruner.is_some() {
+            return Ok((
+                state_db.record_stale_versions(),
+                native_db.record_stale_versions(),
+            ));
+        }
+        Ok((state_db, native_db))
     }
 
     #[cfg(test)]
@@ -96,35 +136,100 @@ where
             ReadOnlyLock::new(self.state_snapshot_manager.clone()),
         );
 
-        let state_db = StateDB::with_db_snapshot(state_db_snapshot)?;
-
         let native_db_snapshot = DbSnapshot::new(
             snapshot_id,
             ReadOnlyLock::new(self.accessory_snapshot_manager.clone()),
         );
 
-        let native_db = NativeDB::with_db_snapshot(native_db_snapshot)?;
+        let (state_db, native_db) = self.db_handles(state_db_snapshot, native_db_snapshot)?;
         Ok(ProverStorage::with_db_handles(state_db, native_db))
     }
 
     fn finalize_by_l2_height(&mut self, l2_block_height: u64) -> anyhow::Result<()> {
-        let snapshot_id = self
+        if !self
             .block_height_to_snapshot_id
-            .remove(&l2_block_height)
-            .ok_or(anyhow::anyhow!("Attempt to finalize non existing snapshot"))?;
+            .contains_key(&l2_block_height)
+        {
+            anyhow::bail!("Attempt to finalize non existing snapshot");
+        }
+
+        // Blocks below that are not finalized yet are finalized first,
+        // so that snapshots are committed in order
+        let mut heights: Vec<u64> = self
+            .block_height_to_snapshot_id
+            .keys()
+            .filter(|height| **height <= l2_block_height)
+            .copied()
+            .collect();
+        heights.sort_unstable();
 
         let mut state_manager = self.state_snapshot_manager.write().unwrap();
         let mut native_manager = self.accessory_snapshot_manager.write().unwrap();
         let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
-        snapshot_id_to_parent.remove(&snapshot_id);
 
-        // Return error here, as underlying database can return error
-        state_manager.commit_snapshot(&snapshot_id)?;
-        native_manager.commit_snapshot(&snapshot_id)?;
+        for height in heights {
+            let snapshot_id = self
+                .block_height_to_snapshot_id
+                .remove(&height)
+                .expect("Height is taken from the map");
+            snapshot_id_to_parent.remove(&snapshot_id);
+
+            if height != l2_block_height && !state_manager.contains_snapshot(&snapshot_id) {
+                debug!(
+                    "Dropping snapshot id={} of block at height={}, which was never saved",
+                    snapshot_id, height
+                );
+                continue;
+            }
+
+            // Return error here, as underlying database can return error
+            state_manager.commit_snapshot(&snapshot_id)?;
+            native_manager.commit_snapshot(&snapshot_id)?;
+
+            // Children read the committed data from the database now
+            snapshot_id_to_parent.retain(|_, parent| *parent != snapshot_id);
+        }
+
+        let head = self.saved_l2_snapshot_below(u64::MAX, &state_manager);
+        self.point_finalized_storages(&mut snapshot_id_to_parent, head);
+
+        if let Some(pruner) = &self.pruner {
+            pruner.notify_finalized(l2_block_height);
+        }
 
         Ok(())
     }
 
+    /// Snapshot of the highest l2 block below the given height that is saved but not finalized
+    fn saved_l2_snapshot_below(
+        &self,
+        l2_block_height: u64,
+        state_manager: &SnapshotManager,
+    ) -> Option<SnapshotId> {
+        self.block_height_to_snapshot_id
+            .iter()
+            .filter(|(height, snapshot_id)| {
+                **height < l2_block_height && state_manager.contains_snapshot(snapshot_id)
+            })
+            .max_by_key(|(height, _)| **height)
+            .map(|(_, snapshot_id)| *snapshot_id)
+    }
+
+    /// Makes 'finalized' storages read the given l2 snapshot before the database,
+    /// so that they see the blocks that are saved but not finalized yet.
+    fn point_finalized_storages(
+        &self,
+        snapshot_id_to_parent: &mut HashMap<SnapshotId, SnapshotId>,
+        head: Option<SnapshotId>,
+    ) {
+        for snapshot_id in self.orphaned_snapshots.iter() {
+            match head {
+                Some(head) => snapshot_id_to_parent.insert(*snapshot_id, head),
+                None => snapshot_id_to_parent.remove(snapshot_id),
+            };
+        }
+    }
+
     fn finalize_by_hash_pair(
         &mut self,
         prev_block_hash: Da::SlotHash,
@@ -215,6 +320,15 @@ where
             Some(snapshot_id) => *snapshot_id,
             None => {
                 let new_snapshot_id = self.latest_snapshot_id + 1;
+                // Builds on top of the previous block if it is not finalized yet
+                let parent_snapshot_id = {
+                    let state_manager = self.state_snapshot_manager.read().unwrap();
+                    self.saved_l2_snapshot_below(l2_block_height, &state_manager)
+                };
+                if let Some(parent_snapshot_id) = parent_snapshot_id {
+                    let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+                    snapshot_id_to_parent.insert(new_snapshot_id, parent_snapshot_id);
+                }
                 self.block_height_to_snapshot_id
                     .insert(l2_block_height, new_snapshot_id);
                 self.latest_snapshot_id = new_snapshot_id;
@@ -233,6 +347,37 @@ where
         self.finalize_by_l2_height(l2_block_height)
     }
 
+    fn rollback_l2(&mut self, l2_block_height: u64) -> anyhow::Result<()> {
+        let mut state_manager = self.state_snapshot_manager.write().unwrap();
+        let mut native_manager = self.accessory_snapshot_manager.write().unwrap();
+        let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+
+        let heights: Vec<u64> = self
+            .block_height_to_snapshot_id
+            .keys()
+            .filter(|height| **height >= l2_block_height)
+            .copied()
+            .collect();
+        for height in heights {
+            let snapshot_id = self
+                .block_height_to_snapshot_id
+                .remove(&height)
+                .expect("Height is taken from the map");
+            debug!(
+                "Discarding snapshot={} of block at height={}",
+                snapshot_id, height
+            );
+            snapshot_id_to_parent.remove(&snapshot_id);
+            state_manager.discard_snapshot(&snapshot_id);
+            native_manager.discard_snapshot(&snapshot_id);
+        }
+
+        let head = self.saved_l2_snapshot_below(u64::MAX, &state_manager);
+        self.point_finalized_storages(&mut snapshot_id_to_parent, head);
+
+        Ok(())
+    }
+
     fn save_change_set_l2(
         &mut self,
         l2_block_height: u64,
@@ -258,6 +403,10 @@ where
                 "Discarded reference to 'finalized' snapshot={}",
                 snapshot_id
             );
+            self.snapshot_id_to_parent
+                .write()
+                .unwrap()
+                .remove(&snapshot_id);
             return Ok(());
         }
 
@@ -267,6 +416,10 @@ where
 
             state_manager.add_snapshot(state_snapshot);
             native_manager.add_snapshot(native_snapshot);
+
+            let head = self.saved_l2_snapshot_below(u64::MAX, &state_manager);
+            let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+            self.point_finalized_storages(&mut snapshot_id_to_parent, head);
         }
         debug!(
             "Snapshot id={} for block at height={} has been saved to StorageManager",
@@ -338,20 +491,25 @@ where
         let snapshot_id = self.latest_snapshot_id;
         debug!("Giving 'finalized' storage ref with id {}", snapshot_id);
         self.orphaned_snapshots.insert(snapshot_id);
+        {
+            let state_manager = self.state_snapshot_manager.read().unwrap();
+            if let Some(head) = self.saved_l2_snapshot_below(u64::MAX, &state_manager) {
+                let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+                snapshot_id_to_parent.insert(snapshot_id, head);
+            }
+        }
         let state_db_snapshot = DbSnapshot::new(
             snapshot_id,
             ReadOnlyLock::new(self.state_snapshot_manager.clone()),
         );
 
-        let state_db = StateDB::with_db_snapshot(state_db_snapshot)?;
-        state_db.max_out_next_version();
-
         let native_db_snapshot = DbSnapshot::new(
             snapshot_id,
             ReadOnlyLock::new(self.accessory_snapshot_manager.clone()),
         );
 
-        let native_db = NativeDB::with_db_snapshot(native_db_snapshot)?;
+        let (state_db, native_db) = self.db_handles(state_db_snapshot, native_db_snapshot)?;
+        state_db.max_out_next_version();
         Ok(ProverStorage::with_db_handles(state_db, native_db))
     }
 
@@ -386,6 +544,10 @@ where
                 "Discarded reference to 'finalized' snapshot={}",
                 snapshot_id
             );
+            self.snapshot_id_to_parent
+                .write()
+                .unwrap()
+                .remove(&snapshot_id);
             return Ok(());
         }
 
@@ -442,6 +604,7 @@ pub fn new_orphan_storage<S: MerkleProofSpec>(
 mod tests {
     use sov_mock_da::{MockBlockHeader, MockHash};
     use sov_rollup_interface::da::Time;
+    use sov_state::storage::NativeStorage;
     use sov_state::storage::{CacheKey, CacheValue};
     use sov_state::{ArrayWitness, OrderedReadsAndWrites, Storage};
 
@@ -1358,4 +1521,113 @@ mod tests {
             storage_last.get_accessory(&key_from(3).into(), None)
         );
     }
+
+    fn save_l2_block(storage_manager: &mut ProverStorageManager<Da, S>, height: u64, value: u64) {
+        let witness = ArrayWitness::default();
+        let storage = storage_manager.create_storage_on_l2_height(height).unwrap();
+        let mut state_operations = OrderedReadsAndWrites::default();
+        state_operations.ordered_writes.push(write_op(1, value));
+        let mut native_operations = OrderedReadsAndWrites::default();
+        native_operations
+            .ordered_writes
+            .push(write_op(1, value * 10));
+        let (_, state_update, _) = storage
+            .compute_state_update(state_operations, &witness)
+            .unwrap();
+        storage.commit(&state_update, &native_operations);
+        storage_manager.save_change_set_l2(height, storage).unwrap();
+    }
+
+    #[test]
+    fn unfinalized_l2_blocks() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        let finalized_storage = storage_manager.create_finalized_storage().unwrap();
+        let witness = ArrayWitness::default();
+
+        let assert_value = |storage: &ProverStorage<S, SnapshotManager>, value: u64| {
+            assert_eq!(
+                Some(value_from(value).into()),
+                storage.get(&key_from(1).into(), None, &witness)
+            );
+            assert_eq!(
+                Some(value_from(value * 10).into()),
+                storage.get_accessory(&key_from(1).into(), None)
+            );
+        };
+
+        // Blocks are built on top of each other before they are finalized
+        for height in 1..=3 {
+            save_l2_block(&mut storage_manager, height, height);
+        }
+        let storage = storage_manager.create_storage_on_l2_height(4).unwrap();
+        assert_value(&storage, 3);
+        // 'Finalized' storage sees the latest saved block
+        assert_value(&finalized_storage, 3);
+
+        storage_manager.rollback_l2(2).unwrap();
+        assert_value(&finalized_storage, 1);
+        let storage = storage_manager.create_storage_on_l2_height(2).unwrap();
+        assert_value(&storage, 1);
+
+        save_l2_block(&mut storage_manager, 2, 4);
+        assert_value(&finalized_storage, 4);
+
+        // Finalizing a block finalizes the ones below it too
+        storage_manager.finalize_l2(2).unwrap();
+        assert!(storage_manager.block_height_to_snapshot_id.is_empty());
+        assert!(storage_manager
+            .snapshot_id_to_parent
+            .read()
+            .unwrap()
+            .is_empty());
+        assert!(storage_manager
+            .state_snapshot_manager
+            .read()
+            .unwrap()
+            .is_empty());
+        assert_value(&finalized_storage, 4);
+    }
+
+    #[test]
+    fn pruned_l2_blocks() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        storage_manager.start_pruner(2).unwrap();
+
+        for height in 1..=5 {
+            save_l2_block(&mut storage_manager, height, height);
+            storage_manager.finalize_l2(height).unwrap();
+        }
+
+        let expected_earliest = pruner::earliest_version_to_keep(5, 2);
+        let storage = storage_manager.create_storage_on_l2_height(6).unwrap();
+        // Pruning happens in the background
+        for _ in 0..100 {
+            if storage.earliest_available_version() == expected_earliest {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(storage.earliest_available_version(), expected_earliest);
+
+        assert!(storage.get_root_hash(expected_earliest).is_ok());
+        assert!(storage.get_root_hash(expected_earliest - 1).is_err());
+        assert_eq!(
+            Some(value_from(5).into()),
+            storage.get(&key_from(1).into(), None, &ArrayWitness::default())
+        );
+        assert_eq!(
+            Some(value_from(50).into()),
+            storage.get_accessory(&key_from(1).into(), None)
+        );
+    }
 }
@@ -0,0 +1,71 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use sov_db::native_db::NativeDB;
+use sov_db::state_db::StateDB;
+use tracing::{debug, error};
+
+use crate::SnapshotManager;
+
+/// Removes state versions that fell out of the retention window.
+/// Runs in a separate thread, so finalization is not slowed down by pruning.
+pub(crate) struct Pruner {
+    finalized_heights: Sender<u64>,
+}
+
+impl Pruner {
+    /// Spawns the pruning thread, which lives as long as the returned [`Pruner`]
+    pub(crate) fn spawn(
+        state_db: Arc<sov_schema_db::DB>,
+        native_db: Arc<sov_schema_db::DB>,
+        retain_l2_heights: u64,
+    ) -> anyhow::Result<Self> {
+        let (finalized_heights, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("state-pruner".to_string())
+            .spawn(move || run(receiver, &state_db, &native_db, retain_l2_heights))?;
+        Ok(Self { finalized_heights })
+    }
+
+    /// Lets the pruner know that all L2 blocks up to `l2_height` are committed to the database
+    pub(crate) fn notify_finalized(&self, l2_height: u64) {
+        if self.finalized_heights.send(l2_height).is_err() {
+            error!("State pruner has stopped, state at L2 height {l2_height} won't be pruned");
+        }
+    }
+}
+
+/// The oldest JMT version that has to stay readable after `l2_height` is finalized.
+/// State after L2 block `h` is stored at JMT version `h + 1`.
+pub(crate) fn earliest_version_to_keep(l2_height: u64, retain_l2_heights: u64) -> u64 {
+    (l2_height + 2).saturating_sub(retain_l2_heights)
+}
+
+fn run(
+    receiver: Receiver<u64>,
+    state_db: &sov_schema_db::DB,
+    native_db: &sov_schema_db::DB,
+    retain_l2_heights: u64,
+) {
+    while let Ok(mut l2_height) = receiver.recv() {
+        // Only the latest finalized height matters if pruning falls behind
+        while let Ok(next_height) = receiver.try_recv() {
+            l2_height = next_height;
+        }
+
+        let earliest_version = earliest_version_to_keep(l2_height, retain_l2_heights);
+        if earliest_version == 0 {
+            continue;
+        }
+        debug!(
+            "Pruning state below version {} after L2 height {} is finalized",
+            earliest_version, l2_height
+        );
+
+        if let Err(e) = StateDB::<SnapshotManager>::prune(state_db, earliest_version)
+            .and_then(|_| NativeDB::<SnapshotManager>::prune(native_db, earliest_version))
+        {
+            error!("Failed to prune state below version {earliest_version}: {e:?}");
+        }
+    }
+}
@@ -15,7 +15,7 @@ use crate::snapshot_manager::DataLocation::Snapshot;
 /// down to DB level
 /// Managed externally by [`crate::ProverStorageManager`]
 pub struct SnapshotManager {
-    db: sov_schema_db::DB,
+    db: Arc<sov_schema_db::DB>,
     snapshots: HashMap<SnapshotId, ReadOnlyDbSnapshot>,
     /// Hierarchical
     to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
@@ -27,7 +27,7 @@ impl SnapshotManager {
         to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
     ) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent,
         }
@@ -37,12 +37,17 @@ impl SnapshotManager {
     /// So it only reads from database.
     pub fn orphan(db: sov_schema_db::DB) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent: Arc::new(RwLock::new(Default::default())),
         }
     }
 
+    /// Handle to the underlying database, which holds only finalized data
+    pub(crate) fn db(&self) -> Arc<sov_schema_db::DB> {
+        self.db.clone()
+    }
+
     pub(crate) fn add_snapshot(&mut self, snapshot: ReadOnlyDbSnapshot) {
         let snapshot_id = snapshot.get_id();
         if self.snapshots.insert(snapshot_id, snapshot).is_some() {
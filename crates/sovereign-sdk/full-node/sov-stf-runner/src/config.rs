@@ -3,20 +3,44 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use shared_backup_db::SharedBackupDbConfig;
+pub use sov_state::config::PruningMode;
 
 use crate::ProverGuestRunConfig;
 
 /// Runner configuration.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct RunnerConfig {
-    /// Sequencer client configuration.
-    pub sequencer_client_url: String,
+    /// Upstream L2 sources to sync soft batches from, in order of preference.
+    /// Usually the sequencer first, followed by trusted full nodes to fail over to.
+    /// A single URL is accepted too, also under the former `sequencer_client_url` key.
+    #[serde(
+        default,
+        alias = "sequencer_client_url",
+        deserialize_with = "deserialize_urls"
+    )]
+    pub sequencer_client_urls: Vec<String>,
     /// Saves sequencer soft batches if set to true
     pub include_tx_body: bool,
     /// Only true for tests
     pub accept_public_input_as_proven: Option<bool>,
+    /// Reconstruct the state only from verified ZK proofs and their state diffs found on DA,
+    /// without syncing soft batches from upstreams
+    pub reconstruct_from_da: Option<bool>,
+}
+
+impl RunnerConfig {
+    /// Checks that a node syncing soft batches has at least one upstream to sync them from.
+    /// Only a node reconstructing its state from DA can go without.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.sequencer_client_urls.is_empty() && !self.reconstruct_from_da.unwrap_or(false) {
+            anyhow::bail!(
+                "At least one sequencer client URL is required unless reconstruct_from_da is set"
+            );
+        }
+        Ok(())
+    }
 }
 
 /// RPC configuration.
@@ -39,6 +63,16 @@ pub struct RpcConfig {
     /// Maximum number of batch requests
     #[serde(default = "default_batch_requests_limit")]
     pub batch_requests_limit: u32,
+    /// Maximum number of filters a connection can install with `eth_newFilter`,
+    /// `eth_newBlockFilter` and `eth_newPendingTransactionFilter`
+    #[serde(default = "default_max_filters_per_connection")]
+    pub max_filters_per_connection: usize,
+    /// Maximum number of filters all connections together can install
+    #[serde(default = "default_max_filters")]
+    pub max_filters: usize,
+    /// Filters that are not polled for this many seconds are uninstalled
+    #[serde(default = "default_filter_idle_timeout_secs")]
+    pub filter_idle_timeout_secs: u64,
 }
 
 #[inline]
@@ -61,16 +95,50 @@ const fn default_batch_requests_limit() -> u32 {
     50
 }
 
+#[inline]
+const fn default_max_filters_per_connection() -> usize {
+    100
+}
+
+#[inline]
+const fn default_max_filters() -> usize {
+    10_000
+}
+
+#[inline]
+const fn default_filter_idle_timeout_secs() -> u64 {
+    // Same as geth
+    5 * 60
+}
+
 #[inline]
 const fn default_sync_blocks_count() -> u64 {
     10
 }
 
+/// Deserializes either a single URL or a list of URLs
+fn deserialize_urls<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Urls {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Urls::deserialize(deserializer)? {
+        Urls::One(url) => vec![url],
+        Urls::Many(urls) => urls,
+    })
+}
+
 /// Simple storage configuration
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct StorageConfig {
     /// Path that can be utilized by concrete rollup implementation
     pub path: PathBuf,
+    /// Archive (default) or pruned retention of historical state
+    #[serde(default)]
+    pub pruning: PruningMode,
 }
 
 /// Important public keys for the rollup
@@ -170,6 +238,7 @@ mod tests {
             bind_host = "127.0.0.1"
             bind_port = 12345
             max_connections = 500
+            max_filters = 500
 
             [da]
             sender_address = "0000000000000000000000000000000000000000000000000000000000000000"
@@ -180,7 +249,7 @@ mod tests {
             
             [runner]
             include_tx_body = true
-            sequencer_client_url = "http://0.0.0.0:12346"
+            sequencer_client_urls = ["http://0.0.0.0:12346", "http://0.0.0.0:12347"]
         "#.to_owned();
 
         let config_file = create_config_from(&config);
@@ -190,9 +259,13 @@ mod tests {
 
         let expected = FullNodeConfig {
             runner: Some(RunnerConfig {
-                sequencer_client_url: "http://0.0.0.0:12346".to_owned(),
+                sequencer_client_urls: vec![
+                    "http://0.0.0.0:12346".to_owned(),
+                    "http://0.0.0.0:12347".to_owned(),
+                ],
                 include_tx_body: true,
                 accept_public_input_as_proven: None,
+                reconstruct_from_da: None,
             }),
             da: sov_mock_da::MockDaConfig {
                 sender_address: [0; 32].into(),
@@ -200,6 +273,7 @@ mod tests {
             },
             storage: StorageConfig {
                 path: "/tmp/rollup".into(),
+                pruning: PruningMode::Archive,
             },
             rpc: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
@@ -208,6 +282,9 @@ mod tests {
                 max_request_body_size: 10 * 1024 * 1024,
                 max_response_body_size: 10 * 1024 * 1024,
                 batch_requests_limit: 50,
+                max_filters_per_connection: 100,
+                max_filters: 500,
+                filter_idle_timeout_secs: 300,
             },
             public_keys: RollupPublicKeys {
                 sequencer_public_key: vec![0; 32],
@@ -219,6 +296,75 @@ mod tests {
         assert_eq!(config, expected);
     }
 
+    #[test]
+    fn test_runner_config_with_single_sequencer_client_url() {
+        let config = r#"
+            include_tx_body = true
+            sequencer_client_url = "http://0.0.0.0:12346"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        let expected = RunnerConfig {
+            sequencer_client_urls: vec!["http://0.0.0.0:12346".to_owned()],
+            include_tx_body: true,
+            accept_public_input_as_proven: None,
+            reconstruct_from_da: None,
+        };
+        assert_eq!(config, expected);
+
+        let config = r#"
+            include_tx_body = true
+            sequencer_client_urls = "http://0.0.0.0:12346"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_runner_config_requires_upstream() {
+        let mut config = RunnerConfig {
+            sequencer_client_urls: vec![],
+            include_tx_body: false,
+            accept_public_input_as_proven: None,
+            reconstruct_from_da: None,
+        };
+        assert!(config.validate().is_err());
+
+        config.reconstruct_from_da = Some(true);
+        assert!(config.validate().is_ok());
+
+        config.reconstruct_from_da = None;
+        config.sequencer_client_urls = vec!["http://0.0.0.0:12346".to_owned()];
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pruned_storage_config() {
+        let config = r#"
+            path = "/tmp/rollup"
+
+            [pruning]
+            mode = "pruned"
+            retain_l2_heights = 1000
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: StorageConfig = from_toml_path(config_file.path()).unwrap();
+        let expected = StorageConfig {
+            path: "/tmp/rollup".into(),
+            pruning: PruningMode::Pruned {
+                retain_l2_heights: 1000,
+            },
+        };
+        assert_eq!(config, expected);
+    }
+
     #[test]
     fn test_correct_prover_config() {
         let config = r#"
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::da::{DaSpec, ForcedTransaction};
 use sov_rollup_interface::stf::{BatchReceipt, SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{CumulativeStateDiff, ValidityCondition, Zkvm};
 
@@ -70,6 +70,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _witness: Self::Witness,
         _slot_header: &<Da as DaSpec>::BlockHeader,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        _forced_transactions: &[ForcedTransaction],
         _soft_batch: &mut sov_modules_api::SignedSoftConfirmationBatch,
     ) -> SlotResult<
         Self::StateRoot,
@@ -90,6 +91,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _da_data: Vec<<Da as DaSpec>::BlobTransaction>,
         _witnesses: std::collections::VecDeque<Vec<Self::Witness>>,
         _slot_headers: std::collections::VecDeque<Vec<<Da as DaSpec>::BlockHeader>>,
+        _forced_transactions: std::collections::VecDeque<Vec<Vec<ForcedTransaction>>>,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
         _soft_confirmations: std::collections::VecDeque<
             Vec<sov_modules_api::SignedSoftConfirmationBatch>,
@@ -20,7 +20,7 @@ use sov_db::schema::types::{BatchNumber, SlotNumber, StoredSoftBatch, StoredStat
 use sov_modules_api::{Context, SignedSoftConfirmationBatch};
 use sov_modules_stf_blueprint::StfBlueprintTrait;
 use sov_rollup_interface::da::{
-    BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, SequencerCommitment,
+    BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, ForcedTransaction, SequencerCommitment,
 };
 use sov_rollup_interface::rpc::SoftConfirmationStatus;
 use sov_rollup_interface::services::da::{DaService, SlotData};
@@ -165,7 +165,7 @@ where
             state_root: prev_state_root,
             rpc_config,
             prover_service,
-            sequencer_client: SequencerClient::new(runner_config.sequencer_client_url),
+            sequencer_client: SequencerClient::new(runner_config.sequencer_client_urls),
             sequencer_pub_key: public_keys.sequencer_public_key,
             sequencer_da_pub_key: public_keys.sequencer_da_pub_key,
             prover_da_pub_key: public_keys.prover_da_pub_key,
@@ -334,9 +334,12 @@ where
                     } else if tx.sender().as_ref() == self.prover_da_pub_key.as_slice() {
                         // The prover doesn't really care about proofs in DA blocks.
                         // They've already been proven so we can skip here.
-                    } else {
-                        warn!("Force transactions are not implemented yet");
-                        // TODO: This is where force transactions will land - try to parse DA data force transaction
+                    } else if let Ok(DaData::ForcedTransaction(_)) = data {
+                        // Applied with the soft confirmations built on this block
+                        debug!(
+                            "Found forced transaction in block 0x{}",
+                            hex::encode(filtered_block.hash())
+                        );
                     }
                 });
 
@@ -382,6 +385,9 @@ where
             let mut da_block_headers_of_soft_confirmations: VecDeque<
                 Vec<<<Da as DaService>::Spec as DaSpec>::BlockHeader>,
             > = VecDeque::new();
+            let mut da_data_of_soft_confirmations = VecDeque::new();
+            let mut inclusion_proofs_of_soft_confirmations = VecDeque::new();
+            let mut completeness_proofs_of_soft_confirmations = VecDeque::new();
 
             let mut traversed_l1_tuples = vec![];
 
@@ -391,6 +397,10 @@ where
                 let mut da_block_headers_to_push: Vec<
                     <<Da as DaService>::Spec as DaSpec>::BlockHeader,
                 > = vec![];
+                let mut da_data_to_push = vec![];
+                let mut inclusion_proofs_to_push = vec![];
+                let mut completeness_proofs_to_push = vec![];
+                let mut forced_transactions = vec![];
 
                 let start_l1_height = retry_backoff(exponential_backoff.clone(), || async {
                     da_service
@@ -496,6 +506,21 @@ where
                             != filtered_block.header().height()
                     {
                         da_block_headers_to_push.push(filtered_block.header().clone());
+
+                        let mut da_data_of_block =
+                            da_service.extract_relevant_blobs(&filtered_block);
+                        let (inclusion_proof, completeness_proof) = da_service
+                            .get_extraction_proof(&filtered_block, &da_data_of_block)
+                            .await;
+                        // the zk circuit reads the forced transactions from the verified data
+                        da_data_of_block.iter_mut().for_each(|blob| {
+                            blob.full_data();
+                        });
+                        forced_transactions = ForcedTransaction::from_blobs(&da_data_of_block);
+
+                        da_data_to_push.push(da_data_of_block);
+                        inclusion_proofs_to_push.push(inclusion_proof);
+                        completeness_proofs_to_push.push(completeness_proof);
                     }
 
                     let mut data_to_commit = SlotCommit::new(filtered_block.clone());
@@ -512,6 +537,7 @@ where
                         Default::default(),
                         filtered_block.header(),
                         &filtered_block.validity_condition(),
+                        &forced_transactions,
                         &mut signed_soft_confirmation,
                     );
 
@@ -570,6 +596,9 @@ where
                 soft_confirmations.push_back(sof_soft_confirmations_to_push);
                 state_transition_witnesses.push_back(state_transition_witnesses_to_push);
                 da_block_headers_of_soft_confirmations.push_back(da_block_headers_to_push);
+                da_data_of_soft_confirmations.push_back(da_data_to_push);
+                inclusion_proofs_of_soft_confirmations.push_back(inclusion_proofs_to_push);
+                completeness_proofs_of_soft_confirmations.push_back(completeness_proofs_to_push);
             }
 
             let hash = da_block_header_of_commitments.hash();
@@ -585,6 +614,9 @@ where
                     soft_confirmations,
                     state_transition_witnesses,
                     da_block_headers_of_soft_confirmations,
+                    da_data_of_soft_confirmations,
+                    inclusion_proofs_of_soft_confirmations,
+                    completeness_proofs_of_soft_confirmations,
 
                     sequencer_public_key: self.sequencer_pub_key.clone(),
                     sequencer_da_public_key: self.sequencer_da_pub_key.clone(),
@@ -727,6 +759,7 @@ where
     pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
         let mut last_l1_height = 0;
         let mut cur_l1_block = None;
+        let mut forced_transactions = Vec::<ForcedTransaction>::new();
 
         let mut height = self.start_height;
         info!("Starting to sync from height {}", height);
@@ -801,12 +834,17 @@ where
 
                     let mut sequencer_commitments = Vec::<SequencerCommitment>::new();
                     let mut zk_proofs = Vec::<Proof>::new();
+                    forced_transactions.clear();
 
                     self.da_service
                         .extract_relevant_blobs(&filtered_block)
                         .into_iter()
                         .for_each(|mut tx| {
                             let data = DaData::try_from_slice(tx.full_data());
+                            // Forced transactions can be sent by anyone
+                            if let Ok(DaData::ForcedTransaction(forced_tx)) = &data {
+                                forced_transactions.push(forced_tx.clone());
+                            }
                             // Check for commitment
                             if tx.sender().as_ref() == self.sequencer_da_pub_key.as_slice() {
                                 match data {
@@ -845,9 +883,6 @@ where
                                         );
                                     }
                                 }
-                            } else {
-                                warn!("Force transactions are not implemented yet");
-                                // TODO: This is where force transactions will land - try to parse DA data force transaction
                             }
                         });
 
@@ -1149,6 +1184,7 @@ where
                     Default::default(),
                     cur_l1_block.header(),
                     &cur_l1_block.validity_condition(),
+                    &forced_transactions,
                     &mut soft_batch.clone().into(),
                 );
 
@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
-use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier};
+use std::collections::VecDeque;
+
+use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier, ForcedTransaction};
 use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::zk::{StateTransition, StateTransitionData, Zkvm, ZkvmGuest};
 
@@ -58,6 +60,50 @@ where
             "Invalid initial state root"
         );
 
+        // Forced transactions can only be trusted if all relevant DA data of the block is proven
+        let mut forced_transactions = VecDeque::with_capacity(data.soft_confirmations.len());
+        for (((da_block_headers, da_data), inclusion_proofs), completeness_proofs) in data
+            .da_block_headers_of_soft_confirmations
+            .iter()
+            .zip(data.da_data_of_soft_confirmations)
+            .zip(data.inclusion_proofs_of_soft_confirmations)
+            .zip(data.completeness_proofs_of_soft_confirmations)
+        {
+            assert_eq!(
+                da_block_headers.len(),
+                da_data.len(),
+                "Every DA block header must come with its DA data"
+            );
+            assert_eq!(
+                inclusion_proofs.len(),
+                da_data.len(),
+                "Every DA data must come with its inclusion proof"
+            );
+            assert_eq!(
+                completeness_proofs.len(),
+                da_data.len(),
+                "Every DA data must come with its completeness proof"
+            );
+
+            let mut forced_transactions_of_blocks = Vec::with_capacity(da_block_headers.len());
+            for (((da_block_header, da_data), inclusion_proof), completeness_proof) in
+                da_block_headers
+                    .iter()
+                    .zip(da_data)
+                    .zip(inclusion_proofs)
+                    .zip(completeness_proofs)
+            {
+                self.da_verifier.verify_relevant_tx_list(
+                    da_block_header,
+                    &da_data,
+                    inclusion_proof,
+                    completeness_proof,
+                )?;
+                forced_transactions_of_blocks.push(ForcedTransaction::from_blobs(&da_data));
+            }
+            forced_transactions.push_back(forced_transactions_of_blocks);
+        }
+
         println!("going into apply_soft_confirmations_from_sequencer_commitments");
         let (final_state_root, state_diff) = self
             .app
@@ -69,6 +115,7 @@ where
                 data.da_data,
                 data.state_transition_witnesses,
                 data.da_block_headers_of_soft_confirmations,
+                forced_transactions,
                 &validity_condition,
                 data.soft_confirmations,
             );
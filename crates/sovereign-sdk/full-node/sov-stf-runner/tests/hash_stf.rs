@@ -6,7 +6,7 @@ use sov_mock_zkvm::MockZkvm;
 use sov_modules_api::Context;
 use sov_modules_stf_blueprint::StfBlueprintTrait;
 use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
-use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec, ForcedTransaction};
 use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{CumulativeStateDiff, ValidityCondition, Zkvm};
 use sov_state::storage::{NativeStorage, StorageKey, StorageValue};
@@ -75,6 +75,7 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
         _pre_state: Self::PreState,
         _witness: <<C as sov_modules_api::Spec>::Storage as Storage>::Witness,
         _slot_header: &<Da as DaSpec>::BlockHeader,
+        _forced_transactions: &[ForcedTransaction],
         _soft_batch: &mut sov_modules_api::SignedSoftConfirmationBatch,
     ) -> (
         Result<(), sov_modules_api::hooks::ApplySoftConfirmationError>,
@@ -83,6 +84,10 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
         unimplemented!()
     }
 
+    fn valid_forced_txs(&self, _forced_transactions: &[ForcedTransaction]) -> Vec<Vec<u8>> {
+        unimplemented!()
+    }
+
     fn apply_soft_batch_txs(
         &self,
         _txs: Vec<Vec<u8>>,
@@ -127,6 +132,14 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
     > {
         unimplemented!()
     }
+
+    fn apply_state_diff(
+        &self,
+        _pre_state: Self::PreState,
+        _state_diff: CumulativeStateDiff,
+    ) -> anyhow::Result<(Self::StateRoot, Self::ChangeSet)> {
+        unimplemented!()
+    }
 }
 
 impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm, Da>
@@ -210,6 +223,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _witness: Self::Witness,
         _slot_header: &<Da as DaSpec>::BlockHeader,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        _forced_transactions: &[ForcedTransaction],
         _soft_batch: &mut sov_modules_api::SignedSoftConfirmationBatch,
     ) -> SlotResult<
         Self::StateRoot,
@@ -230,6 +244,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         _da_data: Vec<<Da as DaSpec>::BlobTransaction>,
         _witnesses: std::collections::VecDeque<Vec<Self::Witness>>,
         _slot_headers: std::collections::VecDeque<Vec<<Da as DaSpec>::BlockHeader>>,
+        _forced_transactions: std::collections::VecDeque<Vec<Vec<ForcedTransaction>>>,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
         _soft_confirmations: std::collections::VecDeque<
             Vec<sov_modules_api::SignedSoftConfirmationBatch>,
@@ -222,6 +222,9 @@ fn make_transition_data(
         soft_confirmations: VecDeque::new(),
         state_transition_witnesses: VecDeque::new(),
         da_block_headers_of_soft_confirmations: VecDeque::new(),
+        da_data_of_soft_confirmations: VecDeque::new(),
+        inclusion_proofs_of_soft_confirmations: VecDeque::new(),
+        completeness_proofs_of_soft_confirmations: VecDeque::new(),
         sequencer_public_key: vec![],
         sequencer_da_public_key: vec![],
     }
@@ -70,6 +70,7 @@ fn initialize_runner(
     let rollup_config = FullNodeConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: rollup_storage_path.clone(),
+            pruning: Default::default(),
         },
         rpc: RpcConfig {
             bind_host: "127.0.0.1".to_string(),
@@ -78,11 +79,15 @@ fn initialize_runner(
             max_request_body_size: 10 * 1024 * 1024,
             max_response_body_size: 10 * 1024 * 1024,
             batch_requests_limit: 50,
+            max_filters_per_connection: 100,
+            max_filters: 10_000,
+            filter_idle_timeout_secs: 300,
         },
         runner: Some(RunnerConfig {
-            sequencer_client_url: "http://127.0.0.1:4444".to_string(),
+            sequencer_client_urls: vec!["http://127.0.0.1:4444".to_string()],
             include_tx_body: true,
             accept_public_input_as_proven: None,
+            reconstruct_from_da: None,
         }),
         da: MockDaConfig {
             sender_address: address,
@@ -104,6 +109,7 @@ fn initialize_runner(
 
     let storage_config = sov_state::config::Config {
         path: rollup_storage_path.to_path_buf(),
+        pruning: Default::default(),
     };
     let storage_manager = ProverStorageManager::new(storage_config).unwrap();
 
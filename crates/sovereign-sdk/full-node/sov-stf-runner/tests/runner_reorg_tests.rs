@@ -121,6 +121,7 @@ async fn runner_execution(
     let rollup_config = FullNodeConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: rollup_storage_path.clone(),
+            pruning: Default::default(),
         },
         rpc: RpcConfig {
             bind_host: "127.0.0.1".to_string(),
@@ -129,11 +130,15 @@ async fn runner_execution(
             max_request_body_size: 10 * 1024 * 1024,
             max_response_body_size: 10 * 1024 * 1024,
             batch_requests_limit: 50,
+            max_filters_per_connection: 100,
+            max_filters: 10_000,
+            filter_idle_timeout_secs: 300,
         },
         runner: Some(RunnerConfig {
-            sequencer_client_url: "http://127.0.0.1:4444".to_string(),
+            sequencer_client_urls: vec!["http://127.0.0.1:4444".to_string()],
             include_tx_body: true,
             accept_public_input_as_proven: None,
+            reconstruct_from_da: None,
         }),
         da: MockDaConfig {
             sender_address: da_service.get_sequencer_address(),
@@ -153,6 +158,7 @@ async fn runner_execution(
 
     let storage_config = sov_state::config::Config {
         path: rollup_storage_path,
+        pruning: Default::default(),
     };
     let mut storage_manager = ProverStorageManager::new(storage_config).unwrap();
 
@@ -200,6 +206,7 @@ fn get_saved_root_hash(
 ) -> anyhow::Result<Option<<ProverStorage<S, Q> as Storage>::Root>> {
     let storage_config = sov_state::config::Config {
         path: path.to_path_buf(),
+        pruning: Default::default(),
     };
     let mut storage_manager = ProverStorageManager::<MockDaSpec, S>::new(storage_config).unwrap();
     let finalized_storage = storage_manager.create_finalized_storage()?;
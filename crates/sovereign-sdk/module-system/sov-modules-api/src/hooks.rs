@@ -40,6 +40,12 @@ pub enum ApplySoftConfirmationError {
         prev
     )]
     CurrentTimestampIsNotGreaterThanPrev { current: u64, prev: u64 },
+    #[error(
+        "Forced transaction {:?} had to be included by L2 block {}",
+        tx_hash,
+        deadline
+    )]
+    ForcedTransactionNotIncluded { tx_hash: [u8; 32], deadline: u64 },
 }
 
 /// Hooks that execute within the `StateTransitionFunction::apply_blob` function for each processed transaction.
@@ -112,7 +118,8 @@ pub trait ApplySoftConfirmationHooks<Da: DaSpec> {
 }
 
 /// Information about the soft confirmation block
-/// Does not include txs because txs can be appended by the sequencer
+/// Txs can be appended by the sequencer after the hook runs,
+/// so only the forced transactions are guaranteed to be in `txs` when the sequencer builds the block
 #[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize, Eq)]
 pub struct HookSoftConfirmationInfo {
     /// DA block this soft confirmation was given for
@@ -131,6 +138,10 @@ pub struct HookSoftConfirmationInfo {
     pub l1_fee_rate: u128,
     /// Timestamp
     pub timestamp: u64,
+    /// Transactions of the soft confirmation
+    pub txs: Vec<Vec<u8>>,
+    /// Valid forced transactions found in the DA block
+    pub forced_txs: Vec<Vec<u8>>,
 }
 
 impl From<SignedSoftConfirmationBatch> for HookSoftConfirmationInfo {
@@ -144,6 +155,8 @@ impl From<SignedSoftConfirmationBatch> for HookSoftConfirmationInfo {
             deposit_data: signed_soft_confirmation_batch.deposit_data(),
             l1_fee_rate: signed_soft_confirmation_batch.l1_fee_rate(),
             timestamp: signed_soft_confirmation_batch.timestamp(),
+            txs: signed_soft_confirmation_batch.txs(),
+            forced_txs: vec![],
         }
     }
 }
@@ -157,7 +170,7 @@ impl From<HookSoftConfirmationInfo> for SignedSoftConfirmationBatch {
             val.da_slot_txs_commitment(),
             val.pre_state_root(),
             val.l1_fee_rate,
-            vec![],
+            val.txs,
             val.deposit_data,
             vec![],
             val.pub_key.clone(),
@@ -203,6 +216,14 @@ impl HookSoftConfirmationInfo {
     pub fn timestamp(&self) -> u64 {
         self.timestamp
     }
+
+    pub fn txs(&self) -> &[Vec<u8>] {
+        &self.txs
+    }
+
+    pub fn forced_txs(&self) -> &[Vec<u8>] {
+        &self.forced_txs
+    }
 }
 
 /// Hooks that execute during the `StateTransitionFunction::begin_slot` and `end_slot` functions.
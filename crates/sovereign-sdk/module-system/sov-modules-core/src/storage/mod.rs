@@ -285,6 +285,13 @@ pub trait Storage: Clone {
     /// Indicates if storage is empty or not.
     /// Useful during initialization.
     fn is_empty(&self) -> bool;
+
+    /// Returns the oldest version whose state can still be read.
+    ///
+    /// Storages keep every version unless they override this method, so the default is `0`.
+    fn earliest_available_version(&self) -> Version {
+        0
+    }
 }
 
 /// Used only in tests.
@@ -312,6 +319,14 @@ pub trait NativeStorage: Storage {
     /// get the value.
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof>;
 
+    /// Returns the value corresponding to the key at the given version, or None if key is absent
+    /// at that version, and a proof of it against the root hash of that version.
+    fn get_with_proof_at_version(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> Result<StorageProof<Self::Proof>, anyhow::Error>;
+
     /// Get the root hash of the tree at the requested version
     fn get_root_hash(&self, version: Version) -> Result<Self::Root, anyhow::Error>;
 }
@@ -376,6 +376,11 @@ impl<C: Context> WorkingSet<C> {
         self.archival_accessory_working_set = Some(self.archival_accessory_state(version));
     }
 
+    /// Returns the oldest version the archival version can be set to, older versions are pruned.
+    pub fn earliest_available_version(&self) -> Version {
+        self.delta.inner.inner.earliest_available_version()
+    }
+
     /// Unset archival version
     pub fn unset_archival_version(&mut self) {
         self.archival_working_set = None;
@@ -459,6 +464,30 @@ impl<C: Context> WorkingSet<C> {
         // First inner is `RevertableWriter` and second inner is actually a `Storage` instance
         self.delta.inner.inner.get_with_proof(key)
     }
+
+    /// Fetches given value at the given version and provides a proof of it presence/absence
+    /// against the root hash of that version.
+    pub fn get_with_proof_at_version(
+        &mut self,
+        key: StorageKey,
+        version: Version,
+    ) -> anyhow::Result<StorageProof<<C::Storage as Storage>::Proof>>
+    where
+        C::Storage: NativeStorage,
+    {
+        self.delta
+            .inner
+            .inner
+            .get_with_proof_at_version(key, version)
+    }
+
+    /// Returns the root hash of the state at the given version.
+    pub fn get_root_hash(&self, version: Version) -> anyhow::Result<<C::Storage as Storage>::Root>
+    where
+        C::Storage: NativeStorage,
+    {
+        self.delta.inner.inner.get_root_hash(version)
+    }
 }
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
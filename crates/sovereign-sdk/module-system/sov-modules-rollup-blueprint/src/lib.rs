@@ -16,7 +16,7 @@ use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{
-    FullNodeConfig, InitVariant, ProverConfig, ProverService, StateTransitionRunner,
+    FullNodeConfig, InitVariant, ProverConfig, ProverService, RpcConfig, StateTransitionRunner,
 };
 use tokio::sync::oneshot;
 use tracing::{instrument, Instrument};
@@ -72,7 +72,8 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         storage: &<Self::NativeContext as Spec>::Storage,
         ledger_db: &LedgerDB,
         da_service: &Self::DaService,
-        sequencer_client_url: Option<String>,
+        rpc_config: &RpcConfig,
+        sequencer_client_urls: Option<Vec<String>>,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error>;
 
     /// Creates GenesisConfig from genesis files.
@@ -161,7 +162,8 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             &prover_storage,
             &ledger_db,
             &da_service,
-            Some(runner_config.sequencer_client_url.clone()),
+            &rollup_config.rpc,
+            Some(runner_config.sequencer_client_urls.clone()),
         )?;
 
         let native_stf = StfBlueprint::new();
@@ -19,13 +19,15 @@ use sov_modules_api::{
     native_debug, native_warn, BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall,
     Genesis, Signature, Spec, StateCheckpoint, UnsignedSoftConfirmationBatch, WorkingSet, Zkvm,
 };
-use sov_rollup_interface::da::{DaData, SequencerCommitment};
+use sov_rollup_interface::da::{DaData, ForcedTransaction, SequencerCommitment};
 use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::maybestd::RefCount;
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 pub use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::CumulativeStateDiff;
-use sov_state::Storage;
+use sov_state::storage::{CacheKey, CacheValue};
+use sov_state::{OrderedReadsAndWrites, Storage};
 pub use stf_blueprint::StfBlueprint;
 pub use tx_verifier::RawTx;
 
@@ -127,6 +129,7 @@ pub trait StfBlueprintTrait<C: Context, Da: DaSpec, Vm: Zkvm>:
     StateTransitionFunction<Vm, Da>
 {
     /// Begin a soft batch
+    #[allow(clippy::too_many_arguments)]
     fn begin_soft_batch(
         &self,
         sequencer_public_key: &[u8],
@@ -134,9 +137,14 @@ pub trait StfBlueprintTrait<C: Context, Da: DaSpec, Vm: Zkvm>:
         pre_state: Self::PreState,
         witness: <<C as Spec>::Storage as Storage>::Witness,
         slot_header: &<Da as DaSpec>::BlockHeader,
+        forced_transactions: &[ForcedTransaction],
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> (Result<(), ApplySoftConfirmationError>, WorkingSet<C>);
 
+    /// Forced transactions that the sequencer has to include.
+    /// Forced transactions that could not be applied as soft confirmation transactions are skipped.
+    fn valid_forced_txs(&self, forced_transactions: &[ForcedTransaction]) -> Vec<Vec<u8>>;
+
     /// Apply soft batch transactions
     fn apply_soft_batch_txs(
         &self,
@@ -167,6 +175,14 @@ pub trait StfBlueprintTrait<C: Context, Da: DaSpec, Vm: Zkvm>:
         Self::TxReceiptContents,
         Self::Witness,
     >;
+
+    /// Applies a proven state diff on top of `pre_state` without executing any transactions.
+    /// Used to reconstruct the rollup state from ZK proofs published on DA.
+    fn apply_state_diff(
+        &self,
+        pre_state: Self::PreState,
+        state_diff: CumulativeStateDiff,
+    ) -> anyhow::Result<(Self::StateRoot, Self::ChangeSet)>;
 }
 
 impl<C, RT, Vm, Da> StfBlueprintTrait<C, Da, Vm> for StfBlueprint<C, Da, Vm, RT>
@@ -183,6 +199,7 @@ where
         pre_state: <C>::Storage,
         witness: <<C as Spec>::Storage as Storage>::Witness,
         slot_header: &<Da as DaSpec>::BlockHeader,
+        forced_transactions: &[ForcedTransaction],
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> (Result<(), ApplySoftConfirmationError>, WorkingSet<C>) {
         native_debug!("Applying soft batch in STF Blueprint");
@@ -217,7 +234,13 @@ where
 
         let checkpoint = StateCheckpoint::with_witness(pre_state, witness);
 
-        self.begin_soft_confirmation_inner(checkpoint, soft_batch)
+        let forced_txs = self.valid_forced_txs(forced_transactions);
+
+        self.begin_soft_confirmation_inner(checkpoint, forced_txs, soft_batch)
+    }
+
+    fn valid_forced_txs(&self, forced_transactions: &[ForcedTransaction]) -> Vec<Vec<u8>> {
+        self.filter_forced_txs(forced_transactions)
     }
 
     fn apply_soft_batch_txs(
@@ -336,6 +359,34 @@ where
             state_diff,
         }
     }
+
+    fn apply_state_diff(
+        &self,
+        pre_state: <C>::Storage,
+        state_diff: CumulativeStateDiff,
+    ) -> anyhow::Result<(<C::Storage as Storage>::Root, <C>::Storage)> {
+        let state_accesses = OrderedReadsAndWrites {
+            ordered_reads: vec![],
+            ordered_writes: state_diff
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        CacheKey {
+                            key: RefCount::new(key),
+                        },
+                        value.map(|value| CacheValue {
+                            value: RefCount::new(value),
+                        }),
+                    )
+                })
+                .collect(),
+        };
+
+        let witness = <<C as Spec>::Storage as Storage>::Witness::default();
+        let state_root = pre_state.validate_and_commit(state_accesses, &witness)?;
+
+        Ok((state_root, pre_state))
+    }
 }
 
 impl<C, RT, Vm, Da> StateTransitionFunction<Vm, Da> for StfBlueprint<C, Da, Vm, RT>
@@ -421,6 +472,7 @@ where
         witness: Self::Witness,
         slot_header: &<Da as DaSpec>::BlockHeader,
         _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        forced_transactions: &[ForcedTransaction],
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> SlotResult<
         Self::StateRoot,
@@ -435,6 +487,7 @@ where
             pre_state.clone(),
             witness,
             slot_header,
+            forced_transactions,
             soft_batch,
         ) {
             (Ok(()), batch_workspace) => {
@@ -476,13 +529,14 @@ where
         da_data: Vec<<Da as DaSpec>::BlobTransaction>,
         witnesses: std::collections::VecDeque<Vec<Self::Witness>>,
         slot_headers: std::collections::VecDeque<Vec<<Da as DaSpec>::BlockHeader>>,
+        forced_transactions: std::collections::VecDeque<Vec<Vec<ForcedTransaction>>>,
         validity_condition: &<Da as DaSpec>::ValidityCondition,
         soft_confirmations: std::collections::VecDeque<Vec<SignedSoftConfirmationBatch>>,
     ) -> (Self::StateRoot, CumulativeStateDiff) {
         let mut state_diff = CumulativeStateDiff::default();
 
         // First extract all sequencer commitments
-        // Ignore broken DaData and zk proofs. Also ignore ForcedTransaction's (will be implemented in the future).
+        // Ignore broken DaData, zk proofs and forced transactions. Forced transactions are read from the DA blocks of the soft confirmations.
         let mut sequencer_commitments: Vec<SequencerCommitment> = vec![];
         for blob in da_data {
             // TODO: get sequencer da pub key
@@ -499,13 +553,16 @@ where
 
         let mut current_state_root = initial_state_root.clone();
 
-        // should panic if number of sequencer commitments, soft confirmations, slot headers and witnesses don't match
-        for (((sequencer_commitment, soft_confirmations), da_block_headers), witnesses) in
-            sequencer_commitments
-                .into_iter()
-                .zip_eq(soft_confirmations)
-                .zip_eq(slot_headers)
-                .zip_eq(witnesses)
+        // should panic if number of sequencer commitments, soft confirmations, slot headers, forced transactions and witnesses don't match
+        for (
+            (((sequencer_commitment, soft_confirmations), da_block_headers), forced_transactions),
+            witnesses,
+        ) in sequencer_commitments
+            .into_iter()
+            .zip_eq(soft_confirmations)
+            .zip_eq(slot_headers)
+            .zip_eq(forced_transactions)
+            .zip_eq(witnesses)
         {
             // we must verify given DA headers match the commitments
             let mut index_headers = 0;
@@ -600,15 +657,21 @@ where
                 "Invalid merkle root"
             );
 
-            let mut da_block_headers_iter = da_block_headers.into_iter().peekable();
-            let mut da_block_header = da_block_headers_iter.next().unwrap();
+            // every DA block header must come with its forced transactions
+            let mut da_block_headers_iter = da_block_headers
+                .into_iter()
+                .zip_eq(forced_transactions)
+                .peekable();
+            let (mut da_block_header, mut da_block_forced_transactions) =
+                da_block_headers_iter.next().unwrap();
 
             // now that we verified the claimed root, we can apply the soft confirmations
             // should panic if the number of witnesses and soft confirmations don't match
             for (mut soft_confirmation, witness) in soft_confirmations.into_iter().zip_eq(witnesses)
             {
                 if soft_confirmation.da_slot_height() != da_block_header.height() {
-                    da_block_header = da_block_headers_iter.next().unwrap();
+                    (da_block_header, da_block_forced_transactions) =
+                        da_block_headers_iter.next().unwrap();
                 }
 
                 let result = self.apply_soft_batch(
@@ -618,6 +681,7 @@ where
                     witness,
                     &da_block_header,
                     validity_condition,
+                    &da_block_forced_transactions,
                     &mut soft_confirmation,
                 );
 
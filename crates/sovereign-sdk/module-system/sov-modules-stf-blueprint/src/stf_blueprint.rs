@@ -5,6 +5,7 @@ use sov_modules_api::hooks::{ApplySoftConfirmationError, HookSoftConfirmationInf
 use sov_modules_api::{
     native_debug, native_error, Context, DaSpec, DispatchCall, StateCheckpoint, WorkingSet,
 };
+use sov_rollup_interface::da::ForcedTransaction;
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
@@ -154,6 +155,7 @@ where
     pub fn begin_soft_confirmation_inner(
         &self,
         checkpoint: StateCheckpoint<C>,
+        forced_txs: Vec<Vec<u8>>,
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> (Result<(), ApplySoftConfirmationError>, WorkingSet<C>) {
         native_debug!(
@@ -164,11 +166,14 @@ where
 
         let mut batch_workspace = checkpoint.to_revertable();
 
+        let mut soft_confirmation_info = HookSoftConfirmationInfo::from(soft_batch.clone());
+        soft_confirmation_info.forced_txs = forced_txs;
+
         // ApplySoftConfirmationHook: begin
-        if let Err(e) = self.runtime.begin_soft_confirmation_hook(
-            &mut HookSoftConfirmationInfo::from(soft_batch.clone()),
-            &mut batch_workspace,
-        ) {
+        if let Err(e) = self
+            .runtime
+            .begin_soft_confirmation_hook(&mut soft_confirmation_info, &mut batch_workspace)
+        {
             native_error!(
                 "Error: The batch was rejected by the 'begin_soft_confirmation_hook'. Skipping batch with error: {}",
                 e
@@ -223,9 +228,10 @@ where
     pub(crate) fn _apply_soft_confirmation_inner(
         &self,
         checkpoint: StateCheckpoint<C>,
+        forced_txs: Vec<Vec<u8>>,
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> (ApplySoftConfirmationResult, StateCheckpoint<C>) {
-        match self.begin_soft_confirmation_inner(checkpoint, soft_batch) {
+        match self.begin_soft_confirmation_inner(checkpoint, forced_txs, soft_batch) {
             (Ok(()), batch_workspace) => {
                 // TODO: wait for txs here, apply_sov_txs can be called multiple times
                 let (batch_workspace, tx_receipts) =
@@ -248,6 +254,23 @@ where
         .expect("Sequencer must not include non-deserializable transaction.")
     }
 
+    // Forced transactions which would not pass `verify_txs_stateless_soft` and `decode_txs`
+    // can't be included by the sequencer, so they are dropped.
+    pub(crate) fn filter_forced_txs(&self, forced_txs: &[ForcedTransaction]) -> Vec<Vec<u8>> {
+        forced_txs
+            .iter()
+            .filter(|forced_tx| {
+                verify_txs_stateless::<C>(vec![RawTx {
+                    data: forced_tx.tx.clone(),
+                }])
+                .ok()
+                .and_then(|txs| self.decode_txs(&txs).ok())
+                .is_some()
+            })
+            .map(|forced_tx| forced_tx.tx.clone())
+            .collect()
+    }
+
     // Checks that runtime message can be decoded from transaction.
     // If a single message cannot be decoded, sequencer is slashed
     fn decode_txs(
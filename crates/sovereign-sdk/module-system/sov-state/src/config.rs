@@ -8,4 +8,22 @@ use std::path::PathBuf;
 pub struct Config {
     /// Path to folder where storage files will be stored.
     pub path: PathBuf,
+    /// Whether historical state versions are kept forever or pruned.
+    #[serde(default)]
+    pub pruning: PruningMode,
+}
+
+/// Retention policy for historical state versions.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum PruningMode {
+    /// Every state version is kept, so state can be queried at any height.
+    #[default]
+    Archive,
+    /// Only the state of the last `retain_l2_heights` finalized L2 blocks is kept.
+    /// Older JMT nodes, values and accessory values are removed in the background.
+    Pruned {
+        /// Number of most recent L2 heights whose state stays queryable.
+        retain_l2_heights: u64,
+    },
 }
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use jmt::storage::{NodeBatch, TreeWriter};
+use jmt::storage::{NodeBatch, StaleNodeIndexBatch, TreeWriter};
 use jmt::{JellyfishMerkleTree, KeyHash, Version};
 use sov_db::native_db::NativeDB;
 use sov_db::schema::{QueryManager, ReadOnlyDbSnapshot};
@@ -70,6 +70,7 @@ impl<S: MerkleProofSpec, Q: QueryManager> ProverStorage<S, Q> {
 
 pub struct ProverStateUpdate {
     pub(crate) node_batch: NodeBatch,
+    pub(crate) stale_node_index_batch: StaleNodeIndexBatch,
     pub key_preimages: Vec<(KeyHash, CacheKey)>,
 }
 
@@ -163,6 +164,7 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
 
         let state_update = ProverStateUpdate {
             node_batch: tree_update.node_batch,
+            stale_node_index_batch: tree_update.stale_node_index_batch,
             key_preimages,
         };
 
@@ -193,6 +195,10 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
             )
             .expect("native db write must succeed");
 
+        self.db
+            .put_stale_nodes(&state_update.stale_node_index_batch)
+            .expect("db write must succeed");
+
         // Write the state values last, since we base our view of what has been touched
         // on state. If the node crashes between the `native_db` update and this update,
         // then the whole `commit` will be re-run later so no data can be lost.
@@ -219,22 +225,33 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
     fn is_empty(&self) -> bool {
         self.db.get_next_version() <= 1
     }
+
+    fn earliest_available_version(&self) -> Version {
+        self.db
+            .get_earliest_available_version()
+            .expect("db read must succeed")
+    }
 }
 
 impl<S: MerkleProofSpec, Q: QueryManager> NativeStorage for ProverStorage<S, Q> {
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof> {
+        self.get_with_proof_at_version(key, self.db.get_next_version() - 1)
+            .unwrap()
+    }
+
+    fn get_with_proof_at_version(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> anyhow::Result<StorageProof<Self::Proof>> {
         let merkle = JellyfishMerkleTree::<StateDB<Q>, S::Hasher>::new(&self.db);
-        let (val_opt, proof) = merkle
-            .get_with_proof(
-                KeyHash::with::<S::Hasher>(key.as_ref()),
-                self.db.get_next_version() - 1,
-            )
-            .unwrap();
-        StorageProof {
+        let (val_opt, proof) =
+            merkle.get_with_proof(KeyHash::with::<S::Hasher>(key.as_ref()), version)?;
+        Ok(StorageProof {
             key,
             value: val_opt.map(StorageValue::from),
             proof,
-        }
+        })
     }
 
     fn get_root_hash(&self, version: Version) -> anyhow::Result<jmt::RootHash> {
@@ -219,6 +219,15 @@ pub struct SoftBatchResponse {
     pub timestamp: u64,
 }
 
+/// A soft batch pushed to `citrea_subscribeSoftBatches` subscribers.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct SoftBatchNotification {
+    /// The L2 height of the soft batch.
+    pub height: u64,
+    /// The soft batch itself.
+    pub soft_batch: SoftBatchResponse,
+}
+
 /// The response to a JSON-RPC request for sequencer commitments on a DA Slot.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct SequencerCommitmentResponse {
@@ -590,6 +599,11 @@ pub trait LedgerRpcProvider {
     /// Get a notification each time a slot is processed
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 
+    /// Get a notification with the soft batch number each time a soft batch is committed
+    fn subscribe_soft_batches(
+        &self,
+    ) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
+
     /// Get proof by l1 height
     fn get_proof_data_by_l1_height(
         &self,
@@ -607,6 +621,12 @@ pub trait LedgerRpcProvider {
 
     /// Get head soft batch height
     fn get_head_soft_batch_height(&self) -> Result<u64, anyhow::Error>;
+
+    /// Get the highest L2 height covered by a sequencer commitment on the DA layer
+    fn get_last_committed_l2_height(&self) -> Result<Option<u64>, anyhow::Error>;
+
+    /// Get the highest L2 height covered by a verified ZK proof
+    fn get_last_proven_l2_height(&self) -> Result<Option<u64>, anyhow::Error>;
 }
 
 /// JSON-RPC -related utilities. Occasionally useful but unimportant for most
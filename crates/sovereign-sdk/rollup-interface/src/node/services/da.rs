@@ -23,6 +23,23 @@ pub struct BlobWithNotifier<TxID> {
     pub notify: OneshotSender<Result<TxID, anyhow::Error>>,
 }
 
+/// An event of the stream returned by [`DaService::subscribe_finalized_header`].
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FinalizedHeaderEvent<H> {
+    /// The block with this header was finalized.
+    Finalized(H),
+    /// Blocks that were already reported as finalized are not on the best fork anymore,
+    /// i.e. the DA layer reorged deeper than its finality depth.
+    /// The blocks of the new fork are reported as [`FinalizedHeaderEvent::Finalized`] afterwards.
+    Reorg {
+        /// The last reported block that is still on the best fork.
+        common_ancestor: H,
+        /// The reported blocks that were reorged out, in ascending order of height.
+        reorged: Vec<H>,
+    },
+}
+
 /// A DaService is the local side of an RPC connection talking to a node of the DA layer
 /// It is *not* part of the logic that is zk-proven.
 ///
@@ -43,9 +60,9 @@ pub trait DaService: Send + Sync + 'static {
         Cond = <Self::Spec as DaSpec>::ValidityCondition,
     >;
 
-    /// Type that allow to consume [`futures::Stream`] of BlockHeaders.
+    /// Type that allow to consume [`futures::Stream`] of finalized BlockHeaders and reorgs.
     type HeaderStream: futures::Stream<
-        Item = Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error>,
+        Item = Result<FinalizedHeaderEvent<<Self::Spec as DaSpec>::BlockHeader>, Self::Error>,
     >;
 
     /// A transaction ID, used to identify the transaction in the DA layer.
@@ -72,6 +89,8 @@ pub trait DaService: Send + Sync + 'static {
     /// Subscribe to finalized headers as they are finalized.
     /// Expect only to receive headers which were finalized after subscription
     /// Optimized version of `get_last_finalized_block_header`.
+    /// If finalized blocks that were already received are reorged out,
+    /// a [`FinalizedHeaderEvent::Reorg`] is received before the headers of the new fork.
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error>;
 
     /// Fetch the head block of the most popular fork.
@@ -131,6 +150,10 @@ pub trait DaService: Send + Sync + 'static {
         unimplemented!()
     }
 
+    /// Stops the queue of [`DaService::get_send_transaction_queue`] from accepting new blobs
+    /// and waits until the blobs that were already queued are sent.
+    async fn close_send_transaction_queue(&self) {}
+
     /// Sends am aggregated ZK proofs to the DA layer.
     async fn send_aggregated_zk_proof(
         &self,
@@ -6,6 +6,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::vec::Vec;
 use crate::zk::{Proof, ValidityCondition};
 use crate::BasicAddress;
 
@@ -21,7 +22,30 @@ pub struct SequencerCommitment {
     pub l1_end_block_hash: [u8; 32],
 }
 
-/// Data written to DA can only be one of these two types
+/// A transaction published to the DA layer by any user.
+/// The sequencer has to include it in one of the first L2 blocks built on the DA block it is found in,
+/// so users can get their transactions in even if the sequencer censors them.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct ForcedTransaction {
+    /// Serialized rollup transaction, in the same format as the transactions of a soft confirmation
+    pub tx: Vec<u8>,
+}
+
+impl ForcedTransaction {
+    /// Reads the forced transactions out of the relevant blobs of a DA block.
+    /// Only the verified data of the blobs is read, so in native mode the blobs have to be fully read beforehand.
+    pub fn from_blobs<B: BlobReaderTrait>(blobs: &[B]) -> Vec<ForcedTransaction> {
+        blobs
+            .iter()
+            .filter_map(|blob| match DaData::try_from_slice(blob.verified_data()) {
+                Ok(DaData::ForcedTransaction(forced_tx)) => Some(forced_tx),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Data written to DA can only be one of these types
 /// Data written to DA and read from DA is must be borsh serialization of this enum
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, BorshDeserialize, BorshSerialize)]
 pub enum DaData {
@@ -29,6 +53,8 @@ pub enum DaData {
     SequencerCommitment(SequencerCommitment),
     /// Or a zk proof and state diff
     ZKProof(Proof),
+    /// Or a transaction the sequencer is forced to include
+    ForcedTransaction(ForcedTransaction),
 }
 
 /// A specification for the types used by a DA layer.
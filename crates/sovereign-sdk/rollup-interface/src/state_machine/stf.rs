@@ -11,7 +11,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::da::DaSpec;
+use crate::da::{DaSpec, ForcedTransaction};
 use crate::maybestd::vec::Vec;
 use crate::soft_confirmation::SignedSoftConfirmationBatch;
 use crate::zk::{CumulativeStateDiff, ValidityCondition, Zkvm};
@@ -214,6 +214,9 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
     /// The concrete blob type is defined by the DA layer implementation,
     /// which is why we use a generic here instead of an associated type.
     ///
+    /// `forced_transactions` are the forced transactions found in the DA block of `slot_header`,
+    /// the sequencer has to include them in the next L2 blocks.
+    ///
     /// Commits state changes to the database
     #[allow(clippy::type_complexity)]
     #[allow(clippy::too_many_arguments)]
@@ -225,6 +228,7 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
         witness: Self::Witness,
         slot_header: &Da::BlockHeader,
         validity_condition: &Da::ValidityCondition,
+        forced_transactions: &[ForcedTransaction],
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> SlotResult<
         Self::StateRoot,
@@ -236,6 +240,7 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
 
     /// Runs a vector of Soft Confirmations
     /// Used for proving the L2 block state transitions
+    /// `forced_transactions` has the forced transactions of each DA block in `slot_headers`
     // TODO: don't use tuple as return type.
     #[allow(clippy::type_complexity)]
     #[allow(clippy::too_many_arguments)]
@@ -248,6 +253,7 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
         da_data: Vec<<Da as DaSpec>::BlobTransaction>,
         witnesses: VecDeque<Vec<Self::Witness>>,
         slot_headers: VecDeque<Vec<Da::BlockHeader>>,
+        forced_transactions: VecDeque<Vec<Vec<ForcedTransaction>>>,
         validity_condition: &Da::ValidityCondition,
         soft_confirmations: VecDeque<Vec<SignedSoftConfirmationBatch>>,
     ) -> (Self::StateRoot, CumulativeStateDiff);
@@ -27,6 +27,9 @@ pub trait HierarchicalStorageManager<Da: DaSpec> {
         change_set: Self::NativeChangeSet,
     ) -> anyhow::Result<()>;
 
+    /// Discards snapshots of the l2 blocks at given height and above, which are not finalized yet.
+    fn rollback_l2(&mut self, l2_block_height: u64) -> anyhow::Result<()>;
+
     /// Creates storage based on given Da block header,
     /// meaning that at will have access to previous blocks state in same fork.
     fn create_storage_on(
@@ -34,7 +37,8 @@ pub trait HierarchicalStorageManager<Da: DaSpec> {
         block_header: &Da::BlockHeader,
     ) -> anyhow::Result<Self::NativeStorage>;
 
-    /// Snapshots that points directly to finalized storage.
+    /// Snapshots that points directly to finalized storage,
+    /// and to the l2 blocks that are saved but not finalized yet.
     /// Won't be saved if somehow 'saved'
     fn create_finalized_storage(&mut self) -> anyhow::Result<Self::NativeStorage>;
 
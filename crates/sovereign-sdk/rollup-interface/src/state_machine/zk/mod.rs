@@ -178,6 +178,13 @@ pub struct StateTransitionData<StateRoot, Witness, Da: DaSpec> {
     pub state_transition_witnesses: VecDeque<Vec<Witness>>,
     /// DA block headers the soft confirmations was constructed on.
     pub da_block_headers_of_soft_confirmations: VecDeque<Vec<Da::BlockHeader>>,
+    /// Relevant blobs of the DA blocks the soft confirmations was constructed on.
+    /// Forced transactions are read from these.
+    pub da_data_of_soft_confirmations: VecDeque<Vec<Vec<Da::BlobTransaction>>>,
+    /// The inclusion proofs for the DA data of the soft confirmations.
+    pub inclusion_proofs_of_soft_confirmations: VecDeque<Vec<Da::InclusionMultiProof>>,
+    /// The completeness proofs for the DA data of the soft confirmations.
+    pub completeness_proofs_of_soft_confirmations: VecDeque<Vec<Da::CompletenessProof>>,
 
     /// Sequencer soft confirmation public key.
     pub sequencer_public_key: Vec<u8>,
@@ -13,7 +13,7 @@ use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{Address, AddressBech32, EncodeCall, PrivateKey, PublicKey, Spec};
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, DaVerifier, Time};
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, FinalizedHeaderEvent, SlotData};
 
 const DEFAULT_CHAIN_ID: u64 = 0;
 
@@ -61,7 +61,7 @@ impl DaSpec for RngDaSpec {
 pub struct RngHeaderStream;
 
 impl futures::Stream for RngHeaderStream {
-    type Item = anyhow::Result<<RngDaSpec as DaSpec>::BlockHeader>;
+    type Item = anyhow::Result<FinalizedHeaderEvent<<RngDaSpec as DaSpec>::BlockHeader>>;
 
     fn poll_next(
         self: std::pin::Pin<&mut Self>,